@@ -4,7 +4,7 @@ use sea_orm::entity::prelude::*;
 use serde::{Deserialize, Serialize};
 
 #[sea_orm::model]
-#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
 #[sea_orm(table_name = "answers")]
 pub struct Model {
     #[sea_orm(primary_key, auto_increment = false)]
@@ -13,6 +13,10 @@ pub struct Model {
     pub question_id: Uuid,
     pub value: String,
     pub is_correct: bool,
+    #[sea_orm(column_type = "JsonBinary", nullable)]
+    pub payload: Option<Json>,
+    pub image_id: Option<Uuid>,
+    pub translation_group_id: Uuid,
     #[sea_orm(
         belongs_to,
         from = "question_id",
@@ -21,8 +25,16 @@ pub struct Model {
         on_delete = "Cascade"
     )]
     pub questions: HasOne<super::questions::Entity>,
+    #[sea_orm(
+        belongs_to,
+        from = "image_id",
+        to = "id",
+        on_update = "Cascade",
+        on_delete = "SetNull"
+    )]
+    pub images: HasOne<super::images::Entity>,
     #[sea_orm(has_many)]
-    pub test_question_answers: HasMany<super::test_question_answers::Entity>,
+    pub question_reports: HasMany<super::question_reports::Entity>,
 }
 
 impl ActiveModelBehavior for ActiveModel {}
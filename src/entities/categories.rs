@@ -11,8 +11,21 @@ pub struct Model {
     #[serde(skip_deserializing)]
     pub id: Uuid,
     pub name: String,
+    pub parent_id: Option<Uuid>,
+    pub position: i32,
+    #[sea_orm(
+        self_ref,
+        relation_enum = "Parent",
+        from = "parent_id",
+        to = "id",
+        on_update = "Cascade",
+        on_delete = "SetNull"
+    )]
+    pub parent: HasOne<Entity>,
     #[sea_orm(has_many, via = "question_categories")]
     pub questions: HasMany<super::questions::Entity>,
+    #[sea_orm(has_many)]
+    pub category_translations: HasMany<super::category_translations::Entity>,
 }
 
 impl ActiveModelBehavior for ActiveModel {}
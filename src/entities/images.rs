@@ -14,6 +14,12 @@ pub struct Model {
     pub stored_name: String,
     pub mime_type: String,
     pub size: i64,
+    #[sea_orm(has_many)]
+    pub answers: HasMany<super::answers::Entity>,
+    #[sea_orm(has_many, via = "question_images")]
+    pub questions: HasMany<super::questions::Entity>,
+    #[sea_orm(has_many)]
+    pub road_signs: HasMany<super::road_signs::Entity>,
 }
 
 impl ActiveModelBehavior for ActiveModel {}
@@ -10,10 +10,20 @@ pub struct Model {
     #[sea_orm(primary_key, auto_increment = false)]
     #[serde(skip_deserializing)]
     pub id: Uuid,
-    #[sea_orm(unique)]
     pub topic_id: Uuid,
+    pub title: String,
+    pub position: i16,
+    pub reading_minutes: i16,
     #[sea_orm(column_type = "Text")]
     pub content: String,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub content_html: Option<String>,
+    pub status: String,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub review_comment: Option<String>,
+    pub valid_from: Option<Date>,
+    pub valid_to: Option<Date>,
+    pub region: Option<String>,
     #[sea_orm(
         belongs_to,
         from = "topic_id",
@@ -22,6 +32,16 @@ pub struct Model {
         on_delete = "Cascade"
     )]
     pub topics: HasOne<super::topics::Entity>,
+    #[sea_orm(has_many, via = "lesson_road_signs")]
+    pub road_signs: HasMany<super::road_signs::Entity>,
+    #[sea_orm(has_many)]
+    pub lesson_translations: HasMany<super::lesson_translations::Entity>,
+    #[sea_orm(has_many)]
+    pub lesson_sections: HasMany<super::lesson_sections::Entity>,
+    #[sea_orm(has_many)]
+    pub lesson_progress: HasMany<super::lesson_progress::Entity>,
+    #[sea_orm(has_many)]
+    pub topic_prerequisites: HasMany<super::topic_prerequisites::Entity>,
 }
 
 impl ActiveModelBehavior for ActiveModel {}
@@ -2,13 +2,42 @@
 
 pub mod answers;
 pub mod categories;
+pub mod category_translations;
+pub mod classroom_participants;
+pub mod classroom_session_options;
+pub mod classroom_session_questions;
+pub mod classroom_sessions;
+pub mod content_reviews;
+pub mod daily_challenge_questions;
+pub mod daily_challenges;
+pub mod duel_players;
+pub mod duels;
 pub mod images;
+pub mod lesson_progress;
+pub mod lesson_road_signs;
+pub mod lesson_section_translations;
+pub mod lesson_sections;
+pub mod lesson_translations;
 pub mod lessons;
+pub mod license_classes;
+pub mod notifications;
 pub mod question_categories;
+pub mod question_images;
+pub mod question_license_classes;
+pub mod question_reports;
+pub mod question_revisions;
+pub mod question_road_signs;
 pub mod questions;
+pub mod road_sign_translations;
+pub mod road_signs;
 pub mod test_question_answers;
+pub mod test_question_options;
 pub mod test_questions;
+pub mod test_template_questions;
+pub mod test_templates;
 pub mod tests;
+pub mod topic_prerequisites;
+pub mod topic_translations;
 pub mod topics;
 pub mod user_favorite_questions;
 pub mod user_subscriptions;
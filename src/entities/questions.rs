@@ -16,10 +16,19 @@ pub struct Model {
     #[sea_orm(column_type = "Text", nullable)]
     pub content: Option<String>,
     pub explanation: String,
+    pub points: i16,
+    pub status: String,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub review_comment: Option<String>,
+    #[sea_orm(unique)]
+    pub external_id: Option<String>,
+    pub question_type: String,
+    pub valid_from: Option<Date>,
+    pub valid_to: Option<Date>,
+    pub region: Option<String>,
+    pub translation_group_id: Uuid,
     #[sea_orm(has_many)]
     pub answers: HasMany<super::answers::Entity>,
-    #[sea_orm(has_many)]
-    pub test_question_answers: HasMany<super::test_question_answers::Entity>,
     #[sea_orm(
         belongs_to,
         from = "topic_id",
@@ -28,10 +37,22 @@ pub struct Model {
         on_delete = "Cascade"
     )]
     pub topics: HasOne<super::topics::Entity>,
+    #[sea_orm(has_many, via = "question_images")]
+    pub images: HasMany<super::images::Entity>,
+    #[sea_orm(has_many)]
+    pub question_revisions: HasMany<super::question_revisions::Entity>,
+    #[sea_orm(has_many)]
+    pub question_reports: HasMany<super::question_reports::Entity>,
+    #[sea_orm(has_many, via = "question_road_signs")]
+    pub road_signs: HasMany<super::road_signs::Entity>,
+    #[sea_orm(has_many, via = "question_license_classes")]
+    pub license_classes: HasMany<super::license_classes::Entity>,
     #[sea_orm(has_many, via = "question_categories")]
     pub categories: HasMany<super::categories::Entity>,
-    #[sea_orm(has_many, via = "test_questions")]
-    pub tests: HasMany<super::tests::Entity>,
+    #[sea_orm(has_many, via = "test_template_questions")]
+    pub test_templates: HasMany<super::test_templates::Entity>,
+    #[sea_orm(has_many, via = "daily_challenge_questions")]
+    pub daily_challenges: HasMany<super::daily_challenges::Entity>,
     #[sea_orm(has_many, via = "user_favorite_questions")]
     pub users: HasMany<super::users::Entity>,
 }
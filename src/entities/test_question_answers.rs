@@ -16,22 +16,6 @@ pub struct Model {
     #[sea_orm(primary_key, auto_increment = false)]
     #[serde(skip_deserializing)]
     pub answer_id: Uuid,
-    #[sea_orm(
-        belongs_to,
-        from = "answer_id",
-        to = "id",
-        on_update = "Cascade",
-        on_delete = "Cascade"
-    )]
-    pub answers: HasOne<super::answers::Entity>,
-    #[sea_orm(
-        belongs_to,
-        from = "question_id",
-        to = "id",
-        on_update = "Cascade",
-        on_delete = "Cascade"
-    )]
-    pub questions: HasOne<super::questions::Entity>,
     #[sea_orm(
         belongs_to,
         from = "test_id",
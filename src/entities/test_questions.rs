@@ -4,7 +4,7 @@ use sea_orm::entity::prelude::*;
 use serde::{Deserialize, Serialize};
 
 #[sea_orm::model]
-#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
 #[sea_orm(table_name = "test_questions")]
 pub struct Model {
     #[sea_orm(primary_key, auto_increment = false)]
@@ -16,14 +16,19 @@ pub struct Model {
     pub question_order: i16,
     pub is_correct: Option<bool>,
     pub answered_at: Option<DateTimeWithTimeZone>,
-    #[sea_orm(
-        belongs_to,
-        from = "question_id",
-        to = "id",
-        on_update = "Cascade",
-        on_delete = "Cascade"
-    )]
-    pub questions: HasOne<super::questions::Entity>,
+    pub points: i16,
+    #[sea_orm(column_type = "Decimal(Some((10, 2)))", nullable)]
+    pub earned_points: Option<Decimal>,
+    pub question_name: String,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub question_content: Option<String>,
+    #[sea_orm(column_type = "Text")]
+    pub explanation: String,
+    pub question_type: String,
+    #[sea_orm(column_type = "JsonBinary", nullable)]
+    pub response: Option<Json>,
+    #[sea_orm(column_type = "JsonBinary", nullable)]
+    pub image_ids: Option<Json>,
     #[sea_orm(
         belongs_to,
         from = "test_id",
@@ -34,8 +34,30 @@ pub struct Model {
     pub status: String,
     pub score_percent: Option<i16>,
     pub completed_at: Option<DateTimeWithTimeZone>,
+    pub scoring_policy: String,
+    pub max_points: i16,
+    #[sea_orm(column_type = "Decimal(Some((10, 2)))")]
+    pub earned_points: Decimal,
+    pub retake_of_id: Option<Uuid>,
+    pub series_id: Option<Uuid>,
+    pub attempt_number: i16,
+    pub time_limit_seconds: Option<i32>,
+    pub license_class: Option<String>,
+    #[sea_orm(
+        self_ref,
+        relation_enum = "RetakeOf",
+        from = "retake_of_id",
+        to = "id",
+        on_update = "Cascade",
+        on_delete = "SetNull"
+    )]
+    pub retake_of: HasOne<Entity>,
     #[sea_orm(has_many)]
     pub test_question_answers: HasMany<super::test_question_answers::Entity>,
+    #[sea_orm(has_many)]
+    pub duel_players: HasMany<super::duel_players::Entity>,
+    #[sea_orm(has_many)]
+    pub classroom_participants: HasMany<super::classroom_participants::Entity>,
     #[sea_orm(
         belongs_to,
         from = "user_id",
@@ -44,8 +66,10 @@ pub struct Model {
         on_delete = "Cascade"
     )]
     pub users: HasOne<super::users::Entity>,
-    #[sea_orm(has_many, via = "test_questions")]
-    pub questions: HasMany<super::questions::Entity>,
+    #[sea_orm(has_many)]
+    pub test_questions: HasMany<super::test_questions::Entity>,
+    #[sea_orm(has_many)]
+    pub test_question_options: HasMany<super::test_question_options::Entity>,
 }
 
 impl ActiveModelBehavior for ActiveModel {}
@@ -14,10 +14,17 @@ pub struct Model {
     pub difficulty: String,
     pub duration: i16,
     pub subscription_required: bool,
-    #[sea_orm(has_one)]
-    pub lessons: HasOne<super::lessons::Entity>,
+    pub position: i32,
+    #[sea_orm(has_many)]
+    pub lessons: HasMany<super::lessons::Entity>,
     #[sea_orm(has_many)]
     pub questions: HasMany<super::questions::Entity>,
+    #[sea_orm(has_many, relation_enum = "Prerequisites", via_rel = "Topic")]
+    pub prerequisites: HasMany<super::topic_prerequisites::Entity>,
+    #[sea_orm(has_many, relation_enum = "RequiredBy", via_rel = "RequiredTopic")]
+    pub required_by: HasMany<super::topic_prerequisites::Entity>,
+    #[sea_orm(has_many)]
+    pub topic_translations: HasMany<super::topic_translations::Entity>,
 }
 
 impl ActiveModelBehavior for ActiveModel {}
@@ -26,10 +26,41 @@ pub struct Model {
     pub password: String,
     pub phone_number: Option<String>,
     pub username: Option<String>,
+    pub license_class: Option<String>,
+    pub region: Option<String>,
+    pub role: String,
+    #[sea_orm(
+        belongs_to,
+        from = "license_class",
+        to = "code",
+        on_update = "Cascade",
+        on_delete = "SetNull"
+    )]
+    pub license_classes: HasOne<super::license_classes::Entity>,
     #[sea_orm(has_many)]
     pub tests: HasMany<super::tests::Entity>,
     #[sea_orm(has_many)]
+    pub lesson_progress: HasMany<super::lesson_progress::Entity>,
+    #[sea_orm(has_many)]
     pub user_subscriptions: HasMany<super::user_subscriptions::Entity>,
+    #[sea_orm(has_many)]
+    pub test_templates: HasMany<super::test_templates::Entity>,
+    #[sea_orm(has_many)]
+    pub classroom_sessions: HasMany<super::classroom_sessions::Entity>,
+    #[sea_orm(has_many)]
+    pub classroom_participants: HasMany<super::classroom_participants::Entity>,
+    #[sea_orm(has_many)]
+    pub question_revisions: HasMany<super::question_revisions::Entity>,
+    #[sea_orm(has_many)]
+    pub content_reviews: HasMany<super::content_reviews::Entity>,
+    #[sea_orm(has_many)]
+    pub notifications: HasMany<super::notifications::Entity>,
+    #[sea_orm(has_many, relation_enum = "ReportedQuestions", via_rel = "Reporter")]
+    pub reported_questions: HasMany<super::question_reports::Entity>,
+    #[sea_orm(has_many, relation_enum = "ResolvedReports", via_rel = "Resolver")]
+    pub resolved_reports: HasMany<super::question_reports::Entity>,
     #[sea_orm(has_many, via = "user_favorite_questions")]
     pub questions: HasMany<super::questions::Entity>,
+    #[sea_orm(has_many, via = "duel_players")]
+    pub duels: HasMany<super::duels::Entity>,
 }
@@ -0,0 +1,683 @@
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{
+        Arc,
+        atomic::{AtomicU64, Ordering},
+    },
+};
+
+use dashmap::{DashMap, mapref::entry::Entry};
+use sea_orm::{
+    ActiveModelTrait, ActiveValue::Set, ColumnTrait, DatabaseConnection, DbErr, EntityTrait,
+    IntoActiveModel, QueryFilter, QueryOrder, TransactionTrait,
+};
+use tokio::sync::{Mutex, mpsc};
+use uuid::Uuid;
+
+use super::record_answer;
+use crate::{
+    entities::{
+        classroom_participants, classroom_session_options, classroom_session_questions,
+        classroom_sessions, test_question_answers, test_questions, tests, users,
+    },
+    models::{
+        classroom_sessions::{
+            AnswerDistribution, ClassroomCommand, ClassroomEvent, ClassroomLeaderboardEntry,
+        },
+        question_types::{QuestionType, SubmittedAnswer},
+        tests::{AnswerOption, CurrentQuestionResponse, QuestionInfo},
+    },
+    rest::images::image_links,
+    utils::scoring::score_percent,
+};
+
+enum RoomInput {
+    Connected,
+    Command(ClassroomCommand),
+}
+
+/// Connections of a classroom session and the input queue of its driver
+pub struct ClassroomRoom {
+    session_id: Uuid,
+    host_id: Uuid,
+    connections: Mutex<HashMap<Uuid, (u64, mpsc::UnboundedSender<ClassroomEvent>)>>,
+    next_connection_id: AtomicU64,
+    inputs: mpsc::UnboundedSender<(Uuid, RoomInput)>,
+}
+
+impl ClassroomRoom {
+    async fn send(&self, user_id: Uuid, event: ClassroomEvent) {
+        if let Some((_, sender)) = self.connections.lock().await.get(&user_id) {
+            let _ = sender.send(event);
+        }
+    }
+
+    async fn broadcast(&self, event: ClassroomEvent) {
+        for (_, sender) in self.connections.lock().await.values() {
+            let _ = sender.send(event.clone());
+        }
+    }
+
+    /// Registers a connection, replacing the previous one of the same user.
+    /// The driver then sends the current session state to the user.
+    pub async fn connect(&self, user_id: Uuid) -> (u64, mpsc::UnboundedReceiver<ClassroomEvent>) {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        let connection_id = self.next_connection_id.fetch_add(1, Ordering::Relaxed);
+
+        self.connections
+            .lock()
+            .await
+            .insert(user_id, (connection_id, sender));
+        let _ = self.inputs.send((user_id, RoomInput::Connected));
+
+        (connection_id, receiver)
+    }
+
+    /// Drops the connection unless it was already replaced by a reconnect
+    pub async fn disconnect(&self, user_id: Uuid, connection_id: u64) {
+        let mut connections = self.connections.lock().await;
+        if connections
+            .get(&user_id)
+            .is_some_and(|(id, _)| *id == connection_id)
+        {
+            connections.remove(&user_id);
+        }
+    }
+
+    pub fn submit(&self, user_id: Uuid, command: ClassroomCommand) {
+        let _ = self.inputs.send((user_id, RoomInput::Command(command)));
+    }
+}
+
+/// Registry of classroom sessions with live connections
+#[derive(Clone, Default)]
+pub struct ClassroomHub {
+    rooms: Arc<DashMap<Uuid, Arc<ClassroomRoom>>>,
+}
+
+impl ClassroomHub {
+    /// Returns the room of the session, opening it and spawning its driver on first use.
+    /// Session state lives in the database, so a room can be reopened after a restart.
+    pub fn room(
+        &self,
+        db: &DatabaseConnection,
+        session: &classroom_sessions::Model,
+    ) -> Arc<ClassroomRoom> {
+        match self.rooms.entry(session.id) {
+            Entry::Occupied(entry) => entry.get().clone(),
+            Entry::Vacant(entry) => {
+                let (inputs, receiver) = mpsc::unbounded_channel();
+                let room = Arc::new(ClassroomRoom {
+                    session_id: session.id,
+                    host_id: session.host_id,
+                    connections: Mutex::new(HashMap::new()),
+                    next_connection_id: AtomicU64::new(0),
+                    inputs,
+                });
+                entry.insert(room.clone());
+
+                let hub = self.clone();
+                let db = db.clone();
+                let driven = room.clone();
+                tokio::spawn(async move {
+                    run_session(&db, &driven, receiver).await;
+                    hub.rooms.remove(&driven.session_id);
+                });
+
+                room
+            }
+        }
+    }
+}
+
+/// Session questions in order
+pub(crate) async fn session_questions(
+    db: &DatabaseConnection,
+    session_id: Uuid,
+) -> Result<Vec<classroom_session_questions::Model>, DbErr> {
+    classroom_session_questions::Entity::find()
+        .filter(classroom_session_questions::Column::SessionId.eq(session_id))
+        .order_by_asc(classroom_session_questions::Column::QuestionOrder)
+        .all(db)
+        .await
+}
+
+/// Participants with their tests, ranked by points and then by correct answers
+pub(crate) async fn leaderboard(
+    db: &DatabaseConnection,
+    session_id: Uuid,
+) -> Result<Vec<(ClassroomLeaderboardEntry, Option<tests::Model>)>, DbErr> {
+    let participants = classroom_participants::Entity::find()
+        .filter(classroom_participants::Column::SessionId.eq(session_id))
+        .all(db)
+        .await?;
+
+    let test_ids: Vec<Uuid> = participants.iter().filter_map(|p| p.test_id).collect();
+    let tests_map: HashMap<Uuid, tests::Model> = tests::Entity::find()
+        .filter(tests::Column::Id.is_in(test_ids))
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|t| (t.id, t))
+        .collect();
+
+    let user_ids: Vec<Uuid> = participants.iter().map(|p| p.user_id).collect();
+    let usernames: HashMap<Uuid, Option<String>> = users::Entity::find()
+        .filter(users::Column::Id.is_in(user_ids))
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|u| (u.id, u.username))
+        .collect();
+
+    let mut rows: Vec<(ClassroomLeaderboardEntry, Option<tests::Model>)> = participants
+        .into_iter()
+        .map(|p| {
+            let test = p.test_id.and_then(|id| tests_map.get(&id)).cloned();
+            let entry = ClassroomLeaderboardEntry {
+                rank: 0,
+                user_id: p.user_id,
+                username: usernames.get(&p.user_id).cloned().flatten(),
+                earned_points: test.as_ref().map(|t| t.earned_points).unwrap_or_default(),
+                correct_count: test.as_ref().map(|t| t.correct_count).unwrap_or(0),
+            };
+            (entry, test)
+        })
+        .collect();
+
+    rows.sort_by(|(a, _), (b, _)| {
+        b.earned_points
+            .cmp(&a.earned_points)
+            .then(b.correct_count.cmp(&a.correct_count))
+    });
+    for (index, (entry, _)) in rows.iter_mut().enumerate() {
+        entry.rank = index as i64 + 1;
+    }
+
+    Ok(rows)
+}
+
+/// Answer distribution, answered and correct counts of a question across the tests
+pub(crate) async fn question_stats(
+    db: &DatabaseConnection,
+    test_ids: &[Uuid],
+    question_id: Uuid,
+) -> Result<(Vec<AnswerDistribution>, i64, i64), DbErr> {
+    let mut counts: HashMap<Uuid, i64> = HashMap::new();
+    for tqa in test_question_answers::Entity::find()
+        .filter(test_question_answers::Column::TestId.is_in(test_ids.to_vec()))
+        .filter(test_question_answers::Column::QuestionId.eq(question_id))
+        .all(db)
+        .await?
+    {
+        *counts.entry(tqa.answer_id).or_default() += 1;
+    }
+
+    let answered = test_questions::Entity::find()
+        .filter(test_questions::Column::TestId.is_in(test_ids.to_vec()))
+        .filter(test_questions::Column::QuestionId.eq(question_id))
+        .filter(test_questions::Column::AnsweredAt.is_not_null())
+        .all(db)
+        .await?;
+    let correct = answered
+        .iter()
+        .filter(|tq| tq.is_correct == Some(true))
+        .count() as i64;
+
+    let mut distribution: Vec<AnswerDistribution> = counts
+        .into_iter()
+        .map(|(answer_id, count)| AnswerDistribution { answer_id, count })
+        .collect();
+    distribution.sort_by_key(|d| std::cmp::Reverse(d.count));
+
+    Ok((distribution, answered.len() as i64, correct))
+}
+
+/// Index of the current question, `None` before the first one
+fn current_index(session: &classroom_sessions::Model) -> Option<usize> {
+    (session.current_question_order as usize).checked_sub(1)
+}
+
+async fn load_session(
+    db: &DatabaseConnection,
+    session_id: Uuid,
+) -> Result<classroom_sessions::Model, DbErr> {
+    classroom_sessions::Entity::find_by_id(session_id)
+        .one(db)
+        .await?
+        .ok_or(DbErr::RecordNotFound(format!(
+            "classroom session {session_id}"
+        )))
+}
+
+/// Test of a participant in the session
+async fn participant_test_id(
+    db: &DatabaseConnection,
+    session_id: Uuid,
+    user_id: Uuid,
+) -> Result<Option<Uuid>, DbErr> {
+    Ok(
+        classroom_participants::Entity::find_by_id((session_id, user_id))
+            .one(db)
+            .await?
+            .and_then(|p| p.test_id),
+    )
+}
+
+/// Current question as snapshotted when the session was created, with the
+/// set of correct answers
+async fn load_session_question(
+    db: &DatabaseConnection,
+    current: &classroom_session_questions::Model,
+    lang: &str,
+) -> Result<(CurrentQuestionResponse, HashSet<Uuid>), DbErr> {
+    let options = classroom_session_options::Entity::find()
+        .filter(classroom_session_options::Column::SessionId.eq(current.session_id))
+        .filter(classroom_session_options::Column::QuestionId.eq(current.question_id))
+        .order_by_asc(classroom_session_options::Column::OptionOrder)
+        .all(db)
+        .await?;
+    let image_ids: Vec<Uuid> = current
+        .image_ids
+        .clone()
+        .and_then(|value| serde_json::from_value(value).ok())
+        .unwrap_or_default();
+    let links = image_links(
+        db,
+        image_ids
+            .iter()
+            .cloned()
+            .chain(options.iter().filter_map(|o| o.image_id)),
+    )
+    .await?;
+
+    let question_type = current
+        .question_type
+        .parse::<QuestionType>()
+        .unwrap_or_default();
+    let correct_ids: HashSet<Uuid> = options
+        .iter()
+        .filter(|o| o.is_correct)
+        .map(|o| o.answer_id)
+        .collect();
+
+    Ok((
+        CurrentQuestionResponse {
+            order: current.question_order,
+            question: QuestionInfo {
+                id: current.question_id,
+                name: current.question_name.clone(),
+                content: current.question_content.clone(),
+                lang: lang.to_string(),
+            },
+            question_type: current.question_type.clone(),
+            images: image_ids
+                .iter()
+                .filter_map(|id| links.get(id).cloned())
+                .collect(),
+            answers: options
+                .into_iter()
+                .filter(|_| question_type.shows_options())
+                .map(|o| AnswerOption {
+                    id: o.answer_id,
+                    image: o.image_id.and_then(|id| links.get(&id).cloned()),
+                    value: o.value,
+                })
+                .collect(),
+            multiple_answers: question_type == QuestionType::Choice && correct_ids.len() > 1,
+        },
+        correct_ids,
+    ))
+}
+
+/// Driver of a session: applies host and participant inputs one at a time
+async fn run_session(
+    db: &DatabaseConnection,
+    room: &ClassroomRoom,
+    mut inputs: mpsc::UnboundedReceiver<(Uuid, RoomInput)>,
+) {
+    while let Some((user_id, input)) = inputs.recv().await {
+        let result = match input {
+            RoomInput::Connected => on_connected(db, room, user_id).await,
+            RoomInput::Command(command) => on_command(db, room, user_id, command).await,
+        };
+
+        match result {
+            Ok(Some(message)) => {
+                room.send(user_id, ClassroomEvent::Error { message }).await;
+            }
+            Ok(None) => {}
+            Err(err) => {
+                tracing::error!("classroom session {}: {err}", room.session_id);
+                room.send(
+                    user_id,
+                    ClassroomEvent::Error {
+                        message: "internal error".to_string(),
+                    },
+                )
+                .await;
+            }
+        }
+
+        if load_session(db, room.session_id)
+            .await
+            .is_ok_and(|session| session.status == "finished")
+        {
+            break;
+        }
+    }
+}
+
+/// Sends the current state to a freshly connected user
+async fn on_connected(
+    db: &DatabaseConnection,
+    room: &ClassroomRoom,
+    user_id: Uuid,
+) -> Result<Option<String>, DbErr> {
+    let session = load_session(db, room.session_id).await?;
+    let entries: Vec<ClassroomLeaderboardEntry> = leaderboard(db, session.id)
+        .await?
+        .into_iter()
+        .map(|(entry, _)| entry)
+        .collect();
+
+    // Хост видит список участников при каждом подключении
+    room.send(
+        room.host_id,
+        ClassroomEvent::Participants {
+            participants: entries.clone(),
+        },
+    )
+    .await;
+
+    match session.status.as_str() {
+        "question" => {
+            let questions = session_questions(db, session.id).await?;
+            let Some(current) = current_index(&session).and_then(|index| questions.get(index))
+            else {
+                return Ok(None);
+            };
+            let (question, _) = load_session_question(db, current, &session.lang).await?;
+            room.send(
+                user_id,
+                ClassroomEvent::Question {
+                    total: questions.len() as i16,
+                    question,
+                },
+            )
+            .await;
+
+            // После переподключения студент видит, что уже ответил
+            if let Some(test_id) = participant_test_id(db, session.id, user_id).await?
+                && test_questions::Entity::find()
+                    .filter(test_questions::Column::TestId.eq(test_id))
+                    .filter(test_questions::Column::QuestionId.eq(current.question_id))
+                    .filter(test_questions::Column::AnsweredAt.is_not_null())
+                    .one(db)
+                    .await?
+                    .is_some()
+            {
+                room.send(
+                    user_id,
+                    ClassroomEvent::AnswerAccepted {
+                        question_id: current.question_id,
+                    },
+                )
+                .await;
+            }
+        }
+        "finished" => {
+            room.send(
+                user_id,
+                ClassroomEvent::Finished {
+                    leaderboard: entries,
+                },
+            )
+            .await;
+        }
+        status => {
+            room.send(
+                user_id,
+                ClassroomEvent::Waiting {
+                    status: status.to_string(),
+                },
+            )
+            .await;
+        }
+    }
+
+    Ok(None)
+}
+
+/// Applies a command, returns a message for the sender when it is rejected
+async fn on_command(
+    db: &DatabaseConnection,
+    room: &ClassroomRoom,
+    user_id: Uuid,
+    command: ClassroomCommand,
+) -> Result<Option<String>, DbErr> {
+    let session = load_session(db, room.session_id).await?;
+
+    if session.status == "finished" {
+        return Ok(Some("session is finished".to_string()));
+    }
+
+    let is_host = user_id == room.host_id;
+    match command {
+        ClassroomCommand::Answer { .. } if is_host => {
+            Ok(Some("the host cannot answer".to_string()))
+        }
+        ClassroomCommand::Answer {
+            question_id,
+            answer,
+        } => submit_answer(db, room, session, user_id, question_id, answer).await,
+        _ if !is_host => Ok(Some("only the host controls the session".to_string())),
+        ClassroomCommand::Next => {
+            let session = if session.status == "question" {
+                close_question(db, room, session).await?
+            } else {
+                session
+            };
+            next_question(db, room, session).await
+        }
+        ClassroomCommand::CloseQuestion => {
+            if session.status != "question" {
+                return Ok(Some("no open question".to_string()));
+            }
+            close_question(db, room, session).await?;
+            Ok(None)
+        }
+        ClassroomCommand::Finish => {
+            let session = if session.status == "question" {
+                close_question(db, room, session).await?
+            } else {
+                session
+            };
+            finish(db, room, session).await?;
+            Ok(None)
+        }
+    }
+}
+
+async fn next_question(
+    db: &DatabaseConnection,
+    room: &ClassroomRoom,
+    session: classroom_sessions::Model,
+) -> Result<Option<String>, DbErr> {
+    let questions = session_questions(db, session.id).await?;
+    let order = session.current_question_order + 1;
+
+    let Some(current) = questions.get(order as usize - 1) else {
+        finish(db, room, session).await?;
+        return Ok(None);
+    };
+    let (question, _) = load_session_question(db, current, &session.lang).await?;
+
+    let mut to_update = session.into_active_model();
+    to_update.status = Set("question".to_string());
+    to_update.current_question_order = Set(order);
+    to_update.updated_at = Set(chrono::Utc::now().into());
+    to_update.update(db).await?;
+
+    room.broadcast(ClassroomEvent::Question {
+        total: questions.len() as i16,
+        question,
+    })
+    .await;
+
+    Ok(None)
+}
+
+/// Stops accepting answers, shows the distribution and the leaderboard
+async fn close_question(
+    db: &DatabaseConnection,
+    room: &ClassroomRoom,
+    session: classroom_sessions::Model,
+) -> Result<classroom_sessions::Model, DbErr> {
+    let questions = session_questions(db, session.id).await?;
+    let Some(current) = current_index(&session)
+        .and_then(|index| questions.get(index))
+        .cloned()
+    else {
+        return Ok(session);
+    };
+
+    let mut to_update = session.into_active_model();
+    to_update.status = Set("results".to_string());
+    to_update.updated_at = Set(chrono::Utc::now().into());
+    let session = to_update.update(db).await?;
+
+    let (_, correct_ids) = load_session_question(db, &current, &session.lang).await?;
+    let rows = leaderboard(db, session.id).await?;
+    let test_ids: Vec<Uuid> = rows
+        .iter()
+        .filter_map(|(_, test)| test.as_ref().map(|t| t.id))
+        .collect();
+    let (distribution, _, _) = question_stats(db, &test_ids, current.question_id).await?;
+
+    room.broadcast(ClassroomEvent::Results {
+        question_id: current.question_id,
+        correct_answer_ids: correct_ids.into_iter().collect(),
+        distribution,
+        leaderboard: rows.iter().map(|(entry, _)| entry.clone()).collect(),
+    })
+    .await;
+
+    let answered: HashMap<Uuid, test_questions::Model> = test_questions::Entity::find()
+        .filter(test_questions::Column::TestId.is_in(test_ids))
+        .filter(test_questions::Column::QuestionId.eq(current.question_id))
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|tq| (tq.test_id, tq))
+        .collect();
+
+    for (entry, test) in &rows {
+        let tq = test.as_ref().and_then(|t| answered.get(&t.id));
+        room.send(
+            entry.user_id,
+            ClassroomEvent::AnswerResult {
+                question_id: current.question_id,
+                is_correct: tq.and_then(|tq| tq.is_correct),
+                earned_points: tq.and_then(|tq| tq.earned_points),
+            },
+        )
+        .await;
+    }
+
+    Ok(session)
+}
+
+/// Completes every participant test and closes the session
+async fn finish(
+    db: &DatabaseConnection,
+    room: &ClassroomRoom,
+    session: classroom_sessions::Model,
+) -> Result<(), DbErr> {
+    let rows = leaderboard(db, session.id).await?;
+    let now = chrono::Utc::now();
+
+    let txn = db.begin().await?;
+    for (_, test) in &rows {
+        let Some(test) = test.clone() else { continue };
+        if test.status != "active" {
+            continue;
+        }
+        let score = score_percent(test.earned_points, test.max_points);
+        let mut test_active = test.into_active_model();
+        test_active.status = Set("completed".to_string());
+        test_active.score_percent = Set(Some(score));
+        test_active.completed_at = Set(Some(now.into()));
+        test_active.update(&txn).await?;
+    }
+
+    let mut to_update = session.into_active_model();
+    to_update.status = Set("finished".to_string());
+    to_update.finished_at = Set(Some(now.into()));
+    to_update.updated_at = Set(now.into());
+    to_update.update(&txn).await?;
+    txn.commit().await?;
+
+    room.broadcast(ClassroomEvent::Finished {
+        leaderboard: rows.into_iter().map(|(entry, _)| entry).collect(),
+    })
+    .await;
+
+    Ok(())
+}
+
+async fn submit_answer(
+    db: &DatabaseConnection,
+    room: &ClassroomRoom,
+    session: classroom_sessions::Model,
+    user_id: Uuid,
+    question_id: Uuid,
+    answer: SubmittedAnswer,
+) -> Result<Option<String>, DbErr> {
+    if session.status != "question" {
+        return Ok(Some("no open question".to_string()));
+    }
+
+    let questions = session_questions(db, session.id).await?;
+    let Some(current) = current_index(&session).and_then(|index| questions.get(index)) else {
+        return Ok(Some("no open question".to_string()));
+    };
+    if current.question_id != question_id {
+        return Ok(Some("question is not open for answers".to_string()));
+    }
+
+    let Some(test_id) = participant_test_id(db, session.id, user_id).await? else {
+        return Ok(Some("join the session first".to_string()));
+    };
+
+    if record_answer(db, test_id, question_id, &answer)
+        .await?
+        .is_none()
+    {
+        return Ok(Some("question already answered".to_string()));
+    }
+
+    room.send(user_id, ClassroomEvent::AnswerAccepted { question_id })
+        .await;
+
+    let test_ids: Vec<Uuid> = classroom_participants::Entity::find()
+        .filter(classroom_participants::Column::SessionId.eq(session.id))
+        .all(db)
+        .await?
+        .into_iter()
+        .filter_map(|p| p.test_id)
+        .collect();
+    let (_, answered, _) = question_stats(db, &test_ids, question_id).await?;
+
+    room.send(
+        room.host_id,
+        ClassroomEvent::AnswerCount {
+            question_id,
+            answered,
+            participants: test_ids.len() as i64,
+        },
+    )
+    .await;
+
+    Ok(None)
+}
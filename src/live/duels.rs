@@ -0,0 +1,443 @@
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{
+        Arc,
+        atomic::{AtomicU64, Ordering},
+    },
+    time::Duration,
+};
+
+use dashmap::DashMap;
+use rust_decimal::Decimal;
+use sea_orm::{
+    ActiveModelTrait, ActiveValue::Set, ColumnTrait, DatabaseConnection, DbErr, EntityTrait,
+    IntoActiveModel, QueryFilter, TransactionTrait, prelude::Expr,
+};
+use tokio::{
+    sync::{Mutex, Notify, mpsc},
+    time::{Instant, sleep_until, timeout_at},
+};
+use uuid::Uuid;
+
+use super::{load_test_question, record_answer};
+use crate::{
+    entities::{duel_players, duels, tests},
+    models::{
+        duels::{DuelCommand, DuelEvent, PlayerScore},
+        tests::CurrentQuestionResponse,
+    },
+    utils::scoring::score_percent,
+};
+
+/// Time to answer a single question
+pub const QUESTION_SECONDS: u64 = 20;
+
+/// Time for both players to connect, after that the duel starts anyway
+const START_TIMEOUT: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Clone, Copy)]
+struct Player {
+    user_id: Uuid,
+    test_id: Uuid,
+}
+
+/// Question currently shown to the players
+struct CurrentQuestion {
+    question: CurrentQuestionResponse,
+    total: i16,
+    deadline: Instant,
+}
+
+/// A running duel: player connections and the command queue of its driver
+pub struct DuelRoom {
+    duel_id: Uuid,
+    players: Vec<Player>,
+    connections: Mutex<HashMap<Uuid, (u64, mpsc::UnboundedSender<DuelEvent>)>>,
+    next_connection_id: AtomicU64,
+    commands: mpsc::UnboundedSender<(Uuid, DuelCommand)>,
+    current: Mutex<Option<CurrentQuestion>>,
+    connected: Notify,
+}
+
+impl DuelRoom {
+    pub fn is_player(&self, user_id: Uuid) -> bool {
+        self.players.iter().any(|p| p.user_id == user_id)
+    }
+
+    fn player(&self, user_id: Uuid) -> Option<Player> {
+        self.players.iter().find(|p| p.user_id == user_id).copied()
+    }
+
+    fn opponent_of(&self, user_id: Uuid) -> Option<Uuid> {
+        self.players
+            .iter()
+            .find(|p| p.user_id != user_id)
+            .map(|p| p.user_id)
+    }
+
+    async fn send(&self, user_id: Uuid, event: DuelEvent) {
+        if let Some((_, sender)) = self.connections.lock().await.get(&user_id) {
+            let _ = sender.send(event);
+        }
+    }
+
+    async fn broadcast(&self, event: DuelEvent) {
+        for (_, sender) in self.connections.lock().await.values() {
+            let _ = sender.send(event.clone());
+        }
+    }
+
+    async fn connected_count(&self) -> usize {
+        self.connections.lock().await.len()
+    }
+
+    /// Registers a player connection, replacing the previous one on reconnect.
+    /// The player immediately receives the current question, if any.
+    pub async fn connect(&self, user_id: Uuid) -> (u64, mpsc::UnboundedReceiver<DuelEvent>) {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        let connection_id = self.next_connection_id.fetch_add(1, Ordering::Relaxed);
+
+        let initial = match self.current.lock().await.as_ref() {
+            Some(current) => DuelEvent::Question {
+                total: current.total,
+                seconds_left: current
+                    .deadline
+                    .saturating_duration_since(Instant::now())
+                    .as_secs(),
+                question: current.question.clone(),
+            },
+            None => DuelEvent::Waiting,
+        };
+        let _ = sender.send(initial);
+
+        self.connections
+            .lock()
+            .await
+            .insert(user_id, (connection_id, sender));
+
+        if let Some(opponent) = self.opponent_of(user_id) {
+            self.send(opponent, DuelEvent::OpponentConnected).await;
+        }
+        self.connected.notify_one();
+
+        (connection_id, receiver)
+    }
+
+    /// Drops the connection unless it was already replaced by a reconnect
+    pub async fn disconnect(&self, user_id: Uuid, connection_id: u64) {
+        {
+            let mut connections = self.connections.lock().await;
+            match connections.get(&user_id) {
+                Some((id, _)) if *id == connection_id => {
+                    connections.remove(&user_id);
+                }
+                _ => return,
+            }
+        }
+
+        if let Some(opponent) = self.opponent_of(user_id) {
+            self.send(opponent, DuelEvent::OpponentDisconnected).await;
+        }
+    }
+
+    pub fn submit(&self, user_id: Uuid, command: DuelCommand) {
+        let _ = self.commands.send((user_id, command));
+    }
+}
+
+/// Registry of running duels
+#[derive(Clone, Default)]
+pub struct DuelHub {
+    rooms: Arc<DashMap<Uuid, Arc<DuelRoom>>>,
+}
+
+impl DuelHub {
+    pub fn room(&self, duel_id: Uuid) -> Option<Arc<DuelRoom>> {
+        self.rooms.get(&duel_id).map(|room| room.clone())
+    }
+
+    /// Registers a matched duel and spawns its driver.
+    /// `players` are `(user_id, test_id)` pairs in player order.
+    pub fn start(
+        &self,
+        db: DatabaseConnection,
+        duel_id: Uuid,
+        players: Vec<(Uuid, Uuid)>,
+        question_ids: Vec<Uuid>,
+    ) {
+        let (commands, receiver) = mpsc::unbounded_channel();
+        let room = Arc::new(DuelRoom {
+            duel_id,
+            players: players
+                .into_iter()
+                .map(|(user_id, test_id)| Player { user_id, test_id })
+                .collect(),
+            connections: Mutex::new(HashMap::new()),
+            next_connection_id: AtomicU64::new(0),
+            commands,
+            current: Mutex::new(None),
+            connected: Notify::new(),
+        });
+        self.rooms.insert(duel_id, room.clone());
+
+        let hub = self.clone();
+        tokio::spawn(async move {
+            if let Err(err) = run_duel(&db, &room, receiver, &question_ids).await {
+                tracing::error!("duel {duel_id}: {err}");
+                room.broadcast(DuelEvent::Error {
+                    message: "duel interrupted".to_string(),
+                })
+                .await;
+                if let Err(err) = close_duel(&db, &room, "cancelled", "abandoned", None).await {
+                    tracing::error!("duel {duel_id} cancel: {err}");
+                }
+            }
+            hub.rooms.remove(&duel_id);
+        });
+    }
+}
+
+/// Closes both player tests and the duel with the given statuses
+async fn close_duel(
+    db: &DatabaseConnection,
+    room: &DuelRoom,
+    duel_status: &str,
+    test_status: &str,
+    winner_id: Option<Uuid>,
+) -> Result<(), DbErr> {
+    let txn = db.begin().await?;
+    let now = chrono::Utc::now();
+
+    for player in &room.players {
+        if let Some(test) = tests::Entity::find_by_id(player.test_id).one(&txn).await? {
+            let score = score_percent(test.earned_points, test.max_points);
+            let mut test_active = test.into_active_model();
+            test_active.status = Set(test_status.to_string());
+            test_active.score_percent = Set(Some(score));
+            test_active.completed_at = Set(Some(now.into()));
+            test_active.update(&txn).await?;
+        }
+    }
+
+    if let Some(winner_id) = winner_id {
+        duel_players::Entity::update_many()
+            .col_expr(duel_players::Column::IsWinner, Expr::value(true))
+            .filter(duel_players::Column::DuelId.eq(room.duel_id))
+            .filter(duel_players::Column::UserId.eq(winner_id))
+            .exec(&txn)
+            .await?;
+    }
+
+    duels::Entity::update_many()
+        .col_expr(duels::Column::Status, Expr::value(duel_status))
+        .col_expr(duels::Column::FinishedAt, Expr::value(now))
+        .col_expr(duels::Column::UpdatedAt, Expr::value(now))
+        .filter(duels::Column::Id.eq(room.duel_id))
+        .exec(&txn)
+        .await?;
+
+    txn.commit().await
+}
+
+/// Cancels the duels a previous run left ready or active: their drivers are
+/// gone, so nobody would finish them and the players could not start another
+pub async fn cancel_orphaned(db: &DatabaseConnection) -> Result<usize, DbErr> {
+    let txn = db.begin().await?;
+    let now = chrono::Utc::now();
+
+    let duel_ids: Vec<Uuid> = duels::Entity::find()
+        .filter(duels::Column::Status.is_in(["ready", "active"]))
+        .all(&txn)
+        .await?
+        .into_iter()
+        .map(|duel| duel.id)
+        .collect();
+    if duel_ids.is_empty() {
+        return Ok(0);
+    }
+
+    let test_ids: Vec<Uuid> = duel_players::Entity::find()
+        .filter(duel_players::Column::DuelId.is_in(duel_ids.clone()))
+        .all(&txn)
+        .await?
+        .into_iter()
+        .filter_map(|player| player.test_id)
+        .collect();
+    for test in tests::Entity::find()
+        .filter(tests::Column::Id.is_in(test_ids))
+        .filter(tests::Column::Status.eq("active"))
+        .all(&txn)
+        .await?
+    {
+        let score = score_percent(test.earned_points, test.max_points);
+        let mut test_active = test.into_active_model();
+        test_active.status = Set("abandoned".to_string());
+        test_active.score_percent = Set(Some(score));
+        test_active.completed_at = Set(Some(now.into()));
+        test_active.update(&txn).await?;
+    }
+
+    duels::Entity::update_many()
+        .col_expr(duels::Column::Status, Expr::value("cancelled"))
+        .col_expr(duels::Column::FinishedAt, Expr::value(now))
+        .col_expr(duels::Column::UpdatedAt, Expr::value(now))
+        .filter(duels::Column::Id.is_in(duel_ids.clone()))
+        .exec(&txn)
+        .await?;
+
+    txn.commit().await?;
+
+    Ok(duel_ids.len())
+}
+
+/// Drives a duel from the first question to the result
+async fn run_duel(
+    db: &DatabaseConnection,
+    room: &DuelRoom,
+    mut commands: mpsc::UnboundedReceiver<(Uuid, DuelCommand)>,
+    question_ids: &[Uuid],
+) -> Result<(), DbErr> {
+    // Ждём обоих игроков, но не дольше START_TIMEOUT
+    let start_deadline = Instant::now() + START_TIMEOUT;
+    while room.connected_count().await < room.players.len() {
+        if timeout_at(start_deadline, room.connected.notified())
+            .await
+            .is_err()
+        {
+            break;
+        }
+    }
+
+    if room.connected_count().await == 0 {
+        close_duel(db, room, "cancelled", "abandoned", None).await?;
+        return Ok(());
+    }
+
+    duels::Entity::update_many()
+        .col_expr(duels::Column::Status, Expr::value("active"))
+        .col_expr(duels::Column::UpdatedAt, Expr::value(chrono::Utc::now()))
+        .filter(duels::Column::Id.eq(room.duel_id))
+        .exec(db)
+        .await?;
+
+    let mut scores: Vec<PlayerScore> = room
+        .players
+        .iter()
+        .map(|p| PlayerScore {
+            user_id: p.user_id,
+            earned_points: Decimal::ZERO,
+            correct_count: 0,
+            answered_count: 0,
+        })
+        .collect();
+    let total = question_ids.len() as i16;
+
+    for question_id in question_ids {
+        // Оба теста созданы одновременно, снимки вопросов совпадают
+        let (question, correct_ids) =
+            load_test_question(db, room.players[0].test_id, *question_id).await?;
+        let deadline = Instant::now() + Duration::from_secs(QUESTION_SECONDS);
+
+        *room.current.lock().await = Some(CurrentQuestion {
+            question: question.clone(),
+            total,
+            deadline,
+        });
+        room.broadcast(DuelEvent::Question {
+            total,
+            seconds_left: QUESTION_SECONDS,
+            question,
+        })
+        .await;
+
+        let mut answered: HashSet<Uuid> = HashSet::new();
+        while answered.len() < room.players.len() {
+            let (user_id, command) = tokio::select! {
+                command = commands.recv() => match command {
+                    Some(command) => command,
+                    None => break,
+                },
+                _ = sleep_until(deadline) => break,
+            };
+
+            let Some(player) = room.player(user_id) else {
+                continue;
+            };
+
+            match command {
+                DuelCommand::Answer {
+                    question_id: answered_id,
+                    answer,
+                } => {
+                    if answered_id != *question_id || answered.contains(&user_id) {
+                        room.send(
+                            user_id,
+                            DuelEvent::Error {
+                                message: "question is not open for answers".to_string(),
+                            },
+                        )
+                        .await;
+                        continue;
+                    }
+
+                    let Some((is_correct, earned_points)) =
+                        record_answer(db, player.test_id, *question_id, &answer).await?
+                    else {
+                        continue;
+                    };
+                    answered.insert(user_id);
+
+                    if let Some(score) = scores.iter_mut().find(|s| s.user_id == user_id) {
+                        score.earned_points += earned_points;
+                        score.correct_count += i16::from(is_correct);
+                        score.answered_count += 1;
+                    }
+
+                    room.send(
+                        user_id,
+                        DuelEvent::AnswerResult {
+                            question_id: *question_id,
+                            is_correct,
+                            earned_points,
+                            correct_answer_ids: correct_ids.iter().cloned().collect(),
+                        },
+                    )
+                    .await;
+                    if let Some(opponent) = room.opponent_of(user_id) {
+                        room.send(
+                            opponent,
+                            DuelEvent::OpponentAnswered {
+                                question_id: *question_id,
+                            },
+                        )
+                        .await;
+                    }
+                    room.broadcast(DuelEvent::Scores {
+                        scores: scores.clone(),
+                    })
+                    .await;
+                }
+            }
+        }
+    }
+
+    *room.current.lock().await = None;
+
+    // Победитель — больше баллов, при равенстве ничья
+    let best = scores.iter().map(|s| s.earned_points).max();
+    let leaders: Vec<&PlayerScore> = scores
+        .iter()
+        .filter(|s| Some(s.earned_points) == best)
+        .collect();
+    let winner_id = match leaders.as_slice() {
+        [winner] => Some(winner.user_id),
+        _ => None,
+    };
+
+    close_duel(db, room, "finished", "completed", winner_id).await?;
+
+    room.broadcast(DuelEvent::Finished { winner_id, scores })
+        .await;
+
+    Ok(())
+}
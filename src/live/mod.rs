@@ -0,0 +1,122 @@
+pub mod classrooms;
+pub mod duels;
+
+use std::collections::HashSet;
+
+use axum::extract::ws::{Message, WebSocket};
+use rust_decimal::Decimal;
+use sea_orm::{
+    ActiveModelTrait, ActiveValue::Set, ColumnTrait, DatabaseConnection, DbErr, EntityTrait,
+    IntoActiveModel, QueryFilter, TransactionTrait,
+};
+use serde::{Serialize, de::DeserializeOwned};
+use tokio::sync::mpsc;
+use uuid::Uuid;
+
+use crate::{
+    entities::{test_questions, tests},
+    models::{question_types::SubmittedAnswer, tests::CurrentQuestionResponse},
+    rest::tests::{grade_answer, snapshot_question},
+};
+
+/// Forwards events to the socket and parsed client messages to `on_command`
+/// until either side closes. Malformed messages are answered with `on_error`.
+pub async fn pump_socket<E, C>(
+    socket: &mut WebSocket,
+    events: &mut mpsc::UnboundedReceiver<E>,
+    mut on_command: impl FnMut(C),
+    on_error: impl Fn(String) -> E,
+) where
+    E: Serialize,
+    C: DeserializeOwned,
+{
+    loop {
+        tokio::select! {
+            event = events.recv() => {
+                // Канал закрыт: сессия завершена или клиент переподключился
+                let Some(event) = event else { break };
+                let Ok(text) = serde_json::to_string(&event) else { continue };
+                if socket.send(Message::Text(text.into())).await.is_err() {
+                    break;
+                }
+            }
+            message = socket.recv() => match message {
+                Some(Ok(Message::Text(text))) => match serde_json::from_str::<C>(&text) {
+                    Ok(command) => on_command(command),
+                    Err(err) => {
+                        if let Ok(text) = serde_json::to_string(&on_error(err.to_string())) {
+                            let _ = socket.send(Message::Text(text.into())).await;
+                        }
+                    }
+                },
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                Some(Ok(_)) => {}
+            },
+        }
+    }
+}
+
+/// Question of the player's test as it was snapshotted, with the set of correct answers
+pub(crate) async fn load_test_question(
+    db: &DatabaseConnection,
+    test_id: Uuid,
+    question_id: Uuid,
+) -> Result<(CurrentQuestionResponse, HashSet<Uuid>), DbErr> {
+    let test = tests::Entity::find_by_id(test_id)
+        .one(db)
+        .await?
+        .ok_or(DbErr::RecordNotFound(format!("test {test_id}")))?;
+
+    let tq = test_questions::Entity::find()
+        .filter(test_questions::Column::TestId.eq(test_id))
+        .filter(test_questions::Column::QuestionId.eq(question_id))
+        .one(db)
+        .await?
+        .ok_or(DbErr::RecordNotFound(format!(
+            "test question {question_id}"
+        )))?;
+
+    snapshot_question(db, tq, &test.lang).await
+}
+
+/// Saves the answer to the player's test, returns correctness and earned points.
+/// `None` when the question was already answered.
+pub(crate) async fn record_answer(
+    db: &DatabaseConnection,
+    test_id: Uuid,
+    question_id: Uuid,
+    answer: &SubmittedAnswer,
+) -> Result<Option<(bool, Decimal)>, DbErr> {
+    let txn = db.begin().await?;
+
+    let test = tests::Entity::find_by_id(test_id)
+        .one(&txn)
+        .await?
+        .ok_or(DbErr::RecordNotFound(format!("test {test_id}")))?;
+
+    let tq = test_questions::Entity::find()
+        .filter(test_questions::Column::TestId.eq(test_id))
+        .filter(test_questions::Column::QuestionId.eq(question_id))
+        .one(&txn)
+        .await?
+        .ok_or(DbErr::RecordNotFound(format!(
+            "test question {question_id}"
+        )))?;
+
+    if tq.answered_at.is_some() {
+        return Ok(None);
+    }
+
+    let (is_correct, earned_points, _) = grade_answer(&txn, &test, tq, answer).await?;
+
+    let correct_count = test.correct_count + i16::from(is_correct);
+    let test_earned_points = test.earned_points + earned_points;
+    let mut test_active = test.into_active_model();
+    test_active.correct_count = Set(correct_count);
+    test_active.earned_points = Set(test_earned_points);
+    test_active.update(&txn).await?;
+
+    txn.commit().await?;
+
+    Ok(Some((is_correct, earned_points)))
+}
@@ -10,6 +10,7 @@ use utoipa_axum::routes;
 use utoipa_scalar::{Scalar, Servable};
 
 mod entities;
+mod live;
 mod migrations;
 mod models;
 mod rest;
@@ -19,6 +20,8 @@ mod utils;
 #[derive(Clone)]
 struct AppContext {
     db: DatabaseConnection,
+    duels: live::duels::DuelHub,
+    classrooms: live::classrooms::ClassroomHub,
 }
 
 #[derive(OpenApi)]
@@ -55,11 +58,42 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         .with_env_filter(tracing_subscriber::EnvFilter::from_default_env())
         .init();
 
+    // Импорт и экспорт вопросов вместо запуска сервера
+    if std::env::args().nth(1).as_deref() == Some("import-questions") {
+        let report = tasks::question_import::run_cli(std::env::args().skip(1)).await?;
+        println!("{}", serde_json::to_string_pretty(&report)?);
+        if !report.errors.is_empty() {
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+    if std::env::args().nth(1).as_deref() == Some("export-questions") {
+        tasks::question_export::run_cli(std::env::args().skip(1)).await?;
+        return Ok(());
+    }
+    // Первого администратора назначают из консоли сервера
+    if std::env::args().nth(1).as_deref() == Some("grant-role") {
+        tasks::user_roles::run_cli(std::env::args().skip(1)).await?;
+        return Ok(());
+    }
+
     let server_config = utils::config::ServerConfig::load();
     let db_config = utils::config::DBConfig::load();
 
     let db = db_config.connect().await;
-    let state = AppContext { db };
+
+    // Дуэли прошлого запуска остались без драйвера
+    match live::duels::cancel_orphaned(&db).await {
+        Ok(0) => {}
+        Ok(count) => tracing::info!("Cancelled {count} orphaned duels."),
+        Err(err) => tracing::error!("orphaned duels: {err}"),
+    }
+
+    let state = AppContext {
+        db,
+        duels: live::duels::DuelHub::default(),
+        classrooms: live::classrooms::ClassroomHub::default(),
+    };
     let state_clone = state.clone();
     let schedule = Schedule::from_str("0 * * * * *").unwrap();
     let worker = WorkerBuilder::new("morning-cereal")
@@ -72,6 +106,18 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         worker.run().await;
     });
 
+    // Ежедневный челлендж генерируется сразу после полуночи UTC
+    let daily_schedule = Schedule::from_str("0 1 0 * * *").unwrap();
+    let daily_worker = WorkerBuilder::new("daily-challenge")
+        .retry(apalis::layers::retry::RetryPolicy::retries(5))
+        .data(state.clone())
+        .backend(CronStream::new(daily_schedule))
+        .build_fn(tasks::daily_task);
+
+    tokio::spawn(async move {
+        daily_worker.run().await;
+    });
+
     let addr: SocketAddr = server_config.get_addr();
 
     tracing::info!(message = "Starting server.", %addr);
@@ -84,12 +130,29 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         .merge(rest::images::routes())
         .merge(rest::topics::routes())
         .merge(rest::lessons::routes())
+        .merge(rest::lesson_progress::routes())
+        .merge(rest::content_reviews::routes())
+        .merge(rest::question_reports::routes())
+        .merge(rest::notifications::routes())
         .merge(rest::questions::routes())
+        .merge(rest::search::routes())
+        .merge(rest::question_export::routes())
+        .merge(rest::question_import::routes())
         .merge(rest::answers::routes())
         .merge(rest::user_favorite_questions::routes())
         .merge(rest::categories::routes())
+        .merge(rest::license_classes::routes())
+        .merge(rest::regions::routes())
         .merge(rest::question_categories::routes())
+        .merge(rest::question_revisions::routes())
+        .merge(rest::question_translations::routes())
+        .merge(rest::road_signs::routes())
+        .merge(rest::road_sign_links::routes())
         .merge(rest::tests::routes())
+        .merge(rest::test_templates::routes())
+        .merge(rest::daily_challenges::routes())
+        .merge(rest::duels::routes())
+        .merge(rest::classroom_sessions::routes())
         .layer(
             tower_http::trace::TraceLayer::new_for_http().make_span_with(
                 tower_http::trace::DefaultMakeSpan::default().include_headers(true),
@@ -137,7 +200,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 async fn healthcheck(
     axum::extract::State(state): axum::extract::State<AppContext>,
 ) -> impl IntoResponse {
-    if let Ok(_) = state.db.ping().await {
+    if state.db.ping().await.is_ok() {
         return ("Ok").into_response();
     }
 
@@ -0,0 +1,99 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Стоимость вопроса в баллах
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Questions::Table)
+                    .add_column(small_integer(Questions::Points).default(1))
+                    .to_owned(),
+            )
+            .await?;
+
+        // Политика оценивания и набранные баллы теста
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Tests::Table)
+                    .add_column(string(Tests::ScoringPolicy).default("exact"))
+                    .add_column(small_integer(Tests::MaxPoints).default(0))
+                    .add_column(decimal_len(Tests::EarnedPoints, 10, 2).default(0))
+                    .to_owned(),
+            )
+            .await?;
+
+        // Баллы за каждый вопрос теста
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(TestQuestions::Table)
+                    .add_column(small_integer(TestQuestions::Points).default(1))
+                    .add_column(decimal_len_null(TestQuestions::EarnedPoints, 10, 2))
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(TestQuestions::Table)
+                    .drop_column(TestQuestions::Points)
+                    .drop_column(TestQuestions::EarnedPoints)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Tests::Table)
+                    .drop_column(Tests::ScoringPolicy)
+                    .drop_column(Tests::MaxPoints)
+                    .drop_column(Tests::EarnedPoints)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Questions::Table)
+                    .drop_column(Questions::Points)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(Iden)]
+enum Questions {
+    Table,
+    Points,
+}
+
+#[derive(Iden)]
+enum Tests {
+    Table,
+    ScoringPolicy,
+    MaxPoints,
+    EarnedPoints,
+}
+
+#[derive(Iden)]
+enum TestQuestions {
+    Table,
+    Points,
+    EarnedPoints,
+}
@@ -0,0 +1,78 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Связь повторных попыток с исходным тестом
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Tests::Table)
+                    .add_column(uuid_null(Tests::RetakeOfId))
+                    .add_column(uuid_null(Tests::SeriesId))
+                    .add_column(small_integer(Tests::AttemptNumber).default(1))
+                    .add_foreign_key(
+                        TableForeignKey::new()
+                            .name("fk_tests_retake_of")
+                            .from_tbl(Tests::Table)
+                            .from_col(Tests::RetakeOfId)
+                            .to_tbl(Tests::Table)
+                            .to_col(Tests::Id)
+                            .on_delete(ForeignKeyAction::SetNull)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_tests_series_id")
+                    .table(Tests::Table)
+                    .col(Tests::SeriesId)
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_tests_series_id")
+                    .table(Tests::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Tests::Table)
+                    .drop_foreign_key("fk_tests_retake_of")
+                    .drop_column(Tests::RetakeOfId)
+                    .drop_column(Tests::SeriesId)
+                    .drop_column(Tests::AttemptNumber)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(Iden)]
+enum Tests {
+    Table,
+    Id,
+    RetakeOfId,
+    SeriesId,
+    AttemptNumber,
+}
@@ -0,0 +1,135 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+use super::utils::table_auto_tz;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let table = table_auto_tz(TestTemplates::Table)
+            .col(
+                pk_uuid(TestTemplates::Id)
+                    .extra("DEFAULT gen_random_uuid()")
+                    .primary_key(),
+            )
+            .col(string(TestTemplates::Title))
+            .col(string(TestTemplates::Lang))
+            .col(integer_null(TestTemplates::TimeLimitSeconds))
+            .col(boolean(TestTemplates::IsPublished).default(false))
+            .col(uuid_null(TestTemplates::CreatedBy))
+            .foreign_key(
+                ForeignKey::create()
+                    .name("fk_test_templates_created_by")
+                    .from(TestTemplates::Table, TestTemplates::CreatedBy)
+                    .to(Users::Table, Users::Id)
+                    .on_delete(ForeignKeyAction::SetNull)
+                    .on_update(ForeignKeyAction::Cascade),
+            )
+            .to_owned();
+        manager.create_table(table).await?;
+
+        let table = Table::create()
+            .table(TestTemplateQuestions::Table)
+            .if_not_exists()
+            .col(uuid(TestTemplateQuestions::TemplateId))
+            .col(uuid(TestTemplateQuestions::QuestionId))
+            .col(small_integer(TestTemplateQuestions::QuestionOrder))
+            .primary_key(
+                Index::create()
+                    .col(TestTemplateQuestions::TemplateId)
+                    .col(TestTemplateQuestions::QuestionId),
+            )
+            .foreign_key(
+                ForeignKey::create()
+                    .name("fk_test_template_questions_template")
+                    .from(
+                        TestTemplateQuestions::Table,
+                        TestTemplateQuestions::TemplateId,
+                    )
+                    .to(TestTemplates::Table, TestTemplates::Id)
+                    .on_delete(ForeignKeyAction::Cascade)
+                    .on_update(ForeignKeyAction::Cascade),
+            )
+            .foreign_key(
+                ForeignKey::create()
+                    .name("fk_test_template_questions_question")
+                    .from(
+                        TestTemplateQuestions::Table,
+                        TestTemplateQuestions::QuestionId,
+                    )
+                    .to(Questions::Table, Questions::Id)
+                    .on_delete(ForeignKeyAction::Cascade)
+                    .on_update(ForeignKeyAction::Cascade),
+            )
+            .to_owned();
+        manager.create_table(table).await?;
+
+        // Лимит времени теста, скопированный из шаблона
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Tests::Table)
+                    .add_column(integer_null(Tests::TimeLimitSeconds))
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Tests::Table)
+                    .drop_column(Tests::TimeLimitSeconds)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .drop_table(Table::drop().table(TestTemplateQuestions::Table).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(TestTemplates::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(Iden)]
+pub enum TestTemplates {
+    Table,
+    Id,
+    Title,
+    Lang,
+    TimeLimitSeconds,
+    IsPublished,
+    CreatedBy,
+}
+
+#[derive(Iden)]
+pub enum TestTemplateQuestions {
+    Table,
+    TemplateId,
+    QuestionId,
+    QuestionOrder,
+}
+
+#[derive(Iden)]
+enum Users {
+    Table,
+    Id,
+}
+
+#[derive(Iden)]
+enum Questions {
+    Table,
+    Id,
+}
+
+#[derive(Iden)]
+enum Tests {
+    Table,
+    TimeLimitSeconds,
+}
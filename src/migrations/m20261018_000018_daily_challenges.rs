@@ -0,0 +1,116 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let table = Table::create()
+            .table(DailyChallenges::Table)
+            .if_not_exists()
+            .col(
+                pk_uuid(DailyChallenges::Id)
+                    .extra("DEFAULT gen_random_uuid()")
+                    .primary_key(),
+            )
+            .col(date(DailyChallenges::ChallengeDate))
+            .col(string(DailyChallenges::Lang))
+            .col(small_integer(DailyChallenges::QuestionsCount))
+            .col(
+                timestamp_with_time_zone(DailyChallenges::CreatedAt)
+                    .default(Expr::current_timestamp()),
+            )
+            .to_owned();
+        manager.create_table(table).await?;
+
+        // Один челлендж на день для каждого языка
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_daily_challenges_date_lang")
+                    .table(DailyChallenges::Table)
+                    .col(DailyChallenges::ChallengeDate)
+                    .col(DailyChallenges::Lang)
+                    .unique()
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await?;
+
+        let table = Table::create()
+            .table(DailyChallengeQuestions::Table)
+            .if_not_exists()
+            .col(uuid(DailyChallengeQuestions::ChallengeId))
+            .col(uuid(DailyChallengeQuestions::QuestionId))
+            .col(small_integer(DailyChallengeQuestions::QuestionOrder))
+            .primary_key(
+                Index::create()
+                    .col(DailyChallengeQuestions::ChallengeId)
+                    .col(DailyChallengeQuestions::QuestionId),
+            )
+            .foreign_key(
+                ForeignKey::create()
+                    .name("fk_daily_challenge_questions_challenge")
+                    .from(
+                        DailyChallengeQuestions::Table,
+                        DailyChallengeQuestions::ChallengeId,
+                    )
+                    .to(DailyChallenges::Table, DailyChallenges::Id)
+                    .on_delete(ForeignKeyAction::Cascade)
+                    .on_update(ForeignKeyAction::Cascade),
+            )
+            .foreign_key(
+                ForeignKey::create()
+                    .name("fk_daily_challenge_questions_question")
+                    .from(
+                        DailyChallengeQuestions::Table,
+                        DailyChallengeQuestions::QuestionId,
+                    )
+                    .to(Questions::Table, Questions::Id)
+                    .on_delete(ForeignKeyAction::Cascade)
+                    .on_update(ForeignKeyAction::Cascade),
+            )
+            .to_owned();
+        manager.create_table(table).await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(
+                Table::drop()
+                    .table(DailyChallengeQuestions::Table)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .drop_table(Table::drop().table(DailyChallenges::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(Iden)]
+pub enum DailyChallenges {
+    Table,
+    Id,
+    ChallengeDate,
+    Lang,
+    QuestionsCount,
+    CreatedAt,
+}
+
+#[derive(Iden)]
+pub enum DailyChallengeQuestions {
+    Table,
+    ChallengeId,
+    QuestionId,
+    QuestionOrder,
+}
+
+#[derive(Iden)]
+enum Questions {
+    Table,
+    Id,
+}
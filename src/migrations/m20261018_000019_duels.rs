@@ -0,0 +1,109 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+use super::utils::table_auto_tz;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let table = table_auto_tz(Duels::Table)
+            .col(
+                pk_uuid(Duels::Id)
+                    .extra("DEFAULT gen_random_uuid()")
+                    .primary_key(),
+            )
+            .col(string(Duels::Lang))
+            .col(small_integer(Duels::QuestionsCount))
+            .col(string_null(Duels::InviteCode).unique_key())
+            .col(string(Duels::Status).default("waiting"))
+            .col(timestamp_with_time_zone_null(Duels::FinishedAt))
+            .to_owned();
+        manager.create_table(table).await?;
+
+        let table = Table::create()
+            .table(DuelPlayers::Table)
+            .if_not_exists()
+            .col(uuid(DuelPlayers::DuelId))
+            .col(uuid(DuelPlayers::UserId))
+            .col(uuid_null(DuelPlayers::TestId))
+            .col(small_integer(DuelPlayers::PlayerOrder))
+            .col(boolean(DuelPlayers::IsWinner).default(false))
+            .primary_key(
+                Index::create()
+                    .col(DuelPlayers::DuelId)
+                    .col(DuelPlayers::UserId),
+            )
+            .foreign_key(
+                ForeignKey::create()
+                    .name("fk_duel_players_duel")
+                    .from(DuelPlayers::Table, DuelPlayers::DuelId)
+                    .to(Duels::Table, Duels::Id)
+                    .on_delete(ForeignKeyAction::Cascade)
+                    .on_update(ForeignKeyAction::Cascade),
+            )
+            .foreign_key(
+                ForeignKey::create()
+                    .name("fk_duel_players_user")
+                    .from(DuelPlayers::Table, DuelPlayers::UserId)
+                    .to(Users::Table, Users::Id)
+                    .on_delete(ForeignKeyAction::Cascade)
+                    .on_update(ForeignKeyAction::Cascade),
+            )
+            .foreign_key(
+                ForeignKey::create()
+                    .name("fk_duel_players_test")
+                    .from(DuelPlayers::Table, DuelPlayers::TestId)
+                    .to(Tests::Table, Tests::Id)
+                    .on_delete(ForeignKeyAction::SetNull)
+                    .on_update(ForeignKeyAction::Cascade),
+            )
+            .to_owned();
+        manager.create_table(table).await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(DuelPlayers::Table).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(Duels::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(Iden)]
+pub enum Duels {
+    Table,
+    Id,
+    Lang,
+    QuestionsCount,
+    InviteCode,
+    Status,
+    FinishedAt,
+}
+
+#[derive(Iden)]
+pub enum DuelPlayers {
+    Table,
+    DuelId,
+    UserId,
+    TestId,
+    PlayerOrder,
+    IsWinner,
+}
+
+#[derive(Iden)]
+enum Users {
+    Table,
+    Id,
+}
+
+#[derive(Iden)]
+enum Tests {
+    Table,
+    Id,
+}
@@ -0,0 +1,194 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+use super::utils::table_auto_tz;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let table = table_auto_tz(ClassroomSessions::Table)
+            .col(
+                pk_uuid(ClassroomSessions::Id)
+                    .extra("DEFAULT gen_random_uuid()")
+                    .primary_key(),
+            )
+            .col(uuid(ClassroomSessions::HostId))
+            .col(string(ClassroomSessions::Title))
+            .col(string(ClassroomSessions::Lang))
+            .col(string(ClassroomSessions::Pin))
+            .col(string(ClassroomSessions::Status).default("lobby"))
+            .col(small_integer(ClassroomSessions::CurrentQuestionOrder).default(0))
+            .col(timestamp_with_time_zone_null(ClassroomSessions::FinishedAt))
+            .foreign_key(
+                ForeignKey::create()
+                    .name("fk_classroom_sessions_host")
+                    .from(ClassroomSessions::Table, ClassroomSessions::HostId)
+                    .to(Users::Table, Users::Id)
+                    .on_delete(ForeignKeyAction::Cascade)
+                    .on_update(ForeignKeyAction::Cascade),
+            )
+            .to_owned();
+        manager.create_table(table).await?;
+
+        // PIN ищется только среди незавершённых сессий
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_classroom_sessions_pin")
+                    .table(ClassroomSessions::Table)
+                    .col(ClassroomSessions::Pin)
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await?;
+
+        let table = Table::create()
+            .table(ClassroomSessionQuestions::Table)
+            .if_not_exists()
+            .col(uuid(ClassroomSessionQuestions::SessionId))
+            .col(uuid(ClassroomSessionQuestions::QuestionId))
+            .col(small_integer(ClassroomSessionQuestions::QuestionOrder))
+            .primary_key(
+                Index::create()
+                    .col(ClassroomSessionQuestions::SessionId)
+                    .col(ClassroomSessionQuestions::QuestionId),
+            )
+            .foreign_key(
+                ForeignKey::create()
+                    .name("fk_classroom_session_questions_session")
+                    .from(
+                        ClassroomSessionQuestions::Table,
+                        ClassroomSessionQuestions::SessionId,
+                    )
+                    .to(ClassroomSessions::Table, ClassroomSessions::Id)
+                    .on_delete(ForeignKeyAction::Cascade)
+                    .on_update(ForeignKeyAction::Cascade),
+            )
+            .foreign_key(
+                ForeignKey::create()
+                    .name("fk_classroom_session_questions_question")
+                    .from(
+                        ClassroomSessionQuestions::Table,
+                        ClassroomSessionQuestions::QuestionId,
+                    )
+                    .to(Questions::Table, Questions::Id)
+                    .on_delete(ForeignKeyAction::Cascade)
+                    .on_update(ForeignKeyAction::Cascade),
+            )
+            .to_owned();
+        manager.create_table(table).await?;
+
+        let table = Table::create()
+            .table(ClassroomParticipants::Table)
+            .if_not_exists()
+            .col(uuid(ClassroomParticipants::SessionId))
+            .col(uuid(ClassroomParticipants::UserId))
+            .col(uuid_null(ClassroomParticipants::TestId))
+            .col(
+                timestamp_with_time_zone(ClassroomParticipants::JoinedAt)
+                    .default(Expr::current_timestamp()),
+            )
+            .primary_key(
+                Index::create()
+                    .col(ClassroomParticipants::SessionId)
+                    .col(ClassroomParticipants::UserId),
+            )
+            .foreign_key(
+                ForeignKey::create()
+                    .name("fk_classroom_participants_session")
+                    .from(
+                        ClassroomParticipants::Table,
+                        ClassroomParticipants::SessionId,
+                    )
+                    .to(ClassroomSessions::Table, ClassroomSessions::Id)
+                    .on_delete(ForeignKeyAction::Cascade)
+                    .on_update(ForeignKeyAction::Cascade),
+            )
+            .foreign_key(
+                ForeignKey::create()
+                    .name("fk_classroom_participants_user")
+                    .from(ClassroomParticipants::Table, ClassroomParticipants::UserId)
+                    .to(Users::Table, Users::Id)
+                    .on_delete(ForeignKeyAction::Cascade)
+                    .on_update(ForeignKeyAction::Cascade),
+            )
+            .foreign_key(
+                ForeignKey::create()
+                    .name("fk_classroom_participants_test")
+                    .from(ClassroomParticipants::Table, ClassroomParticipants::TestId)
+                    .to(Tests::Table, Tests::Id)
+                    .on_delete(ForeignKeyAction::SetNull)
+                    .on_update(ForeignKeyAction::Cascade),
+            )
+            .to_owned();
+        manager.create_table(table).await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(ClassroomParticipants::Table).to_owned())
+            .await?;
+        manager
+            .drop_table(
+                Table::drop()
+                    .table(ClassroomSessionQuestions::Table)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .drop_table(Table::drop().table(ClassroomSessions::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(Iden)]
+pub enum ClassroomSessions {
+    Table,
+    Id,
+    HostId,
+    Title,
+    Lang,
+    Pin,
+    Status,
+    CurrentQuestionOrder,
+    FinishedAt,
+}
+
+#[derive(Iden)]
+pub enum ClassroomSessionQuestions {
+    Table,
+    SessionId,
+    QuestionId,
+    QuestionOrder,
+}
+
+#[derive(Iden)]
+pub enum ClassroomParticipants {
+    Table,
+    SessionId,
+    UserId,
+    TestId,
+    JoinedAt,
+}
+
+#[derive(Iden)]
+enum Users {
+    Table,
+    Id,
+}
+
+#[derive(Iden)]
+enum Questions {
+    Table,
+    Id,
+}
+
+#[derive(Iden)]
+enum Tests {
+    Table,
+    Id,
+}
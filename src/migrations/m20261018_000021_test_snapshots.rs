@@ -0,0 +1,190 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // История тестов не должна зависеть от живого контента
+        for (name, table) in [
+            ("fk_test_questions_question", "test_questions"),
+            ("fk_test_question_answers_question", "test_question_answers"),
+            ("fk_test_question_answers_answer", "test_question_answers"),
+        ] {
+            manager
+                .drop_foreign_key(
+                    ForeignKey::drop()
+                        .name(name)
+                        .table(Alias::new(table))
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        // Снимок вопроса на момент создания теста
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(TestQuestions::Table)
+                    .add_column(string(TestQuestions::QuestionName).default(""))
+                    .add_column(text_null(TestQuestions::QuestionContent))
+                    .add_column(text(TestQuestions::Explanation).default(""))
+                    .to_owned(),
+            )
+            .await?;
+
+        let table = Table::create()
+            .table(TestQuestionOptions::Table)
+            .if_not_exists()
+            .col(uuid(TestQuestionOptions::TestId))
+            .col(uuid(TestQuestionOptions::QuestionId))
+            .col(uuid(TestQuestionOptions::AnswerId))
+            .col(string(TestQuestionOptions::Value))
+            .col(boolean(TestQuestionOptions::IsCorrect))
+            .col(small_integer(TestQuestionOptions::OptionOrder))
+            .primary_key(
+                Index::create()
+                    .col(TestQuestionOptions::TestId)
+                    .col(TestQuestionOptions::QuestionId)
+                    .col(TestQuestionOptions::AnswerId),
+            )
+            .foreign_key(
+                ForeignKey::create()
+                    .name("fk_test_question_options_test")
+                    .from(TestQuestionOptions::Table, TestQuestionOptions::TestId)
+                    .to(Tests::Table, Tests::Id)
+                    .on_delete(ForeignKeyAction::Cascade)
+                    .on_update(ForeignKeyAction::Cascade),
+            )
+            .to_owned();
+        manager.create_table(table).await?;
+
+        // Заполняем снимки для уже пройденных тестов
+        let db = manager.get_connection();
+        db.execute_unprepared(
+            "UPDATE test_questions tq \
+             SET question_name = q.name, question_content = q.content, explanation = q.explanation \
+             FROM questions q WHERE q.id = tq.question_id",
+        )
+        .await?;
+        db.execute_unprepared(
+            "INSERT INTO test_question_options \
+             (test_id, question_id, answer_id, value, is_correct, option_order) \
+             SELECT tq.test_id, tq.question_id, a.id, a.value, a.is_correct, \
+             ROW_NUMBER() OVER (PARTITION BY tq.test_id, tq.question_id ORDER BY a.id) \
+             FROM test_questions tq JOIN answers a ON a.question_id = tq.question_id",
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(TestQuestionOptions::Table).to_owned())
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(TestQuestions::Table)
+                    .drop_column(TestQuestions::QuestionName)
+                    .drop_column(TestQuestions::QuestionContent)
+                    .drop_column(TestQuestions::Explanation)
+                    .to_owned(),
+            )
+            .await?;
+
+        // Ответы удалённых вопросов не восстановить, убираем их перед возвратом ключей
+        let db = manager.get_connection();
+        db.execute_unprepared(
+            "DELETE FROM test_question_answers tqa WHERE NOT EXISTS \
+             (SELECT 1 FROM answers a WHERE a.id = tqa.answer_id)",
+        )
+        .await?;
+        db.execute_unprepared(
+            "DELETE FROM test_questions tq WHERE NOT EXISTS \
+             (SELECT 1 FROM questions q WHERE q.id = tq.question_id)",
+        )
+        .await?;
+
+        manager
+            .create_foreign_key(
+                ForeignKey::create()
+                    .name("fk_test_questions_question")
+                    .from(TestQuestions::Table, TestQuestions::QuestionId)
+                    .to(Questions::Table, Questions::Id)
+                    .on_delete(ForeignKeyAction::Cascade)
+                    .on_update(ForeignKeyAction::Cascade)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_foreign_key(
+                ForeignKey::create()
+                    .name("fk_test_question_answers_question")
+                    .from(TestQuestionAnswers::Table, TestQuestionAnswers::QuestionId)
+                    .to(Questions::Table, Questions::Id)
+                    .on_delete(ForeignKeyAction::Cascade)
+                    .on_update(ForeignKeyAction::Cascade)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_foreign_key(
+                ForeignKey::create()
+                    .name("fk_test_question_answers_answer")
+                    .from(TestQuestionAnswers::Table, TestQuestionAnswers::AnswerId)
+                    .to(Answers::Table, Answers::Id)
+                    .on_delete(ForeignKeyAction::Cascade)
+                    .on_update(ForeignKeyAction::Cascade)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(Iden)]
+pub enum TestQuestions {
+    Table,
+    QuestionId,
+    QuestionName,
+    QuestionContent,
+    Explanation,
+}
+
+#[derive(Iden)]
+pub enum TestQuestionAnswers {
+    Table,
+    QuestionId,
+    AnswerId,
+}
+
+#[derive(Iden)]
+pub enum TestQuestionOptions {
+    Table,
+    TestId,
+    QuestionId,
+    AnswerId,
+    Value,
+    IsCorrect,
+    OptionOrder,
+}
+
+#[derive(Iden)]
+enum Tests {
+    Table,
+    Id,
+}
+
+#[derive(Iden)]
+enum Questions {
+    Table,
+    Id,
+}
+
+#[derive(Iden)]
+enum Answers {
+    Table,
+    Id,
+}
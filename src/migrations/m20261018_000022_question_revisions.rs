@@ -0,0 +1,106 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let table = Table::create()
+            .table(QuestionRevisions::Table)
+            .if_not_exists()
+            .col(
+                pk_uuid(QuestionRevisions::Id)
+                    .extra("DEFAULT gen_random_uuid()")
+                    .primary_key(),
+            )
+            .col(uuid(QuestionRevisions::QuestionId))
+            .col(integer(QuestionRevisions::Revision))
+            .col(uuid_null(QuestionRevisions::AuthorId))
+            .col(string(QuestionRevisions::Action))
+            .col(json_binary(QuestionRevisions::Snapshot))
+            .col(
+                timestamp_with_time_zone(QuestionRevisions::CreatedAt)
+                    .default(Expr::current_timestamp()),
+            )
+            .foreign_key(
+                ForeignKey::create()
+                    .name("fk_question_revisions_question")
+                    .from(QuestionRevisions::Table, QuestionRevisions::QuestionId)
+                    .to(Questions::Table, Questions::Id)
+                    .on_delete(ForeignKeyAction::Cascade)
+                    .on_update(ForeignKeyAction::Cascade),
+            )
+            .foreign_key(
+                ForeignKey::create()
+                    .name("fk_question_revisions_author")
+                    .from(QuestionRevisions::Table, QuestionRevisions::AuthorId)
+                    .to(Users::Table, Users::Id)
+                    .on_delete(ForeignKeyAction::SetNull)
+                    .on_update(ForeignKeyAction::Cascade),
+            )
+            .to_owned();
+        manager.create_table(table).await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_question_revisions_question_revision")
+                    .table(QuestionRevisions::Table)
+                    .col(QuestionRevisions::QuestionId)
+                    .col(QuestionRevisions::Revision)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        // Первая ревизия для уже существующих вопросов
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "INSERT INTO question_revisions (question_id, revision, action, snapshot) \
+                 SELECT q.id, 1, 'initial', jsonb_build_object( \
+                 'topic_id', q.topic_id, 'name', q.name, 'lang', q.lang, \
+                 'content', q.content, 'explanation', q.explanation, 'points', q.points, \
+                 'answers', COALESCE((SELECT jsonb_agg(jsonb_build_object( \
+                 'id', a.id, 'value', a.value, 'is_correct', a.is_correct) ORDER BY a.id) \
+                 FROM answers a WHERE a.question_id = q.id), '[]'::jsonb), \
+                 'category_ids', COALESCE((SELECT jsonb_agg(qc.category_id ORDER BY qc.category_id) \
+                 FROM question_categories qc WHERE qc.question_id = q.id), '[]'::jsonb)) \
+                 FROM questions q",
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(QuestionRevisions::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(Iden)]
+pub enum QuestionRevisions {
+    Table,
+    Id,
+    QuestionId,
+    Revision,
+    AuthorId,
+    Action,
+    Snapshot,
+    CreatedAt,
+}
+
+#[derive(Iden)]
+enum Questions {
+    Table,
+    Id,
+}
+
+#[derive(Iden)]
+enum Users {
+    Table,
+    Id,
+}
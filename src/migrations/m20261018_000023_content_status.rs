@@ -0,0 +1,154 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Новый контент создаётся черновиком, существующий считается опубликованным
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Questions::Table)
+                    .add_column(string(Questions::Status).default("draft"))
+                    .add_column(text_null(Questions::ReviewComment))
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Lessons::Table)
+                    .add_column(string(Lessons::Status).default("draft"))
+                    .add_column(text_null(Lessons::ReviewComment))
+                    .to_owned(),
+            )
+            .await?;
+
+        let db = manager.get_connection();
+        db.execute_unprepared("UPDATE questions SET status = 'published'")
+            .await?;
+        db.execute_unprepared("UPDATE lessons SET status = 'published'")
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_questions_status")
+                    .table(Questions::Table)
+                    .col(Questions::Status)
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await?;
+
+        let table = Table::create()
+            .table(ContentReviews::Table)
+            .if_not_exists()
+            .col(
+                pk_uuid(ContentReviews::Id)
+                    .extra("DEFAULT gen_random_uuid()")
+                    .primary_key(),
+            )
+            .col(string(ContentReviews::ContentType))
+            .col(uuid(ContentReviews::ContentId))
+            .col(uuid_null(ContentReviews::UserId))
+            .col(string(ContentReviews::Action))
+            .col(string(ContentReviews::Status))
+            .col(text_null(ContentReviews::Comment))
+            .col(
+                timestamp_with_time_zone(ContentReviews::CreatedAt)
+                    .default(Expr::current_timestamp()),
+            )
+            .foreign_key(
+                ForeignKey::create()
+                    .name("fk_content_reviews_user")
+                    .from(ContentReviews::Table, ContentReviews::UserId)
+                    .to(Users::Table, Users::Id)
+                    .on_delete(ForeignKeyAction::SetNull)
+                    .on_update(ForeignKeyAction::Cascade),
+            )
+            .to_owned();
+        manager.create_table(table).await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_content_reviews_content")
+                    .table(ContentReviews::Table)
+                    .col(ContentReviews::ContentType)
+                    .col(ContentReviews::ContentId)
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(ContentReviews::Table).to_owned())
+            .await?;
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_questions_status")
+                    .table(Questions::Table)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Lessons::Table)
+                    .drop_column(Lessons::Status)
+                    .drop_column(Lessons::ReviewComment)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Questions::Table)
+                    .drop_column(Questions::Status)
+                    .drop_column(Questions::ReviewComment)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(Iden)]
+pub enum ContentReviews {
+    Table,
+    Id,
+    ContentType,
+    ContentId,
+    UserId,
+    Action,
+    Status,
+    Comment,
+    CreatedAt,
+}
+
+#[derive(Iden)]
+enum Questions {
+    Table,
+    Status,
+    ReviewComment,
+}
+
+#[derive(Iden)]
+enum Lessons {
+    Table,
+    Status,
+    ReviewComment,
+}
+
+#[derive(Iden)]
+enum Users {
+    Table,
+    Id,
+}
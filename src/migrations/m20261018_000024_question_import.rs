@@ -0,0 +1,79 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Внешний ID из таблиц контент-команды и ссылка на картинку
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Questions::Table)
+                    .add_column(string_null(Questions::ExternalId))
+                    .add_column(uuid_null(Questions::ImageId))
+                    .add_foreign_key(
+                        TableForeignKey::new()
+                            .name("fk_questions_image")
+                            .from_tbl(Questions::Table)
+                            .from_col(Questions::ImageId)
+                            .to_tbl(Images::Table)
+                            .to_col(Images::Id)
+                            .on_delete(ForeignKeyAction::SetNull)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_questions_external_id")
+                    .table(Questions::Table)
+                    .col(Questions::ExternalId)
+                    .unique()
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_questions_external_id")
+                    .table(Questions::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Questions::Table)
+                    .drop_foreign_key("fk_questions_image")
+                    .drop_column(Questions::ImageId)
+                    .drop_column(Questions::ExternalId)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(Iden)]
+enum Questions {
+    Table,
+    ExternalId,
+    ImageId,
+}
+
+#[derive(Iden)]
+enum Images {
+    Table,
+    Id,
+}
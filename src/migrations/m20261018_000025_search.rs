@@ -0,0 +1,88 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        db.execute_unprepared("CREATE EXTENSION IF NOT EXISTS pg_trgm")
+            .await?;
+
+        // Словарь полнотекстового поиска по коду языка вопроса
+        db.execute_unprepared(
+            r#"CREATE OR REPLACE FUNCTION search_config(lang text) RETURNS regconfig AS $$
+                SELECT CASE lang
+                    WHEN 'ru' THEN 'russian'
+                    WHEN 'en' THEN 'english'
+                    WHEN 'de' THEN 'german'
+                    WHEN 'fr' THEN 'french'
+                    WHEN 'es' THEN 'spanish'
+                    ELSE 'simple'
+                END::regconfig
+            $$ LANGUAGE sql IMMUTABLE"#,
+        )
+        .await?;
+
+        db.execute_unprepared(
+            r#"ALTER TABLE questions ADD COLUMN search_vector tsvector GENERATED ALWAYS AS (
+                setweight(to_tsvector(search_config(lang), coalesce(name, '')), 'A') ||
+                setweight(to_tsvector(search_config(lang), coalesce(content, '')), 'B') ||
+                setweight(to_tsvector(search_config(lang), coalesce(explanation, '')), 'C')
+            ) STORED"#,
+        )
+        .await?;
+
+        // У уроков пока нет языка, поэтому без стемминга
+        db.execute_unprepared(
+            r#"ALTER TABLE lessons ADD COLUMN search_vector tsvector GENERATED ALWAYS AS (
+                to_tsvector('simple', content)
+            ) STORED"#,
+        )
+        .await?;
+
+        db.execute_unprepared(
+            "CREATE INDEX IF NOT EXISTS idx_questions_search ON questions USING GIN (search_vector)",
+        )
+        .await?;
+        db.execute_unprepared(
+            "CREATE INDEX IF NOT EXISTS idx_lessons_search ON lessons USING GIN (search_vector)",
+        )
+        .await?;
+
+        // Триграммы для запросов с опечатками
+        db.execute_unprepared(
+            "CREATE INDEX IF NOT EXISTS idx_questions_name_trgm ON questions USING GIN (name gin_trgm_ops)",
+        )
+        .await?;
+        db.execute_unprepared(
+            "CREATE INDEX IF NOT EXISTS idx_lessons_content_trgm ON lessons USING GIN (content gin_trgm_ops)",
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        db.execute_unprepared("DROP INDEX IF EXISTS idx_lessons_content_trgm")
+            .await?;
+        db.execute_unprepared("DROP INDEX IF EXISTS idx_questions_name_trgm")
+            .await?;
+        db.execute_unprepared("DROP INDEX IF EXISTS idx_lessons_search")
+            .await?;
+        db.execute_unprepared("DROP INDEX IF EXISTS idx_questions_search")
+            .await?;
+        db.execute_unprepared("ALTER TABLE lessons DROP COLUMN IF EXISTS search_vector")
+            .await?;
+        db.execute_unprepared("ALTER TABLE questions DROP COLUMN IF EXISTS search_vector")
+            .await?;
+        db.execute_unprepared("DROP FUNCTION IF EXISTS search_config(text)")
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,114 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Тип вопроса, существующие вопросы — выбор вариантов
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Questions::Table)
+                    .add_column(string(Questions::QuestionType).default("choice"))
+                    .to_owned(),
+            )
+            .await?;
+
+        // Данные ответа, зависящие от типа: позиция, число, область картинки
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Answers::Table)
+                    .add_column(json_binary_null(Answers::Payload))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(TestQuestions::Table)
+                    .add_column(string(TestQuestions::QuestionType).default("choice"))
+                    .add_column(json_binary_null(TestQuestions::Response))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(TestQuestionOptions::Table)
+                    .add_column(json_binary_null(TestQuestionOptions::Payload))
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(TestQuestionOptions::Table)
+                    .drop_column(TestQuestionOptions::Payload)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(TestQuestions::Table)
+                    .drop_column(TestQuestions::Response)
+                    .drop_column(TestQuestions::QuestionType)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Answers::Table)
+                    .drop_column(Answers::Payload)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Questions::Table)
+                    .drop_column(Questions::QuestionType)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(Iden)]
+enum Questions {
+    Table,
+    QuestionType,
+}
+
+#[derive(Iden)]
+enum Answers {
+    Table,
+    Payload,
+}
+
+#[derive(Iden)]
+enum TestQuestions {
+    Table,
+    QuestionType,
+    Response,
+}
+
+#[derive(Iden)]
+enum TestQuestionOptions {
+    Table,
+    Payload,
+}
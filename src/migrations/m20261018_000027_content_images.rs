@@ -0,0 +1,206 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Несколько картинок у вопроса, в заданном порядке
+        let table = Table::create()
+            .table(QuestionImages::Table)
+            .if_not_exists()
+            .col(uuid(QuestionImages::QuestionId))
+            .col(uuid(QuestionImages::ImageId))
+            .col(small_integer(QuestionImages::Position))
+            .primary_key(
+                Index::create()
+                    .col(QuestionImages::QuestionId)
+                    .col(QuestionImages::ImageId),
+            )
+            .foreign_key(
+                ForeignKey::create()
+                    .name("fk_question_images_question")
+                    .from(QuestionImages::Table, QuestionImages::QuestionId)
+                    .to(Questions::Table, Questions::Id)
+                    .on_delete(ForeignKeyAction::Cascade)
+                    .on_update(ForeignKeyAction::Cascade),
+            )
+            .foreign_key(
+                ForeignKey::create()
+                    .name("fk_question_images_image")
+                    .from(QuestionImages::Table, QuestionImages::ImageId)
+                    .to(Images::Table, Images::Id)
+                    .on_delete(ForeignKeyAction::Cascade)
+                    .on_update(ForeignKeyAction::Cascade),
+            )
+            .to_owned();
+        manager.create_table(table).await?;
+
+        // Переносим единственную картинку из questions.image_id
+        let db = manager.get_connection();
+        db.execute_unprepared(
+            "INSERT INTO question_images (question_id, image_id, position) \
+             SELECT id, image_id, 0 FROM questions WHERE image_id IS NOT NULL",
+        )
+        .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Questions::Table)
+                    .drop_foreign_key("fk_questions_image")
+                    .drop_column(Questions::ImageId)
+                    .to_owned(),
+            )
+            .await?;
+
+        // Картинка у варианта ответа
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Answers::Table)
+                    .add_column(uuid_null(Answers::ImageId))
+                    .add_foreign_key(
+                        TableForeignKey::new()
+                            .name("fk_answers_image")
+                            .from_tbl(Answers::Table)
+                            .from_col(Answers::ImageId)
+                            .to_tbl(Images::Table)
+                            .to_col(Images::Id)
+                            .on_delete(ForeignKeyAction::SetNull)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Снимки тестов хранят картинки без внешних ключей
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(TestQuestions::Table)
+                    .add_column(json_binary_null(TestQuestions::ImageIds))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(TestQuestionOptions::Table)
+                    .add_column(uuid_null(TestQuestionOptions::ImageId))
+                    .to_owned(),
+            )
+            .await?;
+
+        db.execute_unprepared(
+            "UPDATE test_questions tq \
+             SET image_ids = (SELECT jsonb_agg(qi.image_id ORDER BY qi.position) \
+             FROM question_images qi WHERE qi.question_id = tq.question_id)",
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(TestQuestionOptions::Table)
+                    .drop_column(TestQuestionOptions::ImageId)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(TestQuestions::Table)
+                    .drop_column(TestQuestions::ImageIds)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Answers::Table)
+                    .drop_foreign_key("fk_answers_image")
+                    .drop_column(Answers::ImageId)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Questions::Table)
+                    .add_column(uuid_null(Questions::ImageId))
+                    .add_foreign_key(
+                        TableForeignKey::new()
+                            .name("fk_questions_image")
+                            .from_tbl(Questions::Table)
+                            .from_col(Questions::ImageId)
+                            .to_tbl(Images::Table)
+                            .to_col(Images::Id)
+                            .on_delete(ForeignKeyAction::SetNull)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "UPDATE questions q SET image_id = (SELECT qi.image_id FROM question_images qi \
+                 WHERE qi.question_id = q.id ORDER BY qi.position LIMIT 1)",
+            )
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(QuestionImages::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(Iden)]
+enum QuestionImages {
+    Table,
+    QuestionId,
+    ImageId,
+    Position,
+}
+
+#[derive(Iden)]
+enum Questions {
+    Table,
+    Id,
+    ImageId,
+}
+
+#[derive(Iden)]
+enum Answers {
+    Table,
+    ImageId,
+}
+
+#[derive(Iden)]
+enum Images {
+    Table,
+    Id,
+}
+
+#[derive(Iden)]
+enum TestQuestions {
+    Table,
+    ImageIds,
+}
+
+#[derive(Iden)]
+enum TestQuestionOptions {
+    Table,
+    ImageId,
+}
@@ -0,0 +1,196 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let table = Table::create()
+            .table(RoadSigns::Table)
+            .if_not_exists()
+            .col(
+                pk_uuid(RoadSigns::Id)
+                    .extra("DEFAULT gen_random_uuid()")
+                    .primary_key(),
+            )
+            .col(string(RoadSigns::Code).unique_key())
+            .col(string(RoadSigns::SignGroup))
+            .col(uuid_null(RoadSigns::ImageId))
+            .foreign_key(
+                ForeignKey::create()
+                    .name("fk_road_signs_image")
+                    .from(RoadSigns::Table, RoadSigns::ImageId)
+                    .to(Images::Table, Images::Id)
+                    .on_delete(ForeignKeyAction::SetNull)
+                    .on_update(ForeignKeyAction::Cascade),
+            )
+            .to_owned();
+        manager.create_table(table).await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_road_signs_group")
+                    .table(RoadSigns::Table)
+                    .col(RoadSigns::SignGroup)
+                    .to_owned(),
+            )
+            .await?;
+
+        // Название и значение знака на каждом языке
+        let table = Table::create()
+            .table(RoadSignTranslations::Table)
+            .if_not_exists()
+            .col(uuid(RoadSignTranslations::SignId))
+            .col(string(RoadSignTranslations::Lang))
+            .col(string(RoadSignTranslations::Name))
+            .col(text(RoadSignTranslations::Meaning))
+            .primary_key(
+                Index::create()
+                    .col(RoadSignTranslations::SignId)
+                    .col(RoadSignTranslations::Lang),
+            )
+            .foreign_key(
+                ForeignKey::create()
+                    .name("fk_road_sign_translations_sign")
+                    .from(RoadSignTranslations::Table, RoadSignTranslations::SignId)
+                    .to(RoadSigns::Table, RoadSigns::Id)
+                    .on_delete(ForeignKeyAction::Cascade)
+                    .on_update(ForeignKeyAction::Cascade),
+            )
+            .to_owned();
+        manager.create_table(table).await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "CREATE INDEX idx_road_sign_translations_name_trgm \
+                 ON road_sign_translations USING GIN (name gin_trgm_ops)",
+            )
+            .await?;
+
+        let table = Table::create()
+            .table(QuestionRoadSigns::Table)
+            .if_not_exists()
+            .col(uuid(QuestionRoadSigns::QuestionId))
+            .col(uuid(QuestionRoadSigns::SignId))
+            .primary_key(
+                Index::create()
+                    .col(QuestionRoadSigns::QuestionId)
+                    .col(QuestionRoadSigns::SignId),
+            )
+            .foreign_key(
+                ForeignKey::create()
+                    .name("fk_question_road_signs_question")
+                    .from(QuestionRoadSigns::Table, QuestionRoadSigns::QuestionId)
+                    .to(Questions::Table, Questions::Id)
+                    .on_delete(ForeignKeyAction::Cascade)
+                    .on_update(ForeignKeyAction::Cascade),
+            )
+            .foreign_key(
+                ForeignKey::create()
+                    .name("fk_question_road_signs_sign")
+                    .from(QuestionRoadSigns::Table, QuestionRoadSigns::SignId)
+                    .to(RoadSigns::Table, RoadSigns::Id)
+                    .on_delete(ForeignKeyAction::Cascade)
+                    .on_update(ForeignKeyAction::Cascade),
+            )
+            .to_owned();
+        manager.create_table(table).await?;
+
+        let table = Table::create()
+            .table(LessonRoadSigns::Table)
+            .if_not_exists()
+            .col(uuid(LessonRoadSigns::LessonId))
+            .col(uuid(LessonRoadSigns::SignId))
+            .primary_key(
+                Index::create()
+                    .col(LessonRoadSigns::LessonId)
+                    .col(LessonRoadSigns::SignId),
+            )
+            .foreign_key(
+                ForeignKey::create()
+                    .name("fk_lesson_road_signs_lesson")
+                    .from(LessonRoadSigns::Table, LessonRoadSigns::LessonId)
+                    .to(Lessons::Table, Lessons::Id)
+                    .on_delete(ForeignKeyAction::Cascade)
+                    .on_update(ForeignKeyAction::Cascade),
+            )
+            .foreign_key(
+                ForeignKey::create()
+                    .name("fk_lesson_road_signs_sign")
+                    .from(LessonRoadSigns::Table, LessonRoadSigns::SignId)
+                    .to(RoadSigns::Table, RoadSigns::Id)
+                    .on_delete(ForeignKeyAction::Cascade)
+                    .on_update(ForeignKeyAction::Cascade),
+            )
+            .to_owned();
+        manager.create_table(table).await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(LessonRoadSigns::Table).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(QuestionRoadSigns::Table).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(RoadSignTranslations::Table).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(RoadSigns::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(Iden)]
+pub enum RoadSigns {
+    Table,
+    Id,
+    Code,
+    SignGroup,
+    ImageId,
+}
+
+#[derive(Iden)]
+pub enum RoadSignTranslations {
+    Table,
+    SignId,
+    Lang,
+    Name,
+    Meaning,
+}
+
+#[derive(Iden)]
+pub enum QuestionRoadSigns {
+    Table,
+    QuestionId,
+    SignId,
+}
+
+#[derive(Iden)]
+pub enum LessonRoadSigns {
+    Table,
+    LessonId,
+    SignId,
+}
+
+#[derive(Iden)]
+enum Images {
+    Table,
+    Id,
+}
+
+#[derive(Iden)]
+enum Questions {
+    Table,
+    Id,
+}
+
+#[derive(Iden)]
+enum Lessons {
+    Table,
+    Id,
+}
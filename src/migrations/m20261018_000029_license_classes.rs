@@ -0,0 +1,213 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let table = Table::create()
+            .table(LicenseClasses::Table)
+            .if_not_exists()
+            .col(string(LicenseClasses::Code).primary_key())
+            .col(string(LicenseClasses::Name))
+            .col(small_integer(LicenseClasses::Position))
+            .to_owned();
+        manager.create_table(table).await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "INSERT INTO license_classes (code, name, position) VALUES \
+                 ('AM', 'Mopeds', 1), \
+                 ('A1', 'Light motorcycles', 2), \
+                 ('A2', 'Medium motorcycles', 3), \
+                 ('A', 'Motorcycles', 4), \
+                 ('B1', 'Quadricycles', 5), \
+                 ('B', 'Cars', 6), \
+                 ('BE', 'Cars with trailers', 7), \
+                 ('C1', 'Medium trucks', 8), \
+                 ('C1E', 'Medium trucks with trailers', 9), \
+                 ('C', 'Trucks', 10), \
+                 ('CE', 'Trucks with trailers', 11), \
+                 ('D1', 'Minibuses', 12), \
+                 ('D1E', 'Minibuses with trailers', 13), \
+                 ('D', 'Buses', 14), \
+                 ('DE', 'Buses with trailers', 15)",
+            )
+            .await?;
+
+        // Вопрос без классов относится ко всем категориям прав
+        let table = Table::create()
+            .table(QuestionLicenseClasses::Table)
+            .if_not_exists()
+            .col(uuid(QuestionLicenseClasses::QuestionId))
+            .col(string(QuestionLicenseClasses::LicenseClass))
+            .primary_key(
+                Index::create()
+                    .col(QuestionLicenseClasses::QuestionId)
+                    .col(QuestionLicenseClasses::LicenseClass),
+            )
+            .foreign_key(
+                ForeignKey::create()
+                    .name("fk_question_license_classes_question")
+                    .from(
+                        QuestionLicenseClasses::Table,
+                        QuestionLicenseClasses::QuestionId,
+                    )
+                    .to(Questions::Table, Questions::Id)
+                    .on_delete(ForeignKeyAction::Cascade)
+                    .on_update(ForeignKeyAction::Cascade),
+            )
+            .foreign_key(
+                ForeignKey::create()
+                    .name("fk_question_license_classes_class")
+                    .from(
+                        QuestionLicenseClasses::Table,
+                        QuestionLicenseClasses::LicenseClass,
+                    )
+                    .to(LicenseClasses::Table, LicenseClasses::Code)
+                    .on_delete(ForeignKeyAction::Cascade)
+                    .on_update(ForeignKeyAction::Cascade),
+            )
+            .to_owned();
+        manager.create_table(table).await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_question_license_classes_class")
+                    .table(QuestionLicenseClasses::Table)
+                    .col(QuestionLicenseClasses::LicenseClass)
+                    .to_owned(),
+            )
+            .await?;
+
+        // Целевой класс в профиле пользователя
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Users::Table)
+                    .add_column(string_null(Users::LicenseClass))
+                    .add_foreign_key(
+                        TableForeignKey::new()
+                            .name("fk_users_license_class")
+                            .from_tbl(Users::Table)
+                            .from_col(Users::LicenseClass)
+                            .to_tbl(LicenseClasses::Table)
+                            .to_col(LicenseClasses::Code)
+                            .on_delete(ForeignKeyAction::SetNull)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(TestTemplates::Table)
+                    .add_column(string_null(TestTemplates::LicenseClass))
+                    .add_foreign_key(
+                        TableForeignKey::new()
+                            .name("fk_test_templates_license_class")
+                            .from_tbl(TestTemplates::Table)
+                            .from_col(TestTemplates::LicenseClass)
+                            .to_tbl(LicenseClasses::Table)
+                            .to_col(LicenseClasses::Code)
+                            .on_delete(ForeignKeyAction::SetNull)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Тест запоминает класс, под который собран, без внешнего ключа
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Tests::Table)
+                    .add_column(string_null(Tests::LicenseClass))
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Tests::Table)
+                    .drop_column(Tests::LicenseClass)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(TestTemplates::Table)
+                    .drop_column(TestTemplates::LicenseClass)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Users::Table)
+                    .drop_column(Users::LicenseClass)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .drop_table(
+                Table::drop()
+                    .table(QuestionLicenseClasses::Table)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .drop_table(Table::drop().table(LicenseClasses::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(Iden)]
+pub enum LicenseClasses {
+    Table,
+    Code,
+    Name,
+    Position,
+}
+
+#[derive(Iden)]
+pub enum QuestionLicenseClasses {
+    Table,
+    QuestionId,
+    LicenseClass,
+}
+
+#[derive(Iden)]
+enum Questions {
+    Table,
+    Id,
+}
+
+#[derive(Iden)]
+enum Users {
+    Table,
+    LicenseClass,
+}
+
+#[derive(Iden)]
+enum TestTemplates {
+    Table,
+    LicenseClass,
+}
+
+#[derive(Iden)]
+enum Tests {
+    Table,
+    LicenseClass,
+}
@@ -0,0 +1,115 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Пустые даты — без ограничения, пустой регион — общие правила
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Questions::Table)
+                    .add_column(date_null(Questions::ValidFrom))
+                    .add_column(date_null(Questions::ValidTo))
+                    .add_column(string_null(Questions::Region))
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Lessons::Table)
+                    .add_column(date_null(Lessons::ValidFrom))
+                    .add_column(date_null(Lessons::ValidTo))
+                    .add_column(string_null(Lessons::Region))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_questions_valid_to")
+                    .table(Questions::Table)
+                    .col(Questions::ValidTo)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_questions_region")
+                    .table(Questions::Table)
+                    .col(Questions::Region)
+                    .to_owned(),
+            )
+            .await?;
+
+        // Юрисдикция, выбранная пользователем
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Users::Table)
+                    .add_column(string_null(Users::Region))
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Users::Table)
+                    .drop_column(Users::Region)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Lessons::Table)
+                    .drop_column(Lessons::ValidFrom)
+                    .drop_column(Lessons::ValidTo)
+                    .drop_column(Lessons::Region)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Questions::Table)
+                    .drop_column(Questions::ValidFrom)
+                    .drop_column(Questions::ValidTo)
+                    .drop_column(Questions::Region)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(Iden)]
+enum Questions {
+    Table,
+    ValidFrom,
+    ValidTo,
+    Region,
+}
+
+#[derive(Iden)]
+enum Lessons {
+    Table,
+    ValidFrom,
+    ValidTo,
+    Region,
+}
+
+#[derive(Iden)]
+enum Users {
+    Table,
+    Region,
+}
@@ -0,0 +1,111 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Переводы одного вопроса и ответа делят общий ID группы,
+        // существующие записи образуют группы из одного элемента
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Questions::Table)
+                    .add_column(uuid_null(Questions::TranslationGroupId))
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Answers::Table)
+                    .add_column(uuid_null(Answers::TranslationGroupId))
+                    .to_owned(),
+            )
+            .await?;
+
+        let db = manager.get_connection();
+        db.execute_unprepared("UPDATE questions SET translation_group_id = id")
+            .await?;
+        db.execute_unprepared("UPDATE answers SET translation_group_id = id")
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Questions::Table)
+                    .modify_column(
+                        uuid(Questions::TranslationGroupId).extra("DEFAULT gen_random_uuid()"),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Answers::Table)
+                    .modify_column(
+                        uuid(Answers::TranslationGroupId).extra("DEFAULT gen_random_uuid()"),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Не больше одного варианта вопроса на язык
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_questions_translation_group_lang")
+                    .table(Questions::Table)
+                    .col(Questions::TranslationGroupId)
+                    .col(Questions::Lang)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_answers_translation_group")
+                    .table(Answers::Table)
+                    .col(Answers::TranslationGroupId)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Answers::Table)
+                    .drop_column(Answers::TranslationGroupId)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Questions::Table)
+                    .drop_column(Questions::TranslationGroupId)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(Iden)]
+enum Questions {
+    Table,
+    TranslationGroupId,
+    Lang,
+}
+
+#[derive(Iden)]
+enum Answers {
+    Table,
+    TranslationGroupId,
+}
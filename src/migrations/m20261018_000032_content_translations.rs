@@ -0,0 +1,134 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Исходные поля topics.name, categories.name и lessons.content
+        // остаются последним звеном цепочки языков
+        let table = Table::create()
+            .table(TopicTranslations::Table)
+            .if_not_exists()
+            .col(uuid(TopicTranslations::TopicId))
+            .col(string(TopicTranslations::Lang))
+            .col(string(TopicTranslations::Name))
+            .primary_key(
+                Index::create()
+                    .col(TopicTranslations::TopicId)
+                    .col(TopicTranslations::Lang),
+            )
+            .foreign_key(
+                ForeignKey::create()
+                    .name("fk_topic_translations_topic")
+                    .from(TopicTranslations::Table, TopicTranslations::TopicId)
+                    .to(Topics::Table, Topics::Id)
+                    .on_delete(ForeignKeyAction::Cascade)
+                    .on_update(ForeignKeyAction::Cascade),
+            )
+            .to_owned();
+        manager.create_table(table).await?;
+
+        let table = Table::create()
+            .table(CategoryTranslations::Table)
+            .if_not_exists()
+            .col(uuid(CategoryTranslations::CategoryId))
+            .col(string(CategoryTranslations::Lang))
+            .col(string(CategoryTranslations::Name))
+            .primary_key(
+                Index::create()
+                    .col(CategoryTranslations::CategoryId)
+                    .col(CategoryTranslations::Lang),
+            )
+            .foreign_key(
+                ForeignKey::create()
+                    .name("fk_category_translations_category")
+                    .from(
+                        CategoryTranslations::Table,
+                        CategoryTranslations::CategoryId,
+                    )
+                    .to(Categories::Table, Categories::Id)
+                    .on_delete(ForeignKeyAction::Cascade)
+                    .on_update(ForeignKeyAction::Cascade),
+            )
+            .to_owned();
+        manager.create_table(table).await?;
+
+        let table = Table::create()
+            .table(LessonTranslations::Table)
+            .if_not_exists()
+            .col(uuid(LessonTranslations::LessonId))
+            .col(string(LessonTranslations::Lang))
+            .col(text(LessonTranslations::Content))
+            .primary_key(
+                Index::create()
+                    .col(LessonTranslations::LessonId)
+                    .col(LessonTranslations::Lang),
+            )
+            .foreign_key(
+                ForeignKey::create()
+                    .name("fk_lesson_translations_lesson")
+                    .from(LessonTranslations::Table, LessonTranslations::LessonId)
+                    .to(Lessons::Table, Lessons::Id)
+                    .on_delete(ForeignKeyAction::Cascade)
+                    .on_update(ForeignKeyAction::Cascade),
+            )
+            .to_owned();
+        manager.create_table(table).await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(LessonTranslations::Table).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(CategoryTranslations::Table).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(TopicTranslations::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(Iden)]
+pub enum TopicTranslations {
+    Table,
+    TopicId,
+    Lang,
+    Name,
+}
+
+#[derive(Iden)]
+pub enum CategoryTranslations {
+    Table,
+    CategoryId,
+    Lang,
+    Name,
+}
+
+#[derive(Iden)]
+pub enum LessonTranslations {
+    Table,
+    LessonId,
+    Lang,
+    Content,
+}
+
+#[derive(Iden)]
+enum Topics {
+    Table,
+    Id,
+}
+
+#[derive(Iden)]
+enum Categories {
+    Table,
+    Id,
+}
+
+#[derive(Iden)]
+enum Lessons {
+    Table,
+    Id,
+}
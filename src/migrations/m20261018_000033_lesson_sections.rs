@@ -0,0 +1,197 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        // У темы может быть несколько уроков
+        db.execute_unprepared("ALTER TABLE lessons DROP CONSTRAINT IF EXISTS lessons_topic_id_key")
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Lessons::Table)
+                    .add_column(string(Lessons::Title).default(""))
+                    .add_column(small_integer(Lessons::Position).default(1))
+                    .add_column(small_integer(Lessons::ReadingMinutes).default(1))
+                    .to_owned(),
+            )
+            .await?;
+
+        // Существующие уроки получают название темы и оценку по 200 слов в минуту
+        db.execute_unprepared(
+            "UPDATE lessons l SET title = t.name, \
+             reading_minutes = GREATEST(1, CEIL(COALESCE(array_length( \
+                 regexp_split_to_array(btrim(l.content), '\\s+'), 1), 0) / 200.0)) \
+             FROM topics t WHERE t.id = l.topic_id",
+        )
+        .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_lessons_topic_position")
+                    .table(Lessons::Table)
+                    .col(Lessons::TopicId)
+                    .col(Lessons::Position)
+                    .to_owned(),
+            )
+            .await?;
+
+        let table = Table::create()
+            .table(LessonSections::Table)
+            .if_not_exists()
+            .col(
+                pk_uuid(LessonSections::Id)
+                    .extra("DEFAULT gen_random_uuid()")
+                    .primary_key(),
+            )
+            .col(uuid(LessonSections::LessonId))
+            .col(small_integer(LessonSections::Position))
+            .col(string(LessonSections::Title))
+            .col(text(LessonSections::Content))
+            .foreign_key(
+                ForeignKey::create()
+                    .name("fk_lesson_sections_lesson")
+                    .from(LessonSections::Table, LessonSections::LessonId)
+                    .to(Lessons::Table, Lessons::Id)
+                    .on_delete(ForeignKeyAction::Cascade)
+                    .on_update(ForeignKeyAction::Cascade),
+            )
+            .to_owned();
+        manager.create_table(table).await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_lesson_sections_lesson")
+                    .table(LessonSections::Table)
+                    .col(LessonSections::LessonId)
+                    .col(LessonSections::Position)
+                    .to_owned(),
+            )
+            .await?;
+
+        let table = Table::create()
+            .table(LessonSectionTranslations::Table)
+            .if_not_exists()
+            .col(uuid(LessonSectionTranslations::SectionId))
+            .col(string(LessonSectionTranslations::Lang))
+            .col(string(LessonSectionTranslations::Title))
+            .col(text(LessonSectionTranslations::Content))
+            .primary_key(
+                Index::create()
+                    .col(LessonSectionTranslations::SectionId)
+                    .col(LessonSectionTranslations::Lang),
+            )
+            .foreign_key(
+                ForeignKey::create()
+                    .name("fk_lesson_section_translations_section")
+                    .from(
+                        LessonSectionTranslations::Table,
+                        LessonSectionTranslations::SectionId,
+                    )
+                    .to(LessonSections::Table, LessonSections::Id)
+                    .on_delete(ForeignKeyAction::Cascade)
+                    .on_update(ForeignKeyAction::Cascade),
+            )
+            .to_owned();
+        manager.create_table(table).await?;
+
+        // Перевод урока без названия показывает исходное
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(LessonTranslations::Table)
+                    .add_column(string_null(LessonTranslations::Title))
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(LessonTranslations::Table)
+                    .drop_column(LessonTranslations::Title)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .drop_table(
+                Table::drop()
+                    .table(LessonSectionTranslations::Table)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .drop_table(Table::drop().table(LessonSections::Table).to_owned())
+            .await?;
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_lessons_topic_position")
+                    .table(Lessons::Table)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Lessons::Table)
+                    .drop_column(Lessons::ReadingMinutes)
+                    .drop_column(Lessons::Position)
+                    .drop_column(Lessons::Title)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "ALTER TABLE lessons ADD CONSTRAINT lessons_topic_id_key UNIQUE (topic_id)",
+            )
+            .await?;
+        Ok(())
+    }
+}
+
+#[derive(Iden)]
+enum Lessons {
+    Table,
+    Id,
+    TopicId,
+    Title,
+    Position,
+    ReadingMinutes,
+}
+
+#[derive(Iden)]
+pub enum LessonSections {
+    Table,
+    Id,
+    LessonId,
+    Position,
+    Title,
+    Content,
+}
+
+#[derive(Iden)]
+pub enum LessonSectionTranslations {
+    Table,
+    SectionId,
+    Lang,
+    Title,
+    Content,
+}
+
+#[derive(Iden)]
+enum LessonTranslations {
+    Table,
+    Title,
+}
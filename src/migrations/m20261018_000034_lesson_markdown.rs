@@ -0,0 +1,103 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // HTML строится из Markdown при сохранении; пустое значение у старых
+        // записей означает, что HTML строится при чтении
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Lessons::Table)
+                    .add_column(text_null(Lessons::ContentHtml))
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(LessonTranslations::Table)
+                    .add_column(text_null(LessonTranslations::ContentHtml))
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(LessonSections::Table)
+                    .add_column(text_null(LessonSections::ContentHtml))
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(LessonSectionTranslations::Table)
+                    .add_column(text_null(LessonSectionTranslations::ContentHtml))
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(LessonSectionTranslations::Table)
+                    .drop_column(LessonSectionTranslations::ContentHtml)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(LessonSections::Table)
+                    .drop_column(LessonSections::ContentHtml)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(LessonTranslations::Table)
+                    .drop_column(LessonTranslations::ContentHtml)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Lessons::Table)
+                    .drop_column(Lessons::ContentHtml)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(Iden)]
+enum Lessons {
+    Table,
+    ContentHtml,
+}
+
+#[derive(Iden)]
+enum LessonTranslations {
+    Table,
+    ContentHtml,
+}
+
+#[derive(Iden)]
+enum LessonSections {
+    Table,
+    ContentHtml,
+}
+
+#[derive(Iden)]
+enum LessonSectionTranslations {
+    Table,
+    ContentHtml,
+}
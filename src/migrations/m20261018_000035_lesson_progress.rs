@@ -0,0 +1,106 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let table = Table::create()
+            .table(LessonProgress::Table)
+            .if_not_exists()
+            .col(uuid(LessonProgress::UserId))
+            .col(uuid(LessonProgress::LessonId))
+            // Последний раздел, до которого дошёл пользователь
+            .col(uuid_null(LessonProgress::SectionId))
+            .col(small_integer(LessonProgress::ScrollPercent).default(0))
+            .col(integer(LessonProgress::TimeSpentSeconds).default(0))
+            .col(
+                timestamp_with_time_zone(LessonProgress::OpenedAt)
+                    .default(Expr::current_timestamp()),
+            )
+            .col(
+                timestamp_with_time_zone(LessonProgress::UpdatedAt)
+                    .default(Expr::current_timestamp()),
+            )
+            .col(timestamp_with_time_zone_null(LessonProgress::CompletedAt))
+            .primary_key(
+                Index::create()
+                    .col(LessonProgress::UserId)
+                    .col(LessonProgress::LessonId),
+            )
+            .foreign_key(
+                ForeignKey::create()
+                    .name("fk_lesson_progress_user")
+                    .from(LessonProgress::Table, LessonProgress::UserId)
+                    .to(Users::Table, Users::Id)
+                    .on_delete(ForeignKeyAction::Cascade)
+                    .on_update(ForeignKeyAction::Cascade),
+            )
+            .foreign_key(
+                ForeignKey::create()
+                    .name("fk_lesson_progress_lesson")
+                    .from(LessonProgress::Table, LessonProgress::LessonId)
+                    .to(Lessons::Table, Lessons::Id)
+                    .on_delete(ForeignKeyAction::Cascade)
+                    .on_update(ForeignKeyAction::Cascade),
+            )
+            .foreign_key(
+                ForeignKey::create()
+                    .name("fk_lesson_progress_section")
+                    .from(LessonProgress::Table, LessonProgress::SectionId)
+                    .to(LessonSections::Table, LessonSections::Id)
+                    .on_delete(ForeignKeyAction::SetNull)
+                    .on_update(ForeignKeyAction::Cascade),
+            )
+            .to_owned();
+        manager.create_table(table).await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_lesson_progress_lesson")
+                    .table(LessonProgress::Table)
+                    .col(LessonProgress::LessonId)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(LessonProgress::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(Iden)]
+pub enum LessonProgress {
+    Table,
+    UserId,
+    LessonId,
+    SectionId,
+    ScrollPercent,
+    TimeSpentSeconds,
+    OpenedAt,
+    UpdatedAt,
+    CompletedAt,
+}
+
+#[derive(Iden)]
+enum Users {
+    Table,
+    Id,
+}
+
+#[derive(Iden)]
+enum Lessons {
+    Table,
+    Id,
+}
+
+#[derive(Iden)]
+enum LessonSections {
+    Table,
+    Id,
+}
@@ -0,0 +1,110 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Порядок тем в учебной программе
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Topics::Table)
+                    .add_column(integer(Topics::Position).default(0))
+                    .to_owned(),
+            )
+            .await?;
+
+        // Условие открытия темы: урок требуемой темы прочитан и/или
+        // точность по ней не ниже порога; без урока и порога нужны все уроки
+        let table = Table::create()
+            .table(TopicPrerequisites::Table)
+            .if_not_exists()
+            .col(
+                pk_uuid(TopicPrerequisites::Id)
+                    .extra("DEFAULT gen_random_uuid()")
+                    .primary_key(),
+            )
+            .col(uuid(TopicPrerequisites::TopicId))
+            .col(uuid(TopicPrerequisites::RequiredTopicId))
+            .col(uuid_null(TopicPrerequisites::LessonId))
+            .col(small_integer_null(TopicPrerequisites::MinAccuracy))
+            .foreign_key(
+                ForeignKey::create()
+                    .name("fk_topic_prerequisites_topic")
+                    .from(TopicPrerequisites::Table, TopicPrerequisites::TopicId)
+                    .to(Topics::Table, Topics::Id)
+                    .on_delete(ForeignKeyAction::Cascade)
+                    .on_update(ForeignKeyAction::Cascade),
+            )
+            .foreign_key(
+                ForeignKey::create()
+                    .name("fk_topic_prerequisites_required_topic")
+                    .from(
+                        TopicPrerequisites::Table,
+                        TopicPrerequisites::RequiredTopicId,
+                    )
+                    .to(Topics::Table, Topics::Id)
+                    .on_delete(ForeignKeyAction::Cascade)
+                    .on_update(ForeignKeyAction::Cascade),
+            )
+            .foreign_key(
+                ForeignKey::create()
+                    .name("fk_topic_prerequisites_lesson")
+                    .from(TopicPrerequisites::Table, TopicPrerequisites::LessonId)
+                    .to(Lessons::Table, Lessons::Id)
+                    .on_delete(ForeignKeyAction::Cascade)
+                    .on_update(ForeignKeyAction::Cascade),
+            )
+            .to_owned();
+        manager.create_table(table).await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_topic_prerequisites_topic")
+                    .table(TopicPrerequisites::Table)
+                    .col(TopicPrerequisites::TopicId)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(TopicPrerequisites::Table).to_owned())
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Topics::Table)
+                    .drop_column(Topics::Position)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(Iden)]
+pub enum TopicPrerequisites {
+    Table,
+    Id,
+    TopicId,
+    RequiredTopicId,
+    LessonId,
+    MinAccuracy,
+}
+
+#[derive(Iden)]
+enum Topics {
+    Table,
+    Id,
+    Position,
+}
+
+#[derive(Iden)]
+enum Lessons {
+    Table,
+    Id,
+}
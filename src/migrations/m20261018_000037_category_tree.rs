@@ -0,0 +1,61 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Дерево категорий: при удалении родителя дети становятся корневыми
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Categories::Table)
+                    .add_column(uuid_null(Categories::ParentId))
+                    .add_column(integer(Categories::Position).default(0))
+                    .add_foreign_key(
+                        TableForeignKey::new()
+                            .name("fk_categories_parent")
+                            .from_tbl(Categories::Table)
+                            .from_col(Categories::ParentId)
+                            .to_tbl(Categories::Table)
+                            .to_col(Categories::Id)
+                            .on_delete(ForeignKeyAction::SetNull)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_categories_parent_position")
+                    .table(Categories::Table)
+                    .col(Categories::ParentId)
+                    .col(Categories::Position)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Categories::Table)
+                    .drop_column(Categories::ParentId)
+                    .drop_column(Categories::Position)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(Iden)]
+enum Categories {
+    Table,
+    Id,
+    ParentId,
+    Position,
+}
@@ -0,0 +1,181 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Жалобы учеников на вопросы и ответы
+        let table = Table::create()
+            .table(QuestionReports::Table)
+            .if_not_exists()
+            .col(
+                pk_uuid(QuestionReports::Id)
+                    .extra("DEFAULT gen_random_uuid()")
+                    .primary_key(),
+            )
+            .col(uuid(QuestionReports::QuestionId))
+            .col(uuid_null(QuestionReports::AnswerId))
+            .col(uuid_null(QuestionReports::UserId))
+            .col(string(QuestionReports::Reason))
+            .col(text_null(QuestionReports::Comment))
+            .col(string(QuestionReports::Status).default("open"))
+            .col(text_null(QuestionReports::ResolutionComment))
+            .col(uuid_null(QuestionReports::ResolvedBy))
+            .col(
+                timestamp_with_time_zone(QuestionReports::CreatedAt)
+                    .default(Expr::current_timestamp()),
+            )
+            .col(timestamp_with_time_zone_null(QuestionReports::ResolvedAt))
+            .foreign_key(
+                ForeignKey::create()
+                    .name("fk_question_reports_question")
+                    .from(QuestionReports::Table, QuestionReports::QuestionId)
+                    .to(Questions::Table, Questions::Id)
+                    .on_delete(ForeignKeyAction::Cascade)
+                    .on_update(ForeignKeyAction::Cascade),
+            )
+            .foreign_key(
+                ForeignKey::create()
+                    .name("fk_question_reports_answer")
+                    .from(QuestionReports::Table, QuestionReports::AnswerId)
+                    .to(Answers::Table, Answers::Id)
+                    .on_delete(ForeignKeyAction::SetNull)
+                    .on_update(ForeignKeyAction::Cascade),
+            )
+            .foreign_key(
+                ForeignKey::create()
+                    .name("fk_question_reports_user")
+                    .from(QuestionReports::Table, QuestionReports::UserId)
+                    .to(Users::Table, Users::Id)
+                    .on_delete(ForeignKeyAction::SetNull)
+                    .on_update(ForeignKeyAction::Cascade),
+            )
+            .foreign_key(
+                ForeignKey::create()
+                    .name("fk_question_reports_resolved_by")
+                    .from(QuestionReports::Table, QuestionReports::ResolvedBy)
+                    .to(Users::Table, Users::Id)
+                    .on_delete(ForeignKeyAction::SetNull)
+                    .on_update(ForeignKeyAction::Cascade),
+            )
+            .to_owned();
+        manager.create_table(table).await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_question_reports_question")
+                    .table(QuestionReports::Table)
+                    .col(QuestionReports::QuestionId)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_question_reports_status_created")
+                    .table(QuestionReports::Table)
+                    .col(QuestionReports::Status)
+                    .col(QuestionReports::CreatedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        // Уведомления пользователю внутри приложения
+        let table = Table::create()
+            .table(Notifications::Table)
+            .if_not_exists()
+            .col(
+                pk_uuid(Notifications::Id)
+                    .extra("DEFAULT gen_random_uuid()")
+                    .primary_key(),
+            )
+            .col(uuid(Notifications::UserId))
+            .col(string(Notifications::Kind))
+            .col(text(Notifications::Message))
+            .col(uuid_null(Notifications::ReferenceId))
+            .col(
+                timestamp_with_time_zone(Notifications::CreatedAt)
+                    .default(Expr::current_timestamp()),
+            )
+            .col(timestamp_with_time_zone_null(Notifications::ReadAt))
+            .foreign_key(
+                ForeignKey::create()
+                    .name("fk_notifications_user")
+                    .from(Notifications::Table, Notifications::UserId)
+                    .to(Users::Table, Users::Id)
+                    .on_delete(ForeignKeyAction::Cascade)
+                    .on_update(ForeignKeyAction::Cascade),
+            )
+            .to_owned();
+        manager.create_table(table).await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_notifications_user_created")
+                    .table(Notifications::Table)
+                    .col(Notifications::UserId)
+                    .col(Notifications::CreatedAt)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Notifications::Table).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(QuestionReports::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(Iden)]
+pub enum QuestionReports {
+    Table,
+    Id,
+    QuestionId,
+    AnswerId,
+    UserId,
+    Reason,
+    Comment,
+    Status,
+    ResolutionComment,
+    ResolvedBy,
+    CreatedAt,
+    ResolvedAt,
+}
+
+#[derive(Iden)]
+pub enum Notifications {
+    Table,
+    Id,
+    UserId,
+    Kind,
+    Message,
+    ReferenceId,
+    CreatedAt,
+    ReadAt,
+}
+
+#[derive(Iden)]
+enum Questions {
+    Table,
+    Id,
+}
+
+#[derive(Iden)]
+enum Answers {
+    Table,
+    Id,
+}
+
+#[derive(Iden)]
+enum Users {
+    Table,
+    Id,
+}
@@ -0,0 +1,172 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Сессия не должна зависеть от живого контента
+        manager
+            .drop_foreign_key(
+                ForeignKey::drop()
+                    .name("fk_classroom_session_questions_question")
+                    .table(ClassroomSessionQuestions::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        // Снимок вопроса на момент создания сессии
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(ClassroomSessionQuestions::Table)
+                    .add_column(small_integer(ClassroomSessionQuestions::Points).default(1))
+                    .add_column(string(ClassroomSessionQuestions::QuestionName).default(""))
+                    .add_column(text_null(ClassroomSessionQuestions::QuestionContent))
+                    .add_column(text(ClassroomSessionQuestions::Explanation).default(""))
+                    .add_column(string(ClassroomSessionQuestions::QuestionType).default("choice"))
+                    .add_column(json_binary_null(ClassroomSessionQuestions::ImageIds))
+                    .to_owned(),
+            )
+            .await?;
+
+        let table = Table::create()
+            .table(ClassroomSessionOptions::Table)
+            .if_not_exists()
+            .col(uuid(ClassroomSessionOptions::SessionId))
+            .col(uuid(ClassroomSessionOptions::QuestionId))
+            .col(uuid(ClassroomSessionOptions::AnswerId))
+            .col(string(ClassroomSessionOptions::Value))
+            .col(boolean(ClassroomSessionOptions::IsCorrect))
+            .col(small_integer(ClassroomSessionOptions::OptionOrder))
+            .col(json_binary_null(ClassroomSessionOptions::Payload))
+            .col(uuid_null(ClassroomSessionOptions::ImageId))
+            .primary_key(
+                Index::create()
+                    .col(ClassroomSessionOptions::SessionId)
+                    .col(ClassroomSessionOptions::QuestionId)
+                    .col(ClassroomSessionOptions::AnswerId),
+            )
+            .foreign_key(
+                ForeignKey::create()
+                    .name("fk_classroom_session_options_session")
+                    .from(
+                        ClassroomSessionOptions::Table,
+                        ClassroomSessionOptions::SessionId,
+                    )
+                    .to(ClassroomSessions::Table, ClassroomSessions::Id)
+                    .on_delete(ForeignKeyAction::Cascade)
+                    .on_update(ForeignKeyAction::Cascade),
+            )
+            .to_owned();
+        manager.create_table(table).await?;
+
+        // Заполняем снимки уже созданных сессий
+        let db = manager.get_connection();
+        db.execute_unprepared(
+            "UPDATE classroom_session_questions csq \
+             SET points = q.points, question_name = q.name, question_content = q.content, \
+             explanation = q.explanation, question_type = q.question_type, \
+             image_ids = (SELECT jsonb_agg(qi.image_id ORDER BY qi.position) \
+             FROM question_images qi WHERE qi.question_id = q.id) \
+             FROM questions q WHERE q.id = csq.question_id",
+        )
+        .await?;
+        db.execute_unprepared(
+            "INSERT INTO classroom_session_options \
+             (session_id, question_id, answer_id, value, is_correct, option_order, payload, image_id) \
+             SELECT csq.session_id, csq.question_id, a.id, a.value, a.is_correct, \
+             ROW_NUMBER() OVER (PARTITION BY csq.session_id, csq.question_id ORDER BY a.id), \
+             a.payload, a.image_id \
+             FROM classroom_session_questions csq JOIN answers a ON a.question_id = csq.question_id",
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(
+                Table::drop()
+                    .table(ClassroomSessionOptions::Table)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(ClassroomSessionQuestions::Table)
+                    .drop_column(ClassroomSessionQuestions::Points)
+                    .drop_column(ClassroomSessionQuestions::QuestionName)
+                    .drop_column(ClassroomSessionQuestions::QuestionContent)
+                    .drop_column(ClassroomSessionQuestions::Explanation)
+                    .drop_column(ClassroomSessionQuestions::QuestionType)
+                    .drop_column(ClassroomSessionQuestions::ImageIds)
+                    .to_owned(),
+            )
+            .await?;
+
+        // Удалённые вопросы не восстановить, убираем их перед возвратом ключа
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "DELETE FROM classroom_session_questions csq WHERE NOT EXISTS \
+                 (SELECT 1 FROM questions q WHERE q.id = csq.question_id)",
+            )
+            .await?;
+
+        manager
+            .create_foreign_key(
+                ForeignKey::create()
+                    .name("fk_classroom_session_questions_question")
+                    .from(
+                        ClassroomSessionQuestions::Table,
+                        ClassroomSessionQuestions::QuestionId,
+                    )
+                    .to(Questions::Table, Questions::Id)
+                    .on_delete(ForeignKeyAction::Cascade)
+                    .on_update(ForeignKeyAction::Cascade)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(Iden)]
+enum ClassroomSessionQuestions {
+    Table,
+    QuestionId,
+    Points,
+    QuestionName,
+    QuestionContent,
+    Explanation,
+    QuestionType,
+    ImageIds,
+}
+
+#[derive(Iden)]
+enum ClassroomSessionOptions {
+    Table,
+    SessionId,
+    QuestionId,
+    AnswerId,
+    Value,
+    IsCorrect,
+    OptionOrder,
+    Payload,
+    ImageId,
+}
+
+#[derive(Iden)]
+enum ClassroomSessions {
+    Table,
+    Id,
+}
+
+#[derive(Iden)]
+enum Questions {
+    Table,
+    Id,
+}
@@ -12,6 +12,32 @@ pub mod m20251211_000011_question_categories;
 pub mod m20251211_000012_tests;
 pub mod m20251211_000013_test_questions;
 pub mod m20251211_000014_test_question_answers;
+pub mod m20261018_000015_scoring;
+pub mod m20261018_000016_tests_retake;
+pub mod m20261018_000017_test_templates;
+pub mod m20261018_000018_daily_challenges;
+pub mod m20261018_000019_duels;
+pub mod m20261018_000020_classroom_sessions;
+pub mod m20261018_000021_test_snapshots;
+pub mod m20261018_000022_question_revisions;
+pub mod m20261018_000023_content_status;
+pub mod m20261018_000024_question_import;
+pub mod m20261018_000025_search;
+pub mod m20261018_000026_question_types;
+pub mod m20261018_000027_content_images;
+pub mod m20261018_000028_road_signs;
+pub mod m20261018_000029_license_classes;
+pub mod m20261018_000030_validity_regions;
+pub mod m20261018_000031_translation_groups;
+pub mod m20261018_000032_content_translations;
+pub mod m20261018_000033_lesson_sections;
+pub mod m20261018_000034_lesson_markdown;
+pub mod m20261018_000035_lesson_progress;
+pub mod m20261018_000036_curriculum;
+pub mod m20261018_000037_category_tree;
+pub mod m20261018_000038_question_reports;
+pub mod m20261018_000039_user_roles;
+pub mod m20261018_000040_classroom_session_snapshots;
 use sea_orm_migration::prelude::*;
 mod utils;
 
@@ -35,6 +61,32 @@ impl MigratorTrait for Migrator {
             Box::new(m20251211_000012_tests::Migration),
             Box::new(m20251211_000013_test_questions::Migration),
             Box::new(m20251211_000014_test_question_answers::Migration),
+            Box::new(m20261018_000015_scoring::Migration),
+            Box::new(m20261018_000016_tests_retake::Migration),
+            Box::new(m20261018_000017_test_templates::Migration),
+            Box::new(m20261018_000018_daily_challenges::Migration),
+            Box::new(m20261018_000019_duels::Migration),
+            Box::new(m20261018_000020_classroom_sessions::Migration),
+            Box::new(m20261018_000021_test_snapshots::Migration),
+            Box::new(m20261018_000022_question_revisions::Migration),
+            Box::new(m20261018_000023_content_status::Migration),
+            Box::new(m20261018_000024_question_import::Migration),
+            Box::new(m20261018_000025_search::Migration),
+            Box::new(m20261018_000026_question_types::Migration),
+            Box::new(m20261018_000027_content_images::Migration),
+            Box::new(m20261018_000028_road_signs::Migration),
+            Box::new(m20261018_000029_license_classes::Migration),
+            Box::new(m20261018_000030_validity_regions::Migration),
+            Box::new(m20261018_000031_translation_groups::Migration),
+            Box::new(m20261018_000032_content_translations::Migration),
+            Box::new(m20261018_000033_lesson_sections::Migration),
+            Box::new(m20261018_000034_lesson_markdown::Migration),
+            Box::new(m20261018_000035_lesson_progress::Migration),
+            Box::new(m20261018_000036_curriculum::Migration),
+            Box::new(m20261018_000037_category_tree::Migration),
+            Box::new(m20261018_000038_question_reports::Migration),
+            Box::new(m20261018_000039_user_roles::Migration),
+            Box::new(m20261018_000040_classroom_session_snapshots::Migration),
         ]
     }
 }
@@ -3,7 +3,7 @@ use utoipa::ToSchema;
 use uuid::Uuid;
 use validator::Validate;
 
-use crate::entities::answers;
+use crate::{entities::answers, models::question_types::AnswerPayload};
 
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct AnswerResponse {
@@ -11,6 +11,11 @@ pub struct AnswerResponse {
     pub question_id: Uuid,
     pub value: String,
     pub is_correct: bool,
+    /// Position, expected value or image region, depending on the question type
+    pub payload: Option<AnswerPayload>,
+    pub image_id: Option<Uuid>,
+    /// Shared by the equivalent answers of the question translations
+    pub translation_group_id: Uuid,
 }
 
 impl From<answers::Model> for AnswerResponse {
@@ -20,6 +25,9 @@ impl From<answers::Model> for AnswerResponse {
             question_id: model.question_id,
             value: model.value,
             is_correct: model.is_correct,
+            payload: AnswerPayload::from_json(model.payload.as_ref()),
+            image_id: model.image_id,
+            translation_group_id: model.translation_group_id,
         }
     }
 }
@@ -30,6 +38,12 @@ pub struct CreateAnswerParams {
     #[validate(length(min = 1, max = 500))]
     pub value: String,
     pub is_correct: bool,
+    /// Required for "ordering", "numeric" and "hotspot" questions
+    pub payload: Option<AnswerPayload>,
+    /// Image shown next to the option
+    pub image_id: Option<Uuid>,
+    /// Translation group of the equivalent answer in another language
+    pub translation_group_id: Option<Uuid>,
 }
 
 #[derive(Debug, Serialize, Deserialize, ToSchema, Validate)]
@@ -38,4 +52,7 @@ pub struct UpdateAnswerParams {
     #[validate(length(min = 1, max = 500))]
     pub value: Option<String>,
     pub is_correct: Option<bool>,
+    pub payload: Option<AnswerPayload>,
+    pub image_id: Option<Uuid>,
+    pub translation_group_id: Option<Uuid>,
 }
@@ -9,6 +9,12 @@ use crate::entities::categories;
 pub struct CategoryResponse {
     pub id: Uuid,
     pub name: String,
+    /// Language of the translated name, none for the original one
+    pub lang: Option<String>,
+    /// Parent category, none for a root
+    pub parent_id: Option<Uuid>,
+    /// Order among the siblings, lower first
+    pub position: i32,
 }
 
 impl From<categories::Model> for CategoryResponse {
@@ -16,18 +22,57 @@ impl From<categories::Model> for CategoryResponse {
         Self {
             id: model.id,
             name: model.name,
+            lang: None,
+            parent_id: model.parent_id,
+            position: model.position,
         }
     }
 }
 
+/// Category with its ordered subcategories
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct CategoryTreeResponse {
+    #[serde(flatten)]
+    pub category: CategoryResponse,
+    #[schema(no_recursion)]
+    pub children: Vec<CategoryTreeResponse>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema, Validate)]
+pub struct CategoryTranslationParams {
+    #[validate(length(min = 2, max = 10))]
+    pub lang: String,
+    #[validate(length(min = 1, max = 255))]
+    pub name: String,
+}
+
 #[derive(Debug, Serialize, Deserialize, ToSchema, Validate)]
 pub struct CreateCategoryParams {
     #[validate(length(min = 1, max = 255))]
     pub name: String,
+    /// Parent category, none for a root
+    pub parent_id: Option<Uuid>,
+    #[serde(default)]
+    pub position: i32,
+    #[serde(default)]
+    #[validate(nested)]
+    pub translations: Vec<CategoryTranslationParams>,
 }
 
 #[derive(Debug, Serialize, Deserialize, ToSchema, Validate)]
 pub struct UpdateCategoryParams {
     #[validate(length(min = 1, max = 255))]
     pub name: Option<String>,
+    pub position: Option<i32>,
+    /// Added or replaced per language, other languages are kept
+    #[validate(nested)]
+    pub translations: Option<Vec<CategoryTranslationParams>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct MoveCategoryParams {
+    /// New parent, none makes the category a root
+    pub parent_id: Option<Uuid>,
+    /// Order among the new siblings, kept when omitted
+    pub position: Option<i32>,
 }
@@ -0,0 +1,189 @@
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{
+    entities::classroom_sessions,
+    models::{question_types::SubmittedAnswer, tests::CurrentQuestionResponse},
+};
+
+/// Parameters for opening a classroom session
+#[derive(Debug, Serialize, Deserialize, ToSchema, Validate)]
+pub struct CreateClassroomSessionParams {
+    #[validate(length(min = 1, max = 255))]
+    pub title: String,
+    /// Language code
+    #[validate(length(min = 2, max = 10))]
+    pub lang: String,
+    /// Take the questions from a test template
+    pub template_id: Option<Uuid>,
+    /// Explicit question list, used when no template is given
+    #[validate(length(min = 1, max = 100))]
+    pub question_ids: Option<Vec<Uuid>>,
+}
+
+/// Parameters for joining a session by PIN
+#[derive(Debug, Serialize, Deserialize, ToSchema, Validate)]
+pub struct JoinClassroomSessionParams {
+    #[validate(length(min = 1, max = 16))]
+    pub pin: String,
+}
+
+/// Query parameters for the classroom WebSocket
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct ClassroomSocketQuery {
+    /// Access token, browsers cannot set headers on WebSocket requests
+    pub token: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ClassroomSessionResponse {
+    pub id: Uuid,
+    pub host_id: Uuid,
+    pub title: String,
+    pub lang: String,
+    pub pin: String,
+    /// "lobby", "question", "results", "finished"
+    pub status: String,
+    /// Order of the current question, 0 before the first one
+    pub current_question_order: i16,
+    pub questions_count: i16,
+    pub created_at: DateTime<Utc>,
+    pub finished_at: Option<DateTime<Utc>>,
+}
+
+impl ClassroomSessionResponse {
+    pub fn from_model(model: classroom_sessions::Model, questions_count: i16) -> Self {
+        Self {
+            id: model.id,
+            host_id: model.host_id,
+            title: model.title,
+            lang: model.lang,
+            pin: model.pin,
+            status: model.status,
+            current_question_order: model.current_question_order,
+            questions_count,
+            created_at: model.created_at.into(),
+            finished_at: model.finished_at.map(|dt| dt.into()),
+        }
+    }
+}
+
+/// Session info returned to a student after joining
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct JoinedClassroomSessionResponse {
+    pub session_id: Uuid,
+    pub title: String,
+    pub status: String,
+    pub test_id: Option<Uuid>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ClassroomLeaderboardEntry {
+    pub rank: i64,
+    pub user_id: Uuid,
+    pub username: Option<String>,
+    pub earned_points: Decimal,
+    pub correct_count: i16,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct AnswerDistribution {
+    pub answer_id: Uuid,
+    pub count: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ClassroomParticipantResult {
+    pub user_id: Uuid,
+    pub username: Option<String>,
+    pub test_id: Option<Uuid>,
+    pub answered_count: i16,
+    pub correct_count: i16,
+    pub earned_points: Decimal,
+    pub score_percent: Option<i16>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ClassroomQuestionReport {
+    pub question_id: Uuid,
+    pub question_order: i16,
+    pub answered_count: i64,
+    pub correct_count: i64,
+    pub distribution: Vec<AnswerDistribution>,
+}
+
+/// Instructor report for a session
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ClassroomReportResponse {
+    pub session: ClassroomSessionResponse,
+    pub participants: Vec<ClassroomParticipantResult>,
+    pub questions: Vec<ClassroomQuestionReport>,
+}
+
+/// Messages sent to the host and participants over the classroom WebSocket
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ClassroomEvent {
+    /// Session has not started or shows results, wait for the next question
+    Waiting {
+        status: String,
+    },
+    /// Connected participants, sent to the host
+    Participants {
+        participants: Vec<ClassroomLeaderboardEntry>,
+    },
+    Question {
+        total: i16,
+        #[serde(flatten)]
+        question: CurrentQuestionResponse,
+    },
+    AnswerAccepted {
+        question_id: Uuid,
+    },
+    /// Answer progress of the current question, sent to the host
+    AnswerCount {
+        question_id: Uuid,
+        answered: i64,
+        participants: i64,
+    },
+    Results {
+        question_id: Uuid,
+        correct_answer_ids: Vec<Uuid>,
+        distribution: Vec<AnswerDistribution>,
+        leaderboard: Vec<ClassroomLeaderboardEntry>,
+    },
+    /// Personal result of the closed question, sent to each participant
+    AnswerResult {
+        question_id: Uuid,
+        is_correct: Option<bool>,
+        earned_points: Option<Decimal>,
+    },
+    Finished {
+        leaderboard: Vec<ClassroomLeaderboardEntry>,
+    },
+    Error {
+        message: String,
+    },
+}
+
+/// Messages received over the classroom WebSocket
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ClassroomCommand {
+    /// Host: open the next question
+    Next,
+    /// Host: stop accepting answers and show the results
+    CloseQuestion,
+    /// Host: end the session
+    Finish,
+    /// Participant: answer the open question
+    Answer {
+        question_id: Uuid,
+        #[serde(flatten)]
+        answer: SubmittedAnswer,
+    },
+}
@@ -0,0 +1,60 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::entities::content_reviews;
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct ReviewQueueQuery {
+    /// "draft", "in_review", "published", "archived" (defaults to "in_review")
+    pub status: Option<String>,
+}
+
+#[derive(Debug, Deserialize, IntoParams, Validate)]
+pub struct ExpiringQuery {
+    /// Look-ahead window in days (defaults to 30)
+    #[validate(range(min = 1, max = 365))]
+    pub days: Option<u32>,
+}
+
+/// Reviewer decision on submitted content
+#[derive(Debug, Serialize, Deserialize, ToSchema, Validate)]
+pub struct ReviewDecisionParams {
+    /// "approve", "reject" or "archive"
+    pub decision: String,
+    /// Required when rejecting
+    #[validate(length(min = 1, max = 2000))]
+    pub comment: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ContentReviewResponse {
+    pub id: Uuid,
+    /// "question" or "lesson"
+    pub content_type: String,
+    pub content_id: Uuid,
+    pub user_id: Option<Uuid>,
+    /// "submit", "approve", "reject", "archive", "revert", "edit", "import"
+    pub action: String,
+    /// Status after the action
+    pub status: String,
+    pub comment: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<content_reviews::Model> for ContentReviewResponse {
+    fn from(model: content_reviews::Model) -> Self {
+        Self {
+            id: model.id,
+            content_type: model.content_type,
+            content_id: model.content_id,
+            user_id: model.user_id,
+            action: model.action,
+            status: model.status,
+            comment: model.comment,
+            created_at: model.created_at.into(),
+        }
+    }
+}
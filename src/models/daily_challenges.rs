@@ -0,0 +1,71 @@
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+use crate::entities::{daily_challenges, tests};
+
+/// Query parameters for daily challenges
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct DailyChallengesQuery {
+    /// Language code (e.g., "en", "ru")
+    pub lang: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct DailyChallengeResponse {
+    pub id: Uuid,
+    pub challenge_date: NaiveDate,
+    pub lang: String,
+    pub questions_count: i16,
+    /// Question ids in test order
+    pub question_ids: Vec<Uuid>,
+    /// The user's attempt, if taken
+    pub test_id: Option<Uuid>,
+    pub status: Option<String>,
+    pub score_percent: Option<i16>,
+}
+
+impl DailyChallengeResponse {
+    pub fn from_model(
+        model: daily_challenges::Model,
+        question_ids: Vec<Uuid>,
+        attempt: Option<tests::Model>,
+    ) -> Self {
+        Self {
+            id: model.id,
+            challenge_date: model.challenge_date,
+            lang: model.lang,
+            questions_count: model.questions_count,
+            question_ids,
+            test_id: attempt.as_ref().map(|t| t.id),
+            status: attempt.as_ref().map(|t| t.status.clone()),
+            score_percent: attempt.and_then(|t| t.score_percent),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct LeaderboardEntry {
+    /// Position, starting from 1
+    pub rank: i64,
+    pub user_id: Uuid,
+    pub username: Option<String>,
+    pub score_percent: i16,
+    pub earned_points: Decimal,
+    /// Time from start to completion
+    pub duration_seconds: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct LeaderboardResponse {
+    pub challenge_id: Uuid,
+    pub challenge_date: NaiveDate,
+    /// Top entries, ranked by score, then by time
+    pub entries: Vec<LeaderboardEntry>,
+    /// The user's own entry, even outside the top
+    pub me: Option<LeaderboardEntry>,
+    /// Consecutive days with a completed challenge
+    pub streak: i32,
+}
@@ -0,0 +1,142 @@
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{
+    entities::{duel_players, duels, tests},
+    models::{question_types::SubmittedAnswer, tests::CurrentQuestionResponse},
+};
+
+/// Parameters for starting a duel
+#[derive(Debug, Serialize, Deserialize, ToSchema, Validate)]
+pub struct CreateDuelParams {
+    /// Language code
+    #[validate(length(min = 2, max = 10))]
+    pub lang: String,
+    /// Number of questions (1-25)
+    #[validate(range(min = 1, max = 25))]
+    pub questions_count: i16,
+    /// "invite" to get a code for a friend, "matchmaking" to join the queue
+    pub mode: String,
+}
+
+/// Parameters for joining a duel by invite code
+#[derive(Debug, Serialize, Deserialize, ToSchema, Validate)]
+pub struct JoinDuelParams {
+    #[validate(length(min = 1, max = 16))]
+    pub invite_code: String,
+}
+
+/// Query parameters for the duel WebSocket
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct DuelSocketQuery {
+    /// Access token, browsers cannot set headers on WebSocket requests
+    pub token: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct DuelPlayerResponse {
+    pub user_id: Uuid,
+    pub test_id: Option<Uuid>,
+    pub player_order: i16,
+    pub is_winner: bool,
+    pub earned_points: Option<Decimal>,
+    pub score_percent: Option<i16>,
+}
+
+impl DuelPlayerResponse {
+    pub fn from_model(model: duel_players::Model, test: Option<&tests::Model>) -> Self {
+        Self {
+            user_id: model.user_id,
+            test_id: model.test_id,
+            player_order: model.player_order,
+            is_winner: model.is_winner,
+            earned_points: test.map(|t| t.earned_points),
+            score_percent: test.and_then(|t| t.score_percent),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct DuelResponse {
+    pub id: Uuid,
+    pub lang: String,
+    pub questions_count: i16,
+    pub invite_code: Option<String>,
+    /// "waiting", "ready", "active", "finished", "cancelled"
+    pub status: String,
+    pub players: Vec<DuelPlayerResponse>,
+    pub finished_at: Option<DateTime<Utc>>,
+}
+
+impl DuelResponse {
+    pub fn from_model(model: duels::Model, players: Vec<DuelPlayerResponse>) -> Self {
+        Self {
+            id: model.id,
+            lang: model.lang,
+            questions_count: model.questions_count,
+            invite_code: model.invite_code,
+            status: model.status,
+            players,
+            finished_at: model.finished_at.map(|dt| dt.into()),
+        }
+    }
+}
+
+/// Live score of a duel player
+#[derive(Debug, Clone, Serialize)]
+pub struct PlayerScore {
+    pub user_id: Uuid,
+    pub earned_points: Decimal,
+    pub correct_count: i16,
+    pub answered_count: i16,
+}
+
+/// Messages sent to players over the duel WebSocket
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum DuelEvent {
+    /// Waiting for the opponent to connect
+    Waiting,
+    Question {
+        total: i16,
+        seconds_left: u64,
+        #[serde(flatten)]
+        question: CurrentQuestionResponse,
+    },
+    AnswerResult {
+        question_id: Uuid,
+        is_correct: bool,
+        earned_points: Decimal,
+        correct_answer_ids: Vec<Uuid>,
+    },
+    OpponentAnswered {
+        question_id: Uuid,
+    },
+    Scores {
+        scores: Vec<PlayerScore>,
+    },
+    OpponentConnected,
+    OpponentDisconnected,
+    Finished {
+        winner_id: Option<Uuid>,
+        scores: Vec<PlayerScore>,
+    },
+    Error {
+        message: String,
+    },
+}
+
+/// Messages received from players over the duel WebSocket
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum DuelCommand {
+    Answer {
+        question_id: Uuid,
+        #[serde(flatten)]
+        answer: SubmittedAnswer,
+    },
+}
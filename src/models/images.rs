@@ -25,6 +25,13 @@ impl From<images::Model> for ImageResponse {
     }
 }
 
+/// Image attached to a question or an answer option
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ImageLink {
+    pub id: Uuid,
+    pub url: String,
+}
+
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct UploadResponse {
     pub id: Uuid,
@@ -0,0 +1,62 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::entities::lesson_progress;
+
+/// Reading progress reported by the app while a lesson is open
+#[derive(Debug, Serialize, Deserialize, ToSchema, Validate)]
+pub struct LessonProgressParams {
+    /// Section on screen, the furthest one reached is kept
+    pub section_id: Option<Uuid>,
+    /// Scroll position in percent, the furthest one is kept
+    #[validate(range(min = 0, max = 100))]
+    pub scroll_percent: Option<i16>,
+    /// Reading time since the previous report
+    #[validate(range(min = 0, max = 3600))]
+    pub time_spent_seconds: Option<i32>,
+    /// Marks the lesson as read
+    #[serde(default)]
+    pub completed: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct LessonProgressResponse {
+    pub lesson_id: Uuid,
+    pub section_id: Option<Uuid>,
+    pub scroll_percent: i16,
+    pub time_spent_seconds: i32,
+    pub opened_at: DateTime<Utc>,
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+impl From<lesson_progress::Model> for LessonProgressResponse {
+    fn from(model: lesson_progress::Model) -> Self {
+        Self {
+            lesson_id: model.lesson_id,
+            section_id: model.section_id,
+            scroll_percent: model.scroll_percent,
+            time_spent_seconds: model.time_spent_seconds,
+            opened_at: model.opened_at.into(),
+            completed_at: model.completed_at.map(Into::into),
+        }
+    }
+}
+
+/// Learning progress of the current user in a topic
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct TopicProgressResponse {
+    pub topic_id: Uuid,
+    /// Published lessons of the topic the user can read
+    pub lessons_total: u64,
+    pub lessons_completed: u64,
+    /// Answered test questions of the topic
+    pub questions_answered: i64,
+    pub questions_correct: i64,
+    /// Share of correct answers, from 0 to 1
+    pub accuracy: f64,
+    /// Lessons read and practice accuracy combined, in percent
+    pub completion: i16,
+}
@@ -1,15 +1,63 @@
+use chrono::NaiveDate;
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 use uuid::Uuid;
 use validator::Validate;
 
-use crate::entities::lessons;
+use crate::entities::{lesson_sections, lessons};
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct LessonSectionResponse {
+    pub id: Uuid,
+    pub position: i16,
+    pub title: String,
+    /// Markdown source
+    pub content: String,
+    /// Sanitized HTML rendered from the source
+    pub content_html: String,
+    /// Language of the translated section, none for the original one
+    pub lang: Option<String>,
+}
+
+impl From<lesson_sections::Model> for LessonSectionResponse {
+    fn from(model: lesson_sections::Model) -> Self {
+        Self {
+            id: model.id,
+            position: model.position,
+            title: model.title,
+            content: model.content,
+            content_html: model.content_html.unwrap_or_default(),
+            lang: None,
+        }
+    }
+}
 
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct LessonResponse {
     pub id: Uuid,
     pub topic_id: Uuid,
+    pub title: String,
+    /// Order of the lesson within its topic
+    pub position: i16,
+    /// Estimated reading time of the lesson with its sections
+    pub reading_minutes: i16,
+    /// Markdown source of the introduction shown before the sections
     pub content: String,
+    /// Sanitized HTML rendered from the source
+    pub content_html: String,
+    /// Language of the translated content, none for the original one
+    pub lang: Option<String>,
+    /// Sections in reading order
+    pub sections: Vec<LessonSectionResponse>,
+    /// "draft", "in_review", "published", "archived"
+    pub status: String,
+    pub review_comment: Option<String>,
+    /// First day the lesson is in force
+    pub valid_from: Option<NaiveDate>,
+    /// Last day the lesson is in force
+    pub valid_to: Option<NaiveDate>,
+    /// Region the lesson applies to, none for general rules
+    pub region: Option<String>,
 }
 
 impl From<lessons::Model> for LessonResponse {
@@ -17,21 +65,131 @@ impl From<lessons::Model> for LessonResponse {
         Self {
             id: model.id,
             topic_id: model.topic_id,
+            title: model.title,
+            position: model.position,
+            reading_minutes: model.reading_minutes,
             content: model.content,
+            content_html: model.content_html.unwrap_or_default(),
+            lang: None,
+            sections: Vec::new(),
+            status: model.status,
+            review_comment: model.review_comment,
+            valid_from: model.valid_from,
+            valid_to: model.valid_to,
+            region: model.region,
         }
     }
 }
 
+/// Lesson in a topic listing or in the navigation
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct LessonSummaryResponse {
+    pub id: Uuid,
+    pub title: String,
+    /// Language of the translated title, none for the original one
+    pub lang: Option<String>,
+    pub position: i16,
+    pub reading_minutes: i16,
+}
+
+/// Lessons of a topic in reading order
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct TopicLessonsResponse {
+    pub topic_id: Uuid,
+    /// Reading time of all listed lessons
+    pub reading_minutes: i32,
+    pub lessons: Vec<LessonSummaryResponse>,
+}
+
+/// Lesson with links to its neighbours in the topic
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct LessonDetailResponse {
+    #[serde(flatten)]
+    pub lesson: LessonResponse,
+    pub previous: Option<LessonSummaryResponse>,
+    pub next: Option<LessonSummaryResponse>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema, Validate)]
+pub struct LessonTranslationParams {
+    #[validate(length(min = 2, max = 10))]
+    pub lang: String,
+    /// The original title is shown without it
+    #[validate(length(min = 1, max = 255))]
+    pub title: Option<String>,
+    #[validate(length(min = 1))]
+    pub content: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema, Validate)]
+pub struct LessonSectionTranslationParams {
+    #[validate(length(min = 2, max = 10))]
+    pub lang: String,
+    #[validate(length(min = 1, max = 255))]
+    pub title: String,
+    #[validate(length(min = 1))]
+    pub content: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema, Validate)]
+pub struct LessonSectionParams {
+    #[validate(length(min = 1, max = 255))]
+    pub title: String,
+    #[validate(length(min = 1))]
+    pub content: String,
+    #[serde(default)]
+    #[validate(nested)]
+    pub translations: Vec<LessonSectionTranslationParams>,
+}
+
 #[derive(Debug, Serialize, Deserialize, ToSchema, Validate)]
 pub struct CreateLessonParams {
     pub topic_id: Uuid,
+    #[validate(length(min = 1, max = 255))]
+    pub title: String,
+    /// Placed after the other lessons of the topic by default
+    pub position: Option<i16>,
+    /// Estimated from the text by default
+    #[validate(range(min = 1, max = 600))]
+    pub reading_minutes: Option<i16>,
+    /// Markdown. Images may embed uploads from `/api/images/file/...`,
+    /// `question:<id>` and `sign:<code>` links point to questions and road signs.
     #[validate(length(min = 1))]
     pub content: String,
+    /// Sections in reading order
+    #[serde(default)]
+    #[validate(nested)]
+    pub sections: Vec<LessonSectionParams>,
+    pub valid_from: Option<NaiveDate>,
+    pub valid_to: Option<NaiveDate>,
+    /// Region code, none for general rules
+    #[validate(length(min = 1, max = 20))]
+    pub region: Option<String>,
+    #[serde(default)]
+    #[validate(nested)]
+    pub translations: Vec<LessonTranslationParams>,
 }
 
 #[derive(Debug, Serialize, Deserialize, ToSchema, Validate)]
 pub struct UpdateLessonParams {
     pub topic_id: Option<Uuid>,
+    #[validate(length(min = 1, max = 255))]
+    pub title: Option<String>,
+    pub position: Option<i16>,
+    /// Estimated again from the text when the content or the sections change
+    #[validate(range(min = 1, max = 600))]
+    pub reading_minutes: Option<i16>,
     #[validate(length(min = 1))]
     pub content: Option<String>,
+    /// Replaces all sections of the lesson
+    #[validate(nested)]
+    pub sections: Option<Vec<LessonSectionParams>>,
+    pub valid_from: Option<NaiveDate>,
+    pub valid_to: Option<NaiveDate>,
+    /// An empty string makes the lesson a general rule
+    #[validate(length(max = 20))]
+    pub region: Option<String>,
+    /// Added or replaced per language, other languages are kept
+    #[validate(nested)]
+    pub translations: Option<Vec<LessonTranslationParams>>,
 }
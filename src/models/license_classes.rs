@@ -0,0 +1,20 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::entities::license_classes;
+
+/// Driving licence class, e.g. "B" for cars
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct LicenseClassResponse {
+    pub code: String,
+    pub name: String,
+}
+
+impl From<license_classes::Model> for LicenseClassResponse {
+    fn from(model: license_classes::Model) -> Self {
+        Self {
+            code: model.code,
+            name: model.name,
+        }
+    }
+}
@@ -1,9 +1,24 @@
 pub mod answers;
 pub mod categories;
+pub mod classroom_sessions;
+pub mod content_reviews;
+pub mod daily_challenges;
+pub mod duels;
 pub mod images;
+pub mod lesson_progress;
 pub mod lessons;
+pub mod license_classes;
+pub mod notifications;
 pub mod question_categories;
+pub mod question_import;
+pub mod question_reports;
+pub mod question_revisions;
+pub mod question_translations;
+pub mod question_types;
 pub mod questions;
+pub mod road_signs;
+pub mod search;
+pub mod test_templates;
 pub mod tests;
 pub mod topics;
 pub mod users;
@@ -0,0 +1,38 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+use crate::entities::notifications;
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct NotificationsQuery {
+    /// Only notifications that have not been read
+    #[serde(default)]
+    pub unread: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct NotificationResponse {
+    pub id: Uuid,
+    /// "question_report"
+    pub kind: String,
+    pub message: String,
+    /// Object the notification is about, e.g. the report
+    pub reference_id: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub read_at: Option<DateTime<Utc>>,
+}
+
+impl From<notifications::Model> for NotificationResponse {
+    fn from(model: notifications::Model) -> Self {
+        Self {
+            id: model.id,
+            kind: model.kind,
+            message: model.message,
+            reference_id: model.reference_id,
+            created_at: model.created_at.into(),
+            read_at: model.read_at.map(Into::into),
+        }
+    }
+}
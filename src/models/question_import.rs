@@ -0,0 +1,144 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+use crate::models::question_types::AnswerPayload;
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ImportAnswer {
+    pub value: String,
+    pub is_correct: bool,
+    /// Position, expected value or image region, depending on the question type
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub payload: Option<AnswerPayload>,
+    /// Image ID or stored file name
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub image: Option<String>,
+}
+
+/// One question of an import file
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ImportRecord {
+    /// Stable ID from the source spreadsheet, re-importing updates the question
+    pub external_id: String,
+    /// Topic ID or exact topic name
+    pub topic: String,
+    pub lang: String,
+    pub name: String,
+    #[serde(default)]
+    pub content: Option<String>,
+    pub explanation: String,
+    #[serde(default)]
+    pub points: Option<i16>,
+    /// Image IDs or stored file names, in display order
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub images: Vec<String>,
+    /// Category paths from the root such as `Road signs/Warning`, a name alone
+    /// also matches a nested category when it is unique. Missing categories
+    /// are created.
+    #[serde(default)]
+    pub categories: Vec<String>,
+    /// Licence class codes, empty for every class
+    #[serde(default)]
+    pub license_classes: Vec<String>,
+    pub answers: Vec<ImportAnswer>,
+    /// "choice" by default
+    #[serde(default)]
+    pub question_type: Option<String>,
+    /// Workflow status, new questions default to "draft"
+    #[serde(default)]
+    pub status: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub valid_from: Option<NaiveDate>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub valid_to: Option<NaiveDate>,
+    /// Region code, none for general rules
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub region: Option<String>,
+    /// External ID of a question in the file or the bank whose translation
+    /// group this question joins
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub translation_group: Option<String>,
+}
+
+/// Topic of a JSON bundle, matched by exact name
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ImportTopic {
+    pub name: String,
+    pub difficulty: String,
+    pub duration: i16,
+    #[serde(default)]
+    pub subscription_required: bool,
+}
+
+/// Lesson of a JSON bundle, replaces the lesson with the same title in its topic
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ImportLesson {
+    /// Topic ID or exact topic name
+    pub topic: String,
+    /// Defaults to the topic name
+    #[serde(default)]
+    pub title: Option<String>,
+    /// Order within the topic, new lessons go last by default
+    #[serde(default)]
+    pub position: Option<i16>,
+    pub content: String,
+    /// Sections in reading order, they replace the current ones
+    #[serde(default)]
+    pub sections: Vec<ImportLessonSection>,
+    /// Workflow status, new lessons default to "draft"
+    #[serde(default)]
+    pub status: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ImportLessonSection {
+    pub title: String,
+    pub content: String,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct ImportQuery {
+    /// "csv" or "json"
+    pub format: String,
+    /// Write the records, otherwise only the validation report is returned
+    #[serde(default)]
+    pub commit: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ImportIssue {
+    /// "topics", "lessons" or "questions"
+    pub section: String,
+    /// 1-based record number in its section
+    pub row: usize,
+    pub external_id: Option<String>,
+    pub message: String,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize, ToSchema)]
+pub struct ImportReport {
+    pub total: usize,
+    pub created: usize,
+    pub updated: usize,
+    /// Paths of the categories that do not exist yet and will be created
+    pub new_categories: Vec<String>,
+    /// Topics of the bundle that do not exist yet and will be created
+    pub new_topics: Vec<String>,
+    /// Lessons of the bundle that will be written
+    pub lessons: usize,
+    pub errors: Vec<ImportIssue>,
+    /// Records are written only when requested and nothing failed validation
+    pub committed: bool,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct ExportQuery {
+    /// "csv" or "json". CSV holds only questions, JSON also topics and lessons.
+    pub format: String,
+    pub lang: Option<String>,
+    pub topic_id: Option<Uuid>,
+    /// Workflow status of exported questions and lessons
+    pub status: Option<String>,
+}
@@ -0,0 +1,77 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::entities::question_reports;
+
+/// Learner report on a wrong or ambiguous question
+#[derive(Debug, Serialize, Deserialize, ToSchema, Validate)]
+pub struct ReportQuestionParams {
+    /// Answer option the report is about, none for the question itself
+    pub answer_id: Option<Uuid>,
+    /// "wrong_answer", "ambiguous", "outdated", "typo", "translation" or "other"
+    pub reason: String,
+    #[validate(length(min = 1, max = 2000))]
+    pub comment: Option<String>,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct ReportQueueQuery {
+    /// "open", "accepted", "rejected", "fixed" (defaults to "open")
+    pub status: Option<String>,
+    pub question_id: Option<Uuid>,
+}
+
+/// Editor decision on a report
+#[derive(Debug, Serialize, Deserialize, ToSchema, Validate)]
+pub struct ResolveReportParams {
+    /// "accepted", "rejected" or "fixed"
+    pub status: String,
+    /// Required when rejecting, shown to the reporter
+    #[validate(length(min = 1, max = 2000))]
+    pub comment: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct QuestionReportResponse {
+    pub id: Uuid,
+    pub question_id: Uuid,
+    pub answer_id: Option<Uuid>,
+    pub user_id: Option<Uuid>,
+    pub reason: String,
+    pub comment: Option<String>,
+    pub status: String,
+    pub resolution_comment: Option<String>,
+    pub resolved_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub resolved_at: Option<DateTime<Utc>>,
+}
+
+impl From<question_reports::Model> for QuestionReportResponse {
+    fn from(model: question_reports::Model) -> Self {
+        Self {
+            id: model.id,
+            question_id: model.question_id,
+            answer_id: model.answer_id,
+            user_id: model.user_id,
+            reason: model.reason,
+            comment: model.comment,
+            status: model.status,
+            resolution_comment: model.resolution_comment,
+            resolved_by: model.resolved_by,
+            created_at: model.created_at.into(),
+            resolved_at: model.resolved_at.map(Into::into),
+        }
+    }
+}
+
+/// Reports on a question, for prioritising the queue
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct QuestionReportCountResponse {
+    pub question_id: Uuid,
+    /// Open and accepted reports
+    pub unresolved: i64,
+    pub total: i64,
+}
@@ -0,0 +1,225 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+use crate::{
+    entities::question_revisions,
+    models::question_types::{AnswerPayload, QuestionType},
+};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct AnswerSnapshot {
+    pub id: Uuid,
+    pub value: String,
+    pub is_correct: bool,
+    #[serde(default)]
+    pub payload: Option<AnswerPayload>,
+    #[serde(default)]
+    pub image_id: Option<Uuid>,
+}
+
+/// Question content with its answers and categories at a given revision
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct QuestionSnapshot {
+    pub topic_id: Uuid,
+    pub name: String,
+    pub lang: String,
+    pub content: Option<String>,
+    pub explanation: String,
+    pub points: i16,
+    /// Images in display order
+    #[serde(default)]
+    pub image_ids: Vec<Uuid>,
+    /// Revisions written before question types existed are "choice"
+    #[serde(default = "default_question_type")]
+    pub question_type: String,
+    pub answers: Vec<AnswerSnapshot>,
+    pub category_ids: Vec<Uuid>,
+    #[serde(default)]
+    pub valid_from: Option<NaiveDate>,
+    #[serde(default)]
+    pub valid_to: Option<NaiveDate>,
+    #[serde(default)]
+    pub region: Option<String>,
+}
+
+fn default_question_type() -> String {
+    QuestionType::Choice.as_str().to_string()
+}
+
+impl QuestionSnapshot {
+    /// Changes needed to turn `self` into `other`
+    pub fn diff(&self, other: &Self) -> SnapshotDiff {
+        let mut fields = Vec::new();
+        let mut push = |field: &str, from: Value, to: Value| {
+            if from != to {
+                fields.push(FieldChange {
+                    field: field.to_string(),
+                    from,
+                    to,
+                });
+            }
+        };
+        push(
+            "topic_id",
+            self.topic_id.to_string().into(),
+            other.topic_id.to_string().into(),
+        );
+        push("name", self.name.clone().into(), other.name.clone().into());
+        push("lang", self.lang.clone().into(), other.lang.clone().into());
+        push(
+            "content",
+            self.content.clone().into(),
+            other.content.clone().into(),
+        );
+        push(
+            "explanation",
+            self.explanation.clone().into(),
+            other.explanation.clone().into(),
+        );
+        push("points", self.points.into(), other.points.into());
+        let image_ids = |ids: &[Uuid]| ids.iter().map(|id| id.to_string()).collect::<Vec<_>>();
+        push(
+            "image_ids",
+            image_ids(&self.image_ids).into(),
+            image_ids(&other.image_ids).into(),
+        );
+        push(
+            "question_type",
+            self.question_type.clone().into(),
+            other.question_type.clone().into(),
+        );
+        let date = |date: Option<NaiveDate>| date.map(|d| d.to_string());
+        push(
+            "valid_from",
+            date(self.valid_from).into(),
+            date(other.valid_from).into(),
+        );
+        push(
+            "valid_to",
+            date(self.valid_to).into(),
+            date(other.valid_to).into(),
+        );
+        push(
+            "region",
+            self.region.clone().into(),
+            other.region.clone().into(),
+        );
+
+        let mut answers: Vec<AnswerChange> = self
+            .answers
+            .iter()
+            .filter_map(|before| {
+                let after = other.answers.iter().find(|a| a.id == before.id);
+                (after != Some(before)).then(|| AnswerChange {
+                    answer_id: before.id,
+                    from: Some(before.clone()),
+                    to: after.cloned(),
+                })
+            })
+            .collect();
+        answers.extend(
+            other
+                .answers
+                .iter()
+                .filter(|after| !self.answers.iter().any(|a| a.id == after.id))
+                .map(|after| AnswerChange {
+                    answer_id: after.id,
+                    from: None,
+                    to: Some(after.clone()),
+                }),
+        );
+
+        SnapshotDiff {
+            fields,
+            answers,
+            added_category_ids: other
+                .category_ids
+                .iter()
+                .filter(|id| !self.category_ids.contains(id))
+                .cloned()
+                .collect(),
+            removed_category_ids: self
+                .category_ids
+                .iter()
+                .filter(|id| !other.category_ids.contains(id))
+                .cloned()
+                .collect(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct FieldChange {
+    pub field: String,
+    #[schema(value_type = Object)]
+    pub from: Value,
+    #[schema(value_type = Object)]
+    pub to: Value,
+}
+
+/// Added (`from` is empty), removed (`to` is empty) or edited answer
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct AnswerChange {
+    pub answer_id: Uuid,
+    pub from: Option<AnswerSnapshot>,
+    pub to: Option<AnswerSnapshot>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct SnapshotDiff {
+    pub fields: Vec<FieldChange>,
+    pub answers: Vec<AnswerChange>,
+    pub added_category_ids: Vec<Uuid>,
+    pub removed_category_ids: Vec<Uuid>,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct RevisionDiffQuery {
+    /// Older revision number
+    pub from: i32,
+    /// Newer revision number
+    pub to: i32,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct QuestionRevisionSummary {
+    pub id: Uuid,
+    pub question_id: Uuid,
+    pub revision: i32,
+    pub author_id: Option<Uuid>,
+    /// "initial", "create", "update", "answers", "categories", "revert", "import"
+    pub action: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<question_revisions::Model> for QuestionRevisionSummary {
+    fn from(model: question_revisions::Model) -> Self {
+        Self {
+            id: model.id,
+            question_id: model.question_id,
+            revision: model.revision,
+            author_id: model.author_id,
+            action: model.action,
+            created_at: model.created_at.into(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct QuestionRevisionResponse {
+    #[serde(flatten)]
+    pub revision: QuestionRevisionSummary,
+    pub snapshot: QuestionSnapshot,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct QuestionRevisionDiffResponse {
+    pub question_id: Uuid,
+    pub from_revision: i32,
+    pub to_revision: i32,
+    #[serde(flatten)]
+    pub diff: SnapshotDiff,
+}
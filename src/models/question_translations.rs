@@ -0,0 +1,25 @@
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct LinkTranslationParams {
+    /// Question whose translation group is joined
+    pub question_id: Uuid,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct MissingTranslationsQuery {
+    /// Language the translations are missing in
+    pub lang: String,
+}
+
+/// Translation group without a variant in the requested language
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct MissingTranslationResponse {
+    pub translation_group_id: Uuid,
+    /// Languages the group is available in
+    pub langs: Vec<String>,
+    /// Published variants of the group
+    pub question_ids: Vec<Uuid>,
+}
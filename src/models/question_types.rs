@@ -0,0 +1,190 @@
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum QuestionType {
+    /// One or more correct options
+    #[default]
+    Choice,
+    /// Exactly two options, one of them correct
+    TrueFalse,
+    /// Options have to be put in the order given by their positions
+    Ordering,
+    /// A number within the tolerance of the expected value
+    Numeric,
+    /// A point inside one of the correct regions of the question image
+    Hotspot,
+}
+
+impl QuestionType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            QuestionType::Choice => "choice",
+            QuestionType::TrueFalse => "true_false",
+            QuestionType::Ordering => "ordering",
+            QuestionType::Numeric => "numeric",
+            QuestionType::Hotspot => "hotspot",
+        }
+    }
+
+    /// Whether students see the answer options. Numeric values and hotspot
+    /// regions would give the answer away.
+    pub fn shows_options(&self) -> bool {
+        matches!(
+            self,
+            QuestionType::Choice | QuestionType::TrueFalse | QuestionType::Ordering
+        )
+    }
+
+    /// Checks the payload of a single answer fits the question type
+    pub fn check_payload(&self, payload: Option<&AnswerPayload>) -> Result<(), String> {
+        match (self, payload) {
+            (QuestionType::Choice | QuestionType::TrueFalse, None) => Ok(()),
+            (QuestionType::Ordering, Some(AnswerPayload::Ordering { position })) => {
+                if *position >= 1 {
+                    Ok(())
+                } else {
+                    Err("ordering position must be positive".to_string())
+                }
+            }
+            (QuestionType::Numeric, Some(AnswerPayload::Numeric { value, tolerance })) => {
+                if value.is_finite() && tolerance.is_finite() && *tolerance >= 0.0 {
+                    Ok(())
+                } else {
+                    Err("numeric value and a non-negative tolerance are required".to_string())
+                }
+            }
+            (
+                QuestionType::Hotspot,
+                Some(AnswerPayload::Hotspot {
+                    x,
+                    y,
+                    width,
+                    height,
+                }),
+            ) => {
+                let inside = |start: f64, size: f64| {
+                    (0.0..=1.0).contains(&start) && size > 0.0 && start + size <= 1.0
+                };
+                if inside(*x, *width) && inside(*y, *height) {
+                    Ok(())
+                } else {
+                    Err("hotspot region must lie within the image".to_string())
+                }
+            }
+            (question_type, _) => Err(format!(
+                "answer payload does not match the \"{}\" question type",
+                question_type.as_str()
+            )),
+        }
+    }
+
+    /// Checks the full answer set of a question before it is published or imported
+    pub fn check_answers<'a>(
+        &self,
+        answers: impl IntoIterator<Item = (bool, Option<&'a AnswerPayload>)>,
+    ) -> Result<(), String> {
+        let answers: Vec<(bool, Option<&AnswerPayload>)> = answers.into_iter().collect();
+        for (_, payload) in &answers {
+            self.check_payload(*payload)?;
+        }
+
+        let correct = answers.iter().filter(|(is_correct, _)| *is_correct).count();
+        match self {
+            QuestionType::Choice if correct == 0 => {
+                Err("at least one correct answer is required".to_string())
+            }
+            QuestionType::TrueFalse if answers.len() != 2 || correct != 1 => {
+                Err("true/false questions need two answers, one of them correct".to_string())
+            }
+            QuestionType::Ordering if answers.len() < 2 => {
+                Err("ordering questions need at least two answers".to_string())
+            }
+            QuestionType::Ordering => {
+                let mut positions: Vec<i16> = answers
+                    .iter()
+                    .filter_map(|(_, payload)| match payload {
+                        Some(AnswerPayload::Ordering { position }) => Some(*position),
+                        _ => None,
+                    })
+                    .collect();
+                positions.sort_unstable();
+                positions.dedup();
+                if positions.len() == answers.len() {
+                    Ok(())
+                } else {
+                    Err("ordering positions must be unique".to_string())
+                }
+            }
+            QuestionType::Numeric | QuestionType::Hotspot if correct == 0 => {
+                Err("at least one correct answer is required".to_string())
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+impl FromStr for QuestionType {
+    type Err = ();
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "choice" => Ok(QuestionType::Choice),
+            "true_false" => Ok(QuestionType::TrueFalse),
+            "ordering" => Ok(QuestionType::Ordering),
+            "numeric" => Ok(QuestionType::Numeric),
+            "hotspot" => Ok(QuestionType::Hotspot),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Type-specific data of an answer, stored in `answers.payload`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum AnswerPayload {
+    /// 1-based place of the option in the correct order
+    Ordering { position: i16 },
+    /// Expected value and the allowed deviation from it
+    Numeric { value: f64, tolerance: f64 },
+    /// Region of the question image, coordinates relative to its size (0..1)
+    Hotspot {
+        x: f64,
+        y: f64,
+        width: f64,
+        height: f64,
+    },
+}
+
+impl AnswerPayload {
+    pub fn from_json(value: Option<&serde_json::Value>) -> Option<Self> {
+        value.and_then(|v| serde_json::from_value(v.clone()).ok())
+    }
+
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::to_value(self).unwrap_or_default()
+    }
+}
+
+/// Point on the question image, coordinates relative to its size (0..1)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct HotspotPoint {
+    pub x: f64,
+    pub y: f64,
+}
+
+/// Answer to a question of any type
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct SubmittedAnswer {
+    /// Selected options, or every option in the chosen order for "ordering"
+    #[serde(default)]
+    pub answer_ids: Vec<uuid::Uuid>,
+    /// Value of a "numeric" question
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub numeric_value: Option<f64>,
+    /// Clicked point of a "hotspot" question
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub point: Option<HotspotPoint>,
+}
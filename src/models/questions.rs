@@ -1,9 +1,10 @@
+use chrono::NaiveDate;
 use serde::{Deserialize, Serialize};
 use utoipa::{IntoParams, ToSchema};
 use uuid::Uuid;
 use validator::Validate;
 
-use crate::entities::questions;
+use crate::{entities::questions, models::images::ImageLink};
 
 #[derive(Debug, Deserialize, IntoParams)]
 pub struct LangQuery {
@@ -19,6 +20,25 @@ pub struct QuestionResponse {
     pub lang: String,
     pub content: Option<String>,
     pub explanation: String,
+    pub points: i16,
+    /// "draft", "in_review", "published", "archived"
+    pub status: String,
+    pub review_comment: Option<String>,
+    pub external_id: Option<String>,
+    /// Attached images in display order
+    pub images: Vec<ImageLink>,
+    /// Licence classes the question applies to, empty for all classes
+    pub license_classes: Vec<String>,
+    /// "choice", "true_false", "ordering", "numeric" or "hotspot"
+    pub question_type: String,
+    /// First day the question is in force
+    pub valid_from: Option<NaiveDate>,
+    /// Last day the question is in force
+    pub valid_to: Option<NaiveDate>,
+    /// Region the rule applies to, none for general rules
+    pub region: Option<String>,
+    /// Shared by the variants of the question in other languages
+    pub translation_group_id: Uuid,
 }
 
 impl From<questions::Model> for QuestionResponse {
@@ -30,6 +50,17 @@ impl From<questions::Model> for QuestionResponse {
             lang: model.lang,
             content: model.content,
             explanation: model.explanation,
+            points: model.points,
+            status: model.status,
+            review_comment: model.review_comment,
+            external_id: model.external_id,
+            images: Vec::new(),
+            license_classes: Vec::new(),
+            question_type: model.question_type,
+            valid_from: model.valid_from,
+            valid_to: model.valid_to,
+            region: model.region,
+            translation_group_id: model.translation_group_id,
         }
     }
 }
@@ -44,6 +75,22 @@ pub struct CreateQuestionParams {
     pub content: Option<String>,
     #[validate(length(min = 1))]
     pub explanation: String,
+    /// Points the question is worth (defaults to 1)
+    #[validate(range(min = 1, max = 100))]
+    pub points: Option<i16>,
+    /// "choice" (default), "true_false", "ordering", "numeric" or "hotspot"
+    pub question_type: Option<String>,
+    /// Images in display order
+    pub image_ids: Option<Vec<Uuid>>,
+    /// Licence class codes, none means every class
+    pub license_classes: Option<Vec<String>>,
+    pub valid_from: Option<NaiveDate>,
+    pub valid_to: Option<NaiveDate>,
+    /// Region code, none for general rules
+    #[validate(length(min = 1, max = 20))]
+    pub region: Option<String>,
+    /// Creates the question as a translation of this group
+    pub translation_group_id: Option<Uuid>,
 }
 
 #[derive(Debug, Serialize, Deserialize, ToSchema, Validate)]
@@ -56,4 +103,16 @@ pub struct UpdateQuestionParams {
     pub content: Option<String>,
     #[validate(length(min = 1))]
     pub explanation: Option<String>,
+    #[validate(range(min = 1, max = 100))]
+    pub points: Option<i16>,
+    pub question_type: Option<String>,
+    /// Replaces the images, in display order
+    pub image_ids: Option<Vec<Uuid>>,
+    /// Replaces the licence classes, an empty list applies to every class
+    pub license_classes: Option<Vec<String>>,
+    pub valid_from: Option<NaiveDate>,
+    pub valid_to: Option<NaiveDate>,
+    /// An empty string makes the question a general rule
+    #[validate(length(max = 20))]
+    pub region: Option<String>,
 }
@@ -0,0 +1,107 @@
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{entities::road_sign_translations, models::images::ImageLink};
+
+/// Sign with its name and meaning in the requested language
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct RoadSignResponse {
+    pub id: Uuid,
+    /// Official code, e.g. "2.1"
+    pub code: String,
+    /// "warning", "priority", "prohibitory", "mandatory", "special", "information",
+    /// "service" or "additional"
+    pub group: String,
+    pub image: Option<ImageLink>,
+    pub lang: String,
+    pub name: String,
+    pub meaning: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct RoadSignTranslationResponse {
+    pub lang: String,
+    pub name: String,
+    pub meaning: String,
+}
+
+impl From<road_sign_translations::Model> for RoadSignTranslationResponse {
+    fn from(model: road_sign_translations::Model) -> Self {
+        Self {
+            lang: model.lang,
+            name: model.name,
+            meaning: model.meaning,
+        }
+    }
+}
+
+/// Sign with every translation and the content linked to it
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct RoadSignDetailResponse {
+    pub id: Uuid,
+    pub code: String,
+    pub group: String,
+    pub image: Option<ImageLink>,
+    pub translations: Vec<RoadSignTranslationResponse>,
+    /// Published questions about the sign
+    pub question_ids: Vec<Uuid>,
+    /// Published lessons explaining the sign
+    pub lesson_ids: Vec<Uuid>,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct RoadSignsQuery {
+    pub lang: String,
+    /// Only signs of this group
+    pub group: Option<String>,
+}
+
+#[derive(Debug, Deserialize, IntoParams, Validate)]
+pub struct RoadSignSearchQuery {
+    /// Code prefix or part of the name or meaning
+    #[validate(length(min = 1, max = 100))]
+    pub q: String,
+    pub lang: String,
+    /// 20 by default
+    #[validate(range(min = 1, max = 100))]
+    pub limit: Option<u64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema, Validate)]
+pub struct RoadSignTranslationParams {
+    #[validate(length(min = 2, max = 10))]
+    pub lang: String,
+    #[validate(length(min = 1, max = 255))]
+    pub name: String,
+    #[validate(length(min = 1))]
+    pub meaning: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema, Validate)]
+pub struct CreateRoadSignParams {
+    #[validate(length(min = 1, max = 20))]
+    pub code: String,
+    pub group: String,
+    pub image_id: Option<Uuid>,
+    #[validate(length(min = 1))]
+    pub translations: Vec<RoadSignTranslationParams>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema, Validate)]
+pub struct UpdateRoadSignParams {
+    #[validate(length(min = 1, max = 20))]
+    pub code: Option<String>,
+    pub group: Option<String>,
+    pub image_id: Option<Uuid>,
+    /// Added or replaced per language, other languages are kept
+    pub translations: Option<Vec<RoadSignTranslationParams>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct RoadSignLinkResponse {
+    pub sign_id: Uuid,
+    /// Question or lesson ID
+    pub target_id: Uuid,
+}
@@ -0,0 +1,57 @@
+use sea_orm::FromQueryResult;
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+use validator::Validate;
+
+#[derive(Debug, Deserialize, IntoParams, Validate)]
+pub struct SearchQuery {
+    /// Search phrase, supports quotes, `or` and `-word`
+    #[validate(length(min = 2, max = 200))]
+    pub q: String,
+    /// Only questions in this language
+    pub lang: Option<String>,
+    pub topic_id: Option<Uuid>,
+    /// Only questions of this category, lessons are skipped
+    pub category_id: Option<Uuid>,
+    /// Results per section, 20 by default
+    #[validate(range(min = 1, max = 100))]
+    pub limit: Option<u64>,
+    #[serde(default)]
+    pub offset: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema, FromQueryResult)]
+pub struct SearchHit {
+    pub id: Uuid,
+    pub topic_id: Uuid,
+    pub topic_name: String,
+    /// Question name or lesson title
+    pub title: String,
+    /// Fragment of the text with matches wrapped in `<mark>`
+    pub snippet: String,
+    pub rank: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema, FromQueryResult)]
+pub struct FacetCount {
+    pub id: Uuid,
+    pub name: String,
+    pub count: i64,
+}
+
+/// Number of matching questions per topic and category
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct SearchFacets {
+    pub topics: Vec<FacetCount>,
+    pub categories: Vec<FacetCount>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct SearchResponse {
+    pub questions: Vec<SearchHit>,
+    pub total_questions: i64,
+    pub lessons: Vec<SearchHit>,
+    pub total_lessons: i64,
+    pub facets: SearchFacets,
+}
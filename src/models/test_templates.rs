@@ -0,0 +1,77 @@
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::entities::test_templates;
+
+/// Query parameters for listing published templates
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct TemplatesQuery {
+    /// Language code (e.g., "en", "ru")
+    pub lang: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct TestTemplateResponse {
+    pub id: Uuid,
+    pub title: String,
+    pub lang: String,
+    pub time_limit_seconds: Option<i32>,
+    pub is_published: bool,
+    pub created_by: Option<Uuid>,
+    /// Licence class the template is built for, none for every class
+    pub license_class: Option<String>,
+    /// Question ids in test order
+    pub question_ids: Vec<Uuid>,
+}
+
+impl TestTemplateResponse {
+    pub fn from_model(model: test_templates::Model, question_ids: Vec<Uuid>) -> Self {
+        Self {
+            id: model.id,
+            title: model.title,
+            lang: model.lang,
+            time_limit_seconds: model.time_limit_seconds,
+            is_published: model.is_published,
+            created_by: model.created_by,
+            license_class: model.license_class,
+            question_ids,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema, Validate)]
+pub struct CreateTestTemplateParams {
+    #[validate(length(min = 1, max = 255))]
+    pub title: String,
+    #[validate(length(min = 2, max = 10))]
+    pub lang: String,
+    /// Time limit in seconds
+    #[validate(range(min = 1))]
+    pub time_limit_seconds: Option<i32>,
+    #[serde(default)]
+    pub is_published: bool,
+    /// Licence class the template is built for, questions must apply to it
+    pub license_class: Option<String>,
+    /// Question ids in test order
+    #[validate(length(min = 1, max = 100))]
+    pub question_ids: Vec<Uuid>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema, Validate)]
+pub struct UpdateTestTemplateParams {
+    #[validate(length(min = 1, max = 255))]
+    pub title: Option<String>,
+    #[validate(length(min = 2, max = 10))]
+    pub lang: Option<String>,
+    /// Zero removes the time limit
+    #[validate(range(min = 0))]
+    pub time_limit_seconds: Option<i32>,
+    pub is_published: Option<bool>,
+    /// An empty string makes the template available to every class
+    pub license_class: Option<String>,
+    /// Replaces the question list, in test order
+    #[validate(length(min = 1, max = 100))]
+    pub question_ids: Option<Vec<Uuid>>,
+}
@@ -1,10 +1,17 @@
 use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 use utoipa::{IntoParams, ToSchema};
 use uuid::Uuid;
 use validator::Validate;
 
-use crate::entities::tests;
+use crate::{
+    entities::tests,
+    models::{
+        images::ImageLink,
+        question_types::{AnswerPayload, SubmittedAnswer},
+    },
+};
 
 /// Query parameters for listing tests
 #[derive(Debug, Deserialize, IntoParams)]
@@ -13,20 +20,45 @@ pub struct TestsQuery {
     pub status: Option<String>,
 }
 
+/// Query parameters for test history
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct HistoryQuery {
+    /// Only show attempts of the given retake series
+    pub series_id: Option<Uuid>,
+}
+
 /// Parameters for creating a new test
 #[derive(Debug, Serialize, Deserialize, ToSchema, Validate)]
 pub struct CreateTestParams {
-    /// Filter type: "favorites", "category", "topic"
+    /// Filter type: "favorites", "category", "topic", "template", "daily", "signs"
     #[validate(length(min = 1, max = 50))]
     pub filter_type: String,
-    /// Filter ID (required for "category" and "topic" filter types)
+    /// Filter ID (required for "category", "topic" and "template" filter types,
+    /// optional challenge ID for "daily", defaults to today's challenge)
     pub filter_id: Option<Uuid>,
     /// Language code
     #[validate(length(min = 2, max = 10))]
     pub lang: String,
-    /// Number of questions (1-25)
+    /// Number of questions (1-25), ignored for "template" and "daily" tests.
+    /// "signs" tests are generated from the road sign catalogue.
     #[validate(range(min = 1, max = 25))]
     pub questions_count: i16,
+    /// Scoring policy: "exact" (default), "partial", "negative"
+    pub scoring_policy: Option<String>,
+}
+
+/// Parameters for retaking a finished test
+#[derive(Debug, Default, Serialize, Deserialize, ToSchema, Validate)]
+pub struct RetakeTestParams {
+    /// Shuffle the question order
+    #[serde(default)]
+    pub shuffle: bool,
+    /// Only include questions answered incorrectly or left unanswered
+    #[serde(default)]
+    pub only_wrong: bool,
+    /// Retake in another language, questions without a translation are left out
+    #[validate(length(min = 2, max = 10))]
+    pub lang: Option<String>,
 }
 
 /// Response for a test (list view)
@@ -41,6 +73,17 @@ pub struct TestResponse {
     pub correct_count: i16,
     pub status: String,
     pub score_percent: Option<i16>,
+    pub scoring_policy: String,
+    pub max_points: i16,
+    pub earned_points: Decimal,
+    /// Test this attempt retakes
+    pub retake_of_id: Option<Uuid>,
+    /// First test of the retake series
+    pub series_id: Uuid,
+    pub attempt_number: i16,
+    pub time_limit_seconds: Option<i32>,
+    /// Licence class the questions were picked for
+    pub license_class: Option<String>,
     pub created_at: DateTime<Utc>,
     pub completed_at: Option<DateTime<Utc>>,
 }
@@ -57,12 +100,36 @@ impl TestResponse {
             correct_count: model.correct_count,
             status: model.status,
             score_percent: model.score_percent,
+            scoring_policy: model.scoring_policy,
+            max_points: model.max_points,
+            earned_points: model.earned_points,
+            retake_of_id: model.retake_of_id,
+            series_id: model.series_id.unwrap_or(model.id),
+            attempt_number: model.attempt_number,
+            time_limit_seconds: model.time_limit_seconds,
+            license_class: model.license_class,
             created_at: model.created_at.into(),
             completed_at: model.completed_at.map(|dt| dt.into()),
         }
     }
 }
 
+/// Attempt of a retake series (score progression)
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct TestAttemptResponse {
+    pub id: Uuid,
+    pub attempt_number: i16,
+    pub total_questions: i16,
+    pub correct_count: i16,
+    pub status: String,
+    pub score_percent: Option<i16>,
+    /// Score difference to the previous finished attempt
+    pub score_change: Option<i16>,
+    pub earned_points: Decimal,
+    pub created_at: DateTime<Utc>,
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
 /// Question info in test details
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct TestQuestionInfo {
@@ -70,6 +137,8 @@ pub struct TestQuestionInfo {
     pub question_id: Uuid,
     pub is_answered: bool,
     pub is_correct: Option<bool>,
+    pub points: i16,
+    pub earned_points: Option<Decimal>,
 }
 
 /// Detailed test response
@@ -84,14 +153,19 @@ pub struct TestDetailResponse {
     pub correct_count: i16,
     pub status: String,
     pub score_percent: Option<i16>,
+    pub scoring_policy: String,
+    pub max_points: i16,
+    pub earned_points: Decimal,
+    pub time_limit_seconds: Option<i32>,
     pub questions: Vec<TestQuestionInfo>,
 }
 
 /// Answer option (without is_correct for active test)
-#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct AnswerOption {
     pub id: Uuid,
     pub value: String,
+    pub image: Option<ImageLink>,
 }
 
 /// Answer option with correctness (for review)
@@ -100,10 +174,13 @@ pub struct AnswerOptionWithCorrectness {
     pub id: Uuid,
     pub value: String,
     pub is_correct: bool,
+    /// Position, expected value or image region, depending on the question type
+    pub payload: Option<AnswerPayload>,
+    pub image: Option<ImageLink>,
 }
 
 /// Question info (without explanation for active test)
-#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct QuestionInfo {
     pub id: Uuid,
     pub name: String,
@@ -122,10 +199,15 @@ pub struct QuestionInfoWithExplanation {
 }
 
 /// Current question response
-#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct CurrentQuestionResponse {
     pub order: i16,
     pub question: QuestionInfo,
+    /// "choice", "true_false", "ordering", "numeric" or "hotspot"
+    pub question_type: String,
+    /// Question images in display order, the first one is the hotspot image
+    pub images: Vec<ImageLink>,
+    /// Options to pick or to order, empty for "numeric" and "hotspot" questions
     pub answers: Vec<AnswerOption>,
     pub multiple_answers: bool,
 }
@@ -134,8 +216,8 @@ pub struct CurrentQuestionResponse {
 #[derive(Debug, Serialize, Deserialize, ToSchema, Validate)]
 pub struct AnswerParams {
     pub question_id: Uuid,
-    #[validate(length(min = 1))]
-    pub answer_ids: Vec<Uuid>,
+    #[serde(flatten)]
+    pub answer: SubmittedAnswer,
 }
 
 /// Result of answering a question
@@ -143,10 +225,16 @@ pub struct AnswerParams {
 pub struct AnswerResultResponse {
     pub is_correct: bool,
     pub correct_answer_ids: Vec<Uuid>,
+    /// Correct options with their payloads, in the correct order for "ordering"
+    pub correct_answers: Vec<AnswerOptionWithCorrectness>,
     pub explanation: String,
+    /// Points earned for this question (may be negative)
+    pub earned_points: Decimal,
     pub test_completed: bool,
     pub answered_count: i16,
     pub correct_count: i16,
+    /// Points earned in the test so far
+    pub test_earned_points: Decimal,
     pub score_percent: Option<i16>,
 }
 
@@ -156,6 +244,7 @@ pub struct CompleteTestResponse {
     pub status: String,
     pub answered_count: i16,
     pub correct_count: i16,
+    pub earned_points: Decimal,
     pub score_percent: i16,
 }
 
@@ -164,9 +253,15 @@ pub struct CompleteTestResponse {
 pub struct ReviewQuestionResponse {
     pub order: i16,
     pub question: QuestionInfoWithExplanation,
+    pub question_type: String,
+    pub images: Vec<ImageLink>,
     pub answers: Vec<AnswerOptionWithCorrectness>,
     pub selected_answer_ids: Vec<Uuid>,
+    /// Submitted answer as given, including the order, value or point
+    pub response: Option<SubmittedAnswer>,
     pub is_correct: bool,
+    pub points: i16,
+    pub earned_points: Option<Decimal>,
 }
 
 /// Review response for completed test
@@ -179,6 +274,9 @@ pub struct TestReviewResponse {
     pub total_questions: i16,
     pub correct_count: i16,
     pub score_percent: i16,
+    pub scoring_policy: String,
+    pub max_points: i16,
+    pub earned_points: Decimal,
     pub status: String,
     pub questions: Vec<ReviewQuestionResponse>,
 }
@@ -3,15 +3,29 @@ use utoipa::ToSchema;
 use uuid::Uuid;
 use validator::Validate;
 
-use crate::entities::topics;
+use crate::{
+    entities::{topic_prerequisites, topics},
+    models::lesson_progress::TopicProgressResponse,
+};
 
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct TopicResponse {
     pub id: Uuid,
     pub name: String,
+    /// Language of the translated name, none for the original one
+    pub lang: Option<String>,
     pub difficulty: String,
     pub duration: i16,
     pub subscription_required: bool,
+    /// Place in the curriculum, lower first
+    pub position: i32,
+    /// Conditions to open the topic, all of them must be met
+    pub prerequisites: Vec<TopicPrerequisiteResponse>,
+    /// Completion for the current user, only for authorized requests
+    pub progress: Option<TopicProgressResponse>,
+    /// Whether the current user met the prerequisites, only for authorized
+    /// requests in curriculum mode
+    pub unlocked: Option<bool>,
 }
 
 impl From<topics::Model> for TopicResponse {
@@ -19,13 +33,55 @@ impl From<topics::Model> for TopicResponse {
         Self {
             id: model.id,
             name: model.name,
+            lang: None,
             difficulty: model.difficulty,
             duration: model.duration,
             subscription_required: model.subscription_required,
+            position: model.position,
+            prerequisites: Vec::new(),
+            progress: None,
+            unlocked: None,
+        }
+    }
+}
+
+/// Condition on a required topic: the lesson is read and the accuracy
+/// reaches the threshold; with neither set every lesson must be read
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct TopicPrerequisiteResponse {
+    pub required_topic_id: Uuid,
+    pub lesson_id: Option<Uuid>,
+    /// Minimum share of correct answers in the required topic, in percent
+    pub min_accuracy: Option<i16>,
+}
+
+impl From<topic_prerequisites::Model> for TopicPrerequisiteResponse {
+    fn from(model: topic_prerequisites::Model) -> Self {
+        Self {
+            required_topic_id: model.required_topic_id,
+            lesson_id: model.lesson_id,
+            min_accuracy: model.min_accuracy,
         }
     }
 }
 
+#[derive(Debug, Serialize, Deserialize, ToSchema, Validate)]
+pub struct TopicPrerequisiteParams {
+    pub required_topic_id: Uuid,
+    /// Lesson of the required topic that must be read
+    pub lesson_id: Option<Uuid>,
+    #[validate(range(min = 1, max = 100))]
+    pub min_accuracy: Option<i16>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema, Validate)]
+pub struct TopicTranslationParams {
+    #[validate(length(min = 2, max = 10))]
+    pub lang: String,
+    #[validate(length(min = 1, max = 255))]
+    pub name: String,
+}
+
 #[derive(Debug, Serialize, Deserialize, ToSchema, Validate)]
 pub struct CreateTopicParams {
     #[validate(length(min = 1, max = 255))]
@@ -35,6 +91,14 @@ pub struct CreateTopicParams {
     pub duration: i16,
     #[serde(default)]
     pub subscription_required: bool,
+    #[serde(default)]
+    pub position: i32,
+    #[serde(default)]
+    #[validate(nested)]
+    pub translations: Vec<TopicTranslationParams>,
+    #[serde(default)]
+    #[validate(nested)]
+    pub prerequisites: Vec<TopicPrerequisiteParams>,
 }
 
 #[derive(Debug, Serialize, Deserialize, ToSchema, Validate)]
@@ -45,4 +109,11 @@ pub struct UpdateTopicParams {
     pub difficulty: Option<String>,
     pub duration: Option<i16>,
     pub subscription_required: Option<bool>,
+    pub position: Option<i32>,
+    /// Added or replaced per language, other languages are kept
+    #[validate(nested)]
+    pub translations: Option<Vec<TopicTranslationParams>>,
+    /// Replaces all prerequisites of the topic
+    #[validate(nested)]
+    pub prerequisites: Option<Vec<TopicPrerequisiteParams>>,
 }
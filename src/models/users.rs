@@ -26,6 +26,20 @@ pub struct UpdateUserParams {
     pub username: Option<String>,
     pub phone_number: Option<String>,
     pub email: Option<String>,
+    /// Target licence class, an empty string clears it
+    pub license_class: Option<String>,
+    /// Jurisdiction for regional rules, an empty string clears it
+    #[validate(length(max = 20))]
+    pub region: Option<String>,
+}
+
+/// Roles a user can have, editors and admins pass as `AdminUser`
+pub const ROLES: [&str; 3] = ["learner", "editor", "admin"];
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct UpdateRoleParams {
+    /// "learner", "editor" or "admin"
+    pub role: String,
 }
 
 #[derive(Clone, Debug, Validate, Serialize, Deserialize, ToSchema)]
@@ -41,6 +55,9 @@ pub struct UsersResponse {
     pub email: String,
     pub phone_number: Option<String>,
     pub username: Option<String>,
+    pub license_class: Option<String>,
+    pub region: Option<String>,
+    pub role: String,
 }
 
 impl From<crate::entities::users::Model> for UsersResponse {
@@ -50,6 +67,9 @@ impl From<crate::entities::users::Model> for UsersResponse {
             email: value.email,
             phone_number: value.phone_number,
             username: value.username,
+            license_class: value.license_class,
+            region: value.region,
+            role: value.role,
         }
     }
 }
@@ -75,6 +95,9 @@ pub struct UserSubscriptionResponse {
     pub email: String,
     pub phone_number: Option<String>,
     pub username: Option<String>,
+    pub license_class: Option<String>,
+    pub region: Option<String>,
+    pub role: String,
     pub subscription: Option<SubscriptionResponse>,
 }
 
@@ -85,6 +108,9 @@ impl From<crate::entities::users::Model> for UserSubscriptionResponse {
             email: value.email,
             phone_number: value.phone_number,
             username: value.username,
+            license_class: value.license_class,
+            region: value.region,
+            role: value.role,
             subscription: None,
         }
     }
@@ -134,6 +160,11 @@ impl crate::entities::users::Model {
         Ok(user)
     }
 
+    /// Editors and admins manage content, review it and host classrooms
+    pub fn is_editor(&self) -> bool {
+        matches!(self.role.as_str(), "editor" | "admin")
+    }
+
     pub fn validate_password(&self, password: String) -> Result<(), ApiError> {
         if crate::utils::password::verify_password(password, self.password.clone())? {
             Ok(())
@@ -1,7 +1,15 @@
 use crate::{
     AppContext,
     entities::{answers, questions},
-    models::answers::{AnswerResponse, CreateAnswerParams, UpdateAnswerParams},
+    models::{
+        answers::{AnswerResponse, CreateAnswerParams, UpdateAnswerParams},
+        question_types::{AnswerPayload, QuestionType},
+    },
+    rest::{
+        content_reviews::reopen_edited_question, curriculum::check_unlocked,
+        images::check_images_exist, question_revisions::record_revision,
+        question_translations::check_answer_translation,
+    },
     utils::{
         extractors::{AuthUser, check_topic_access_by_id},
         response::ApiError,
@@ -13,7 +21,8 @@ use axum::{
     response::{IntoResponse, Response},
 };
 use sea_orm::{
-    ActiveModelTrait, ActiveValue::Set, ColumnTrait, EntityTrait, IntoActiveModel, QueryFilter,
+    ActiveModelTrait, ActiveValue::Set, ColumnTrait, ConnectionTrait, EntityTrait, IntoActiveModel,
+    QueryFilter, TransactionTrait,
 };
 use utoipa_axum::{router::OpenApiRouter, routes};
 use uuid::Uuid;
@@ -33,6 +42,78 @@ async fn get_topic_id_by_question(
     Ok(question.topic_id)
 }
 
+/// Checks the answer payload fits the type of its question
+async fn check_payload(
+    db: &sea_orm::DatabaseConnection,
+    question_id: Uuid,
+    payload: Option<&AnswerPayload>,
+) -> Result<(), ApiError> {
+    let question = questions::Entity::find_by_id(question_id)
+        .one(db)
+        .await
+        .map_err(ApiError::from)?
+        .ok_or(ApiError::NotFound)?;
+
+    question
+        .question_type
+        .parse::<QuestionType>()
+        .unwrap_or_default()
+        .check_payload(payload)
+        .map_err(|err| {
+            tracing::debug!("answer payload: {err}");
+            ApiError::InvalidFieldValue
+        })
+}
+
+/// Checks the stored answer set of a question fits its type
+pub(crate) async fn check_answer_set<C: ConnectionTrait>(
+    db: &C,
+    question: &questions::Model,
+) -> Result<(), ApiError> {
+    let answers_list = answers::Entity::find()
+        .filter(answers::Column::QuestionId.eq(question.id))
+        .all(db)
+        .await
+        .map_err(ApiError::from)?;
+    let payloads: Vec<Option<AnswerPayload>> = answers_list
+        .iter()
+        .map(|a| AnswerPayload::from_json(a.payload.as_ref()))
+        .collect();
+
+    question
+        .question_type
+        .parse::<QuestionType>()
+        .unwrap_or_default()
+        .check_answers(
+            answers_list
+                .iter()
+                .zip(payloads.iter())
+                .map(|(a, payload)| (a.is_correct, payload.as_ref())),
+        )
+        .map_err(|err| {
+            tracing::debug!("question {} answers: {err}", question.id);
+            ApiError::InvalidState
+        })
+}
+
+/// Questions in review or published keep a complete answer set for their type
+async fn check_reviewed_answers<C: ConnectionTrait>(
+    db: &C,
+    question_id: Uuid,
+) -> Result<(), ApiError> {
+    let question = questions::Entity::find_by_id(question_id)
+        .one(db)
+        .await
+        .map_err(ApiError::from)?
+        .ok_or(ApiError::NotFound)?;
+
+    if matches!(question.status.as_str(), "in_review" | "published") {
+        check_answer_set(db, &question).await?;
+    }
+
+    Ok(())
+}
+
 /// List all answers (requires auth)
 #[utoipa::path(
     get,
@@ -85,6 +166,7 @@ async fn get(
     // Проверяем доступ к topic через question
     let topic_id = get_topic_id_by_question(&ctx.db, answer.question_id).await?;
     check_topic_access_by_id(&ctx.db, auth_user.user.id, topic_id).await?;
+    check_unlocked(&ctx.db, auth_user.user.id, topic_id).await?;
 
     Ok(Json(AnswerResponse::from(answer)).into_response())
 }
@@ -111,15 +193,32 @@ async fn create(
     // Проверяем доступ к topic через question
     let topic_id = get_topic_id_by_question(&ctx.db, params.question_id).await?;
     check_topic_access_by_id(&ctx.db, auth_user.user.id, topic_id).await?;
+    check_payload(&ctx.db, params.question_id, params.payload.as_ref()).await?;
+    if let Some(image_id) = params.image_id {
+        check_images_exist(&ctx.db, &[image_id]).await?;
+    }
+    if let Some(translation_group_id) = params.translation_group_id {
+        check_answer_translation(&ctx.db, params.question_id, translation_group_id, None).await?;
+    }
 
     let answer = answers::ActiveModel {
         question_id: Set(params.question_id),
         value: Set(params.value),
         is_correct: Set(params.is_correct),
+        payload: Set(params.payload.map(|p| p.to_json())),
+        image_id: Set(params.image_id),
+        translation_group_id: params.translation_group_id.map(Set).unwrap_or_default(),
         ..Default::default()
     };
 
-    let answer = answer.insert(&ctx.db).await.map_err(ApiError::from)?;
+    let txn = ctx.db.begin().await.map_err(ApiError::from)?;
+    let answer = answer.insert(&txn).await.map_err(ApiError::from)?;
+    reopen_edited_question(&txn, answer.question_id, &auth_user.user).await?;
+    check_reviewed_answers(&txn, answer.question_id).await?;
+    record_revision(&txn, answer.question_id, Some(auth_user.user.id), "answers")
+        .await
+        .map_err(ApiError::from)?;
+    txn.commit().await.map_err(ApiError::from)?;
 
     Ok((
         axum::http::StatusCode::CREATED,
@@ -159,6 +258,25 @@ async fn update(
     let topic_id = get_topic_id_by_question(&ctx.db, answer.question_id).await?;
     check_topic_access_by_id(&ctx.db, auth_user.user.id, topic_id).await?;
 
+    let previous_question_id = answer.question_id;
+    let payload = params
+        .payload
+        .or_else(|| AnswerPayload::from_json(answer.payload.as_ref()));
+    check_payload(
+        &ctx.db,
+        params.question_id.unwrap_or(answer.question_id),
+        payload.as_ref(),
+    )
+    .await?;
+    if let Some(translation_group_id) = params.translation_group_id {
+        check_answer_translation(
+            &ctx.db,
+            params.question_id.unwrap_or(answer.question_id),
+            translation_group_id,
+            Some(answer.id),
+        )
+        .await?;
+    }
     let mut to_update = answer.into_active_model();
 
     if let Some(question_id) = params.question_id {
@@ -173,8 +291,27 @@ async fn update(
     if let Some(is_correct) = params.is_correct {
         to_update.is_correct = Set(is_correct);
     }
+    to_update.payload = Set(payload.map(|p| p.to_json()));
+    if let Some(image_id) = params.image_id {
+        check_images_exist(&ctx.db, &[image_id]).await?;
+        to_update.image_id = Set(Some(image_id));
+    }
+    if let Some(translation_group_id) = params.translation_group_id {
+        to_update.translation_group_id = Set(translation_group_id);
+    }
+
+    let txn = ctx.db.begin().await.map_err(ApiError::from)?;
+    let answer = to_update.update(&txn).await.map_err(ApiError::from)?;
 
-    let answer = to_update.update(&ctx.db).await.map_err(ApiError::from)?;
+    // Перенос ответа меняет оба вопроса
+    for question_id in [previous_question_id, answer.question_id] {
+        reopen_edited_question(&txn, question_id, &auth_user.user).await?;
+        check_reviewed_answers(&txn, question_id).await?;
+        record_revision(&txn, question_id, Some(auth_user.user.id), "answers")
+            .await
+            .map_err(ApiError::from)?;
+    }
+    txn.commit().await.map_err(ApiError::from)?;
 
     Ok(Json(AnswerResponse::from(answer)).into_response())
 }
@@ -206,10 +343,17 @@ async fn delete(
     let topic_id = get_topic_id_by_question(&ctx.db, answer.question_id).await?;
     check_topic_access_by_id(&ctx.db, auth_user.user.id, topic_id).await?;
 
+    let txn = ctx.db.begin().await.map_err(ApiError::from)?;
     answers::Entity::delete_by_id(id)
-        .exec(&ctx.db)
+        .exec(&txn)
+        .await
+        .map_err(ApiError::from)?;
+    reopen_edited_question(&txn, answer.question_id, &auth_user.user).await?;
+    check_reviewed_answers(&txn, answer.question_id).await?;
+    record_revision(&txn, answer.question_id, Some(auth_user.user.id), "answers")
         .await
         .map_err(ApiError::from)?;
+    txn.commit().await.map_err(ApiError::from)?;
 
     Ok(().into_response())
 }
@@ -234,6 +378,7 @@ async fn get_by_question(
     // Проверяем доступ к topic через question
     let topic_id = get_topic_id_by_question(&ctx.db, question_id).await?;
     check_topic_access_by_id(&ctx.db, auth_user.user.id, topic_id).await?;
+    check_unlocked(&ctx.db, auth_user.user.id, topic_id).await?;
 
     let answers = answers::Entity::find()
         .filter(answers::Column::QuestionId.eq(question_id))
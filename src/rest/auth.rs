@@ -91,7 +91,7 @@ async fn register(
 
     let claims = Claims {
         id: user.id,
-        is_admin: true,
+        is_admin: user.is_editor(),
         computer_id: None,
         room_id: None,
         exp: (chrono::Utc::now() + chrono::Duration::days(1)).timestamp() as usize, // May 2033
@@ -130,7 +130,7 @@ async fn login(
 
     let claims = Claims {
         id: user.id,
-        is_admin: true,
+        is_admin: user.is_editor(),
         computer_id: None,
         room_id: None,
         exp: (chrono::Utc::now() + chrono::Duration::days(1)).timestamp() as usize, // May 2033
@@ -1,24 +1,197 @@
 use crate::{
     AppContext,
-    entities::categories,
-    models::categories::{CategoryResponse, CreateCategoryParams, UpdateCategoryParams},
-    utils::{extractors::AuthUser, response::ApiError},
+    entities::{categories, category_translations},
+    models::categories::{
+        CategoryResponse, CategoryTranslationParams, CategoryTreeResponse, CreateCategoryParams,
+        MoveCategoryParams, UpdateCategoryParams,
+    },
+    utils::{
+        extractors::AuthUser,
+        lang::{ContentLangQuery, PreferredLangs, check_translation_langs},
+        response::ApiError,
+    },
 };
 use axum::{
     Json,
     extract::{Path, State},
     response::{IntoResponse, Response},
 };
-use sea_orm::{ActiveModelTrait, ActiveValue::Set, EntityTrait, IntoActiveModel};
+use sea_orm::{
+    ActiveModelTrait, ActiveValue::Set, ColumnTrait, ConnectionTrait, DbBackend, DbErr,
+    EntityTrait, FromQueryResult, IntoActiveModel, QueryFilter, QueryOrder, Statement,
+    TransactionTrait,
+};
+use std::collections::{HashMap, HashSet};
 use utoipa_axum::{router::OpenApiRouter, routes};
 use uuid::Uuid;
 use validator::Validate;
 
-/// List all categories
+/// A category and all its descendants; UNION stops on a loop in the data
+const CATEGORY_SUBTREE: &str = r#"
+    WITH RECURSIVE subtree AS (
+        SELECT id FROM categories WHERE id = $1
+        UNION
+        SELECT c.id FROM categories c JOIN subtree s ON c.parent_id = s.id
+    )
+    SELECT id FROM subtree"#;
+
+#[derive(Debug, FromQueryResult)]
+struct SubtreeRow {
+    id: Uuid,
+}
+
+/// IDs of a category and every category below it
+pub(crate) async fn category_subtree<C: ConnectionTrait>(
+    db: &C,
+    category_id: Uuid,
+) -> Result<Vec<Uuid>, DbErr> {
+    Ok(
+        SubtreeRow::find_by_statement(Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            CATEGORY_SUBTREE,
+            [category_id.into()],
+        ))
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|row| row.id)
+        .collect(),
+    )
+}
+
+/// Separator of the category names in a path from the root
+pub(crate) const CATEGORY_PATH_SEPARATOR: char = '/';
+
+/// Path of every category from its root, such as `Road signs/Warning`
+pub(crate) fn category_paths(categories_list: &[categories::Model]) -> HashMap<Uuid, String> {
+    let by_id: HashMap<Uuid, &categories::Model> =
+        categories_list.iter().map(|c| (c.id, c)).collect();
+
+    categories_list
+        .iter()
+        .map(|category| {
+            let mut names = vec![category.name.as_str()];
+            let mut visited = HashSet::from([category.id]);
+            let mut parent_id = category.parent_id;
+            while let Some(parent) = parent_id.and_then(|id| by_id.get(&id)) {
+                if !visited.insert(parent.id) {
+                    break;
+                }
+                names.push(parent.name.as_str());
+                parent_id = parent.parent_id;
+            }
+            names.reverse();
+            (
+                category.id,
+                names.join(&CATEGORY_PATH_SEPARATOR.to_string()),
+            )
+        })
+        .collect()
+}
+
+/// The parent must exist and, for an existing category, lie outside its subtree
+async fn check_parent<C: ConnectionTrait>(
+    db: &C,
+    category_id: Option<Uuid>,
+    parent_id: Uuid,
+) -> Result<(), ApiError> {
+    categories::Entity::find_by_id(parent_id)
+        .one(db)
+        .await
+        .map_err(ApiError::from)?
+        .ok_or(ApiError::NotFound)?;
+
+    if let Some(category_id) = category_id {
+        let subtree = category_subtree(db, category_id)
+            .await
+            .map_err(ApiError::from)?;
+        if subtree.contains(&parent_id) {
+            return Err(ApiError::InvalidFieldValue);
+        }
+    }
+
+    Ok(())
+}
+
+/// Nests the categories under their parents, keeping the given order
+fn build_tree(
+    children: &mut HashMap<Option<Uuid>, Vec<CategoryResponse>>,
+    parent_id: Option<Uuid>,
+) -> Vec<CategoryTreeResponse> {
+    children
+        .remove(&parent_id)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|category| {
+            let id = category.id;
+            CategoryTreeResponse {
+                category,
+                children: build_tree(children, Some(id)),
+            }
+        })
+        .collect()
+}
+
+/// Categories with their names in the first available preferred language
+pub(crate) async fn category_responses<C: ConnectionTrait>(
+    db: &C,
+    categories: Vec<categories::Model>,
+    langs: &PreferredLangs,
+) -> Result<Vec<CategoryResponse>, DbErr> {
+    let mut names: HashMap<Uuid, HashMap<String, String>> = HashMap::new();
+    for translation in category_translations::Entity::find()
+        .filter(category_translations::Column::CategoryId.is_in(categories.iter().map(|c| c.id)))
+        .filter(category_translations::Column::Lang.is_in(langs.0.iter().cloned()))
+        .all(db)
+        .await?
+    {
+        names
+            .entry(translation.category_id)
+            .or_default()
+            .insert(translation.lang, translation.name);
+    }
+
+    Ok(categories
+        .into_iter()
+        .map(|category| {
+            let mut response = CategoryResponse::from(category);
+            if let Some((lang, name)) = langs.pick(names.get(&response.id)) {
+                response.lang = Some(lang.to_string());
+                response.name = name.clone();
+            }
+            response
+        })
+        .collect())
+}
+
+/// Adds or replaces the translations of a category
+async fn save_translations<C: ConnectionTrait>(
+    db: &C,
+    category_id: Uuid,
+    translations: Vec<CategoryTranslationParams>,
+) -> Result<(), DbErr> {
+    for translation in translations {
+        let lang = translation.lang.to_lowercase();
+        category_translations::Entity::delete_by_id((category_id, lang.clone()))
+            .exec(db)
+            .await?;
+        category_translations::ActiveModel {
+            category_id: Set(category_id),
+            lang: Set(lang),
+            name: Set(translation.name),
+        }
+        .insert(db)
+        .await?;
+    }
+    Ok(())
+}
+
+/// List all categories in the requested language, siblings in their order
 #[utoipa::path(
     get,
     tag = "Categories",
     path = "/api/categories",
+    params(ContentLangQuery),
     responses(
         (status = 200, body = Vec<CategoryResponse>),
         ApiError
@@ -27,25 +200,69 @@ use validator::Validate;
 )]
 async fn list(
     _auth_user: AuthUser,
+    langs: PreferredLangs,
     State(ctx): State<AppContext>,
 ) -> axum::response::Result<Response> {
     let categories = categories::Entity::find()
+        .order_by_asc(categories::Column::Position)
+        .order_by_asc(categories::Column::Name)
         .all(&ctx.db)
         .await
-        .map_err(ApiError::from)?
-        .into_iter()
-        .map(CategoryResponse::from)
-        .collect::<Vec<_>>();
+        .map_err(ApiError::from)?;
+    let categories = category_responses(&ctx.db, categories, &langs)
+        .await
+        .map_err(ApiError::from)?;
 
     Ok(Json(categories).into_response())
 }
 
-/// Get category by id
+/// Get the category tree in the requested language, children in their order
+#[utoipa::path(
+    get,
+    tag = "Categories",
+    path = "/api/categories/tree",
+    params(ContentLangQuery),
+    responses(
+        (status = 200, body = Vec<CategoryTreeResponse>),
+        ApiError
+    ),
+    security(("jwt_token" = []))
+)]
+async fn tree(
+    _auth_user: AuthUser,
+    langs: PreferredLangs,
+    State(ctx): State<AppContext>,
+) -> axum::response::Result<Response> {
+    let categories = categories::Entity::find()
+        .order_by_asc(categories::Column::Position)
+        .order_by_asc(categories::Column::Name)
+        .all(&ctx.db)
+        .await
+        .map_err(ApiError::from)?;
+    let categories = category_responses(&ctx.db, categories, &langs)
+        .await
+        .map_err(ApiError::from)?;
+
+    let mut children: HashMap<Option<Uuid>, Vec<CategoryResponse>> = HashMap::new();
+    for category in categories {
+        children
+            .entry(category.parent_id)
+            .or_default()
+            .push(category);
+    }
+
+    Ok(Json(build_tree(&mut children, None)).into_response())
+}
+
+/// Get category by id in the requested language
 #[utoipa::path(
     get,
     tag = "Categories",
     path = "/api/categories/{id}",
-    params(("id" = Uuid, Path, description = "Category ID")),
+    params(
+        ("id" = Uuid, Path, description = "Category ID"),
+        ContentLangQuery
+    ),
     responses(
         (status = 200, body = CategoryResponse),
         ApiError
@@ -54,6 +271,7 @@ async fn list(
 )]
 async fn get(
     _auth_user: AuthUser,
+    langs: PreferredLangs,
     Path(id): Path<Uuid>,
     State(ctx): State<AppContext>,
 ) -> axum::response::Result<Response> {
@@ -62,8 +280,12 @@ async fn get(
         .await
         .map_err(ApiError::from)?
         .ok_or(ApiError::NotFound)?;
+    let category = category_responses(&ctx.db, vec![category], &langs)
+        .await
+        .map_err(ApiError::from)?
+        .remove(0);
 
-    Ok(Json(CategoryResponse::from(category)).into_response())
+    Ok(Json(category).into_response())
 }
 
 /// Create category
@@ -84,13 +306,27 @@ async fn create(
     Json(params): Json<CreateCategoryParams>,
 ) -> axum::response::Result<Response> {
     params.validate().map_err(ApiError::from)?;
+    check_translation_langs(params.translations.iter().map(|t| t.lang.as_str()))?;
+
+    let txn = ctx.db.begin().await.map_err(ApiError::from)?;
+
+    if let Some(parent_id) = params.parent_id {
+        check_parent(&txn, None, parent_id).await?;
+    }
 
     let category = categories::ActiveModel {
         name: Set(params.name),
+        parent_id: Set(params.parent_id),
+        position: Set(params.position),
         ..Default::default()
     };
 
-    let category = category.insert(&ctx.db).await.map_err(ApiError::from)?;
+    let category = category.insert(&txn).await.map_err(ApiError::from)?;
+    save_translations(&txn, category.id, params.translations)
+        .await
+        .map_err(ApiError::from)?;
+
+    txn.commit().await.map_err(ApiError::from)?;
 
     Ok((
         axum::http::StatusCode::CREATED,
@@ -119,9 +355,14 @@ async fn update(
     Json(params): Json<UpdateCategoryParams>,
 ) -> axum::response::Result<Response> {
     params.validate().map_err(ApiError::from)?;
+    if let Some(translations) = &params.translations {
+        check_translation_langs(translations.iter().map(|t| t.lang.as_str()))?;
+    }
+
+    let txn = ctx.db.begin().await.map_err(ApiError::from)?;
 
     let category = categories::Entity::find_by_id(id)
-        .one(&ctx.db)
+        .one(&txn)
         .await
         .map_err(ApiError::from)?
         .ok_or(ApiError::NotFound)?;
@@ -131,13 +372,67 @@ async fn update(
     if let Some(name) = params.name {
         to_update.name = Set(name);
     }
+    if let Some(position) = params.position {
+        to_update.position = Set(position);
+    }
+
+    let category = to_update.update(&txn).await.map_err(ApiError::from)?;
+    if let Some(translations) = params.translations {
+        save_translations(&txn, category.id, translations)
+            .await
+            .map_err(ApiError::from)?;
+    }
+
+    txn.commit().await.map_err(ApiError::from)?;
+
+    Ok(Json(CategoryResponse::from(category)).into_response())
+}
+
+/// Move a category with its subtree under another parent or to the root
+#[utoipa::path(
+    post,
+    tag = "Categories",
+    path = "/api/categories/{id}/move",
+    params(("id" = Uuid, Path, description = "Category ID")),
+    request_body = MoveCategoryParams,
+    responses(
+        (status = 200, body = CategoryResponse),
+        ApiError
+    ),
+    security(("jwt_token" = []))
+)]
+async fn move_category(
+    _auth_user: AuthUser,
+    Path(id): Path<Uuid>,
+    State(ctx): State<AppContext>,
+    Json(params): Json<MoveCategoryParams>,
+) -> axum::response::Result<Response> {
+    let txn = ctx.db.begin().await.map_err(ApiError::from)?;
+
+    let category = categories::Entity::find_by_id(id)
+        .one(&txn)
+        .await
+        .map_err(ApiError::from)?
+        .ok_or(ApiError::NotFound)?;
+
+    if let Some(parent_id) = params.parent_id {
+        check_parent(&txn, Some(id), parent_id).await?;
+    }
+
+    let mut to_update = category.into_active_model();
+    to_update.parent_id = Set(params.parent_id);
+    if let Some(position) = params.position {
+        to_update.position = Set(position);
+    }
+
+    let category = to_update.update(&txn).await.map_err(ApiError::from)?;
 
-    let category = to_update.update(&ctx.db).await.map_err(ApiError::from)?;
+    txn.commit().await.map_err(ApiError::from)?;
 
     Ok(Json(CategoryResponse::from(category)).into_response())
 }
 
-/// Delete category by id
+/// Delete category by id, its children become roots
 #[utoipa::path(
     delete,
     tag = "Categories",
@@ -165,8 +460,10 @@ async fn delete(
 pub fn routes() -> OpenApiRouter<AppContext> {
     OpenApiRouter::new()
         .routes(routes!(list))
+        .routes(routes!(tree))
         .routes(routes!(get))
         .routes(routes!(create))
         .routes(routes!(update))
+        .routes(routes!(move_category))
         .routes(routes!(delete))
 }
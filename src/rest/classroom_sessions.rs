@@ -0,0 +1,586 @@
+use crate::{
+    AppContext,
+    entities::{
+        answers, classroom_participants, classroom_session_options, classroom_session_questions,
+        classroom_sessions, questions, test_question_options, test_questions,
+        test_template_questions, test_templates, tests,
+    },
+    live::{
+        classrooms::{ClassroomRoom, leaderboard, question_stats, session_questions},
+        pump_socket,
+    },
+    models::classroom_sessions::{
+        ClassroomCommand, ClassroomEvent, ClassroomParticipantResult, ClassroomQuestionReport,
+        ClassroomReportResponse, ClassroomSessionResponse, ClassroomSocketQuery,
+        CreateClassroomSessionParams, JoinClassroomSessionParams, JoinedClassroomSessionResponse,
+    },
+    rest::{
+        questions::question_image_ids, test_templates::validate_questions,
+        tests::generate_filter_hash,
+    },
+    utils::{
+        extractors::{AdminUser, AuthUser},
+        jwt::Claims,
+        response::ApiError,
+    },
+};
+use axum::{
+    Json,
+    extract::{
+        Path, Query, State,
+        ws::{WebSocket, WebSocketUpgrade},
+    },
+    response::{IntoResponse, Response},
+};
+use rand::Rng;
+use sea_orm::{
+    ActiveModelTrait, ActiveValue::Set, ColumnTrait, ConnectionTrait, EntityTrait, QueryFilter,
+    QueryOrder, TransactionTrait,
+};
+use std::{collections::HashMap, sync::Arc};
+use utoipa_axum::{router::OpenApiRouter, routes};
+use uuid::Uuid;
+use validator::Validate;
+
+/// Six digit PIN, unique among sessions that are not finished
+async fn generate_pin<C: ConnectionTrait>(db: &C) -> Result<String, ApiError> {
+    loop {
+        let pin = format!("{:06}", rand::thread_rng().gen_range(0..1_000_000));
+        let taken = classroom_sessions::Entity::find()
+            .filter(classroom_sessions::Column::Pin.eq(&pin))
+            .filter(classroom_sessions::Column::Status.ne("finished"))
+            .filter(classroom_sessions::Column::IsDeleted.eq(false))
+            .one(db)
+            .await
+            .map_err(ApiError::from)?;
+        if taken.is_none() {
+            return Ok(pin);
+        }
+    }
+}
+
+/// Session hosted by the admin
+async fn find_hosted_session<C: ConnectionTrait>(
+    db: &C,
+    id: Uuid,
+    host_id: Uuid,
+) -> Result<classroom_sessions::Model, ApiError> {
+    let session = classroom_sessions::Entity::find_by_id(id)
+        .filter(classroom_sessions::Column::IsDeleted.eq(false))
+        .one(db)
+        .await
+        .map_err(ApiError::from)?
+        .ok_or(ApiError::NotFound)?;
+
+    if session.host_id != host_id {
+        return Err(ApiError::Forbidden);
+    }
+
+    Ok(session)
+}
+
+/// Snapshots the questions with their answers in the given order, the
+/// session reads only from this snapshot
+async fn insert_session_questions<C: ConnectionTrait>(
+    db: &C,
+    session_id: Uuid,
+    question_ids: &[Uuid],
+) -> Result<(), ApiError> {
+    let mut contents: HashMap<Uuid, questions::Model> = questions::Entity::find()
+        .filter(questions::Column::Id.is_in(question_ids.to_vec()))
+        .all(db)
+        .await
+        .map_err(ApiError::from)?
+        .into_iter()
+        .map(|q| (q.id, q))
+        .collect();
+
+    let mut image_ids = question_image_ids(db, question_ids)
+        .await
+        .map_err(ApiError::from)?;
+
+    let mut options: HashMap<Uuid, Vec<answers::Model>> = HashMap::new();
+    for answer in answers::Entity::find()
+        .filter(answers::Column::QuestionId.is_in(question_ids.to_vec()))
+        .order_by_asc(answers::Column::Id)
+        .all(db)
+        .await
+        .map_err(ApiError::from)?
+    {
+        options.entry(answer.question_id).or_default().push(answer);
+    }
+
+    for (order, question_id) in question_ids.iter().enumerate() {
+        let question = contents.remove(question_id).ok_or(ApiError::NotFound)?;
+        let question_image_ids = image_ids.remove(question_id).unwrap_or_default();
+
+        let link = classroom_session_questions::ActiveModel {
+            session_id: Set(session_id),
+            question_id: Set(*question_id),
+            question_order: Set((order + 1) as i16),
+            points: Set(question.points),
+            question_name: Set(question.name),
+            question_content: Set(question.content),
+            explanation: Set(question.explanation),
+            question_type: Set(question.question_type),
+            image_ids: Set(Some(serde_json::json!(question_image_ids))),
+        };
+        link.insert(db).await.map_err(ApiError::from)?;
+
+        for (option_order, answer) in options
+            .remove(question_id)
+            .unwrap_or_default()
+            .into_iter()
+            .enumerate()
+        {
+            let option = classroom_session_options::ActiveModel {
+                session_id: Set(session_id),
+                question_id: Set(*question_id),
+                answer_id: Set(answer.id),
+                value: Set(answer.value),
+                is_correct: Set(answer.is_correct),
+                option_order: Set((option_order + 1) as i16),
+                payload: Set(answer.payload),
+                image_id: Set(answer.image_id),
+            };
+            option.insert(db).await.map_err(ApiError::from)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Fills a participant's test from the session snapshot, so every student
+/// answers the same content the host shows
+async fn copy_session_questions<C: ConnectionTrait>(
+    db: &C,
+    session_id: Uuid,
+    test_id: Uuid,
+    links: Vec<classroom_session_questions::Model>,
+) -> Result<(), ApiError> {
+    for link in links {
+        let tq = test_questions::ActiveModel {
+            test_id: Set(test_id),
+            question_id: Set(link.question_id),
+            question_order: Set(link.question_order),
+            is_correct: Set(None),
+            answered_at: Set(None),
+            points: Set(link.points),
+            earned_points: Set(None),
+            question_name: Set(link.question_name),
+            question_content: Set(link.question_content),
+            explanation: Set(link.explanation),
+            question_type: Set(link.question_type),
+            response: Set(None),
+            image_ids: Set(link.image_ids),
+        };
+        tq.insert(db).await.map_err(ApiError::from)?;
+    }
+
+    for option in classroom_session_options::Entity::find()
+        .filter(classroom_session_options::Column::SessionId.eq(session_id))
+        .all(db)
+        .await
+        .map_err(ApiError::from)?
+    {
+        let option = test_question_options::ActiveModel {
+            test_id: Set(test_id),
+            question_id: Set(option.question_id),
+            answer_id: Set(option.answer_id),
+            value: Set(option.value),
+            is_correct: Set(option.is_correct),
+            option_order: Set(option.option_order),
+            payload: Set(option.payload),
+            image_id: Set(option.image_id),
+        };
+        option.insert(db).await.map_err(ApiError::from)?;
+    }
+
+    Ok(())
+}
+
+async fn build_response<C: ConnectionTrait>(
+    db: &C,
+    session: classroom_sessions::Model,
+) -> Result<ClassroomSessionResponse, ApiError> {
+    let questions_count = classroom_session_questions::Entity::find()
+        .filter(classroom_session_questions::Column::SessionId.eq(session.id))
+        .all(db)
+        .await
+        .map_err(ApiError::from)?
+        .len() as i16;
+
+    Ok(ClassroomSessionResponse::from_model(
+        session,
+        questions_count,
+    ))
+}
+
+/// List sessions hosted by the instructor
+#[utoipa::path(
+    get,
+    tag = "Classroom Sessions",
+    path = "/api/classroom_sessions",
+    responses(
+        (status = 200, body = Vec<ClassroomSessionResponse>),
+        ApiError
+    ),
+    security(("jwt_token" = []))
+)]
+async fn list(admin: AdminUser, State(ctx): State<AppContext>) -> axum::response::Result<Response> {
+    let sessions = classroom_sessions::Entity::find()
+        .filter(classroom_sessions::Column::HostId.eq(admin.user.id))
+        .filter(classroom_sessions::Column::IsDeleted.eq(false))
+        .order_by_desc(classroom_sessions::Column::CreatedAt)
+        .all(&ctx.db)
+        .await
+        .map_err(ApiError::from)?;
+
+    let mut responses = Vec::new();
+    for session in sessions {
+        responses.push(build_response(&ctx.db, session).await?);
+    }
+
+    Ok(Json(responses).into_response())
+}
+
+/// Get hosted session by id
+#[utoipa::path(
+    get,
+    tag = "Classroom Sessions",
+    path = "/api/classroom_sessions/{id}",
+    params(("id" = Uuid, Path, description = "Session ID")),
+    responses(
+        (status = 200, body = ClassroomSessionResponse),
+        ApiError
+    ),
+    security(("jwt_token" = []))
+)]
+async fn get(
+    admin: AdminUser,
+    Path(id): Path<Uuid>,
+    State(ctx): State<AppContext>,
+) -> axum::response::Result<Response> {
+    let session = find_hosted_session(&ctx.db, id, admin.user.id).await?;
+
+    Ok(Json(build_response(&ctx.db, session).await?).into_response())
+}
+
+/// Open a classroom session (instructor)
+#[utoipa::path(
+    post,
+    tag = "Classroom Sessions",
+    path = "/api/classroom_sessions",
+    request_body = CreateClassroomSessionParams,
+    responses(
+        (status = 201, body = ClassroomSessionResponse),
+        ApiError
+    ),
+    security(("jwt_token" = []))
+)]
+async fn create(
+    admin: AdminUser,
+    State(ctx): State<AppContext>,
+    Json(params): Json<CreateClassroomSessionParams>,
+) -> axum::response::Result<Response> {
+    params.validate().map_err(ApiError::from)?;
+
+    let txn = ctx.db.begin().await.map_err(ApiError::from)?;
+
+    let question_ids = match (params.template_id, params.question_ids) {
+        (Some(template_id), None) => {
+            let template = test_templates::Entity::find_by_id(template_id)
+                .filter(test_templates::Column::IsDeleted.eq(false))
+                .one(&txn)
+                .await
+                .map_err(ApiError::from)?
+                .ok_or(ApiError::NotFound)?;
+
+            if template.lang != params.lang {
+                return Err(ApiError::InvalidFieldValue.into());
+            }
+
+            test_template_questions::Entity::find()
+                .filter(test_template_questions::Column::TemplateId.eq(template.id))
+                .order_by_asc(test_template_questions::Column::QuestionOrder)
+                .all(&txn)
+                .await
+                .map_err(ApiError::from)?
+                .into_iter()
+                .map(|tq| tq.question_id)
+                .collect::<Vec<Uuid>>()
+        }
+        (None, Some(question_ids)) => {
+            validate_questions(&txn, &question_ids, &params.lang, None).await?;
+            question_ids
+        }
+        (Some(_), Some(_)) => return Err(ApiError::InvalidInput.into()),
+        (None, None) => return Err(ApiError::MissingField.into()),
+    };
+
+    if question_ids.is_empty() {
+        return Err(ApiError::InvalidState.into());
+    }
+
+    let session = classroom_sessions::ActiveModel {
+        host_id: Set(admin.user.id),
+        title: Set(params.title),
+        lang: Set(params.lang),
+        pin: Set(generate_pin(&txn).await?),
+        status: Set("lobby".to_string()),
+        current_question_order: Set(0),
+        ..Default::default()
+    };
+    let session = session.insert(&txn).await.map_err(ApiError::from)?;
+
+    insert_session_questions(&txn, session.id, &question_ids).await?;
+
+    txn.commit().await.map_err(ApiError::from)?;
+
+    Ok((
+        axum::http::StatusCode::CREATED,
+        Json(build_response(&ctx.db, session).await?),
+    )
+        .into_response())
+}
+
+/// Join a session by PIN (student)
+#[utoipa::path(
+    post,
+    tag = "Classroom Sessions",
+    path = "/api/classroom_sessions/join",
+    request_body = JoinClassroomSessionParams,
+    responses(
+        (status = 200, body = JoinedClassroomSessionResponse),
+        ApiError
+    ),
+    security(("jwt_token" = []))
+)]
+async fn join(
+    auth_user: AuthUser,
+    State(ctx): State<AppContext>,
+    Json(params): Json<JoinClassroomSessionParams>,
+) -> axum::response::Result<Response> {
+    params.validate().map_err(ApiError::from)?;
+
+    let txn = ctx.db.begin().await.map_err(ApiError::from)?;
+
+    let session = classroom_sessions::Entity::find()
+        .filter(classroom_sessions::Column::Pin.eq(params.pin.trim()))
+        .filter(classroom_sessions::Column::Status.ne("finished"))
+        .filter(classroom_sessions::Column::IsDeleted.eq(false))
+        .one(&txn)
+        .await
+        .map_err(ApiError::from)?
+        .ok_or(ApiError::NotFound)?;
+
+    if session.host_id == auth_user.user.id {
+        return Err(ApiError::InvalidState.into());
+    }
+
+    // Повторный вход возвращает уже созданный тест
+    let existing = classroom_participants::Entity::find_by_id((session.id, auth_user.user.id))
+        .one(&txn)
+        .await
+        .map_err(ApiError::from)?;
+
+    let test_id = match existing {
+        Some(participant) => participant.test_id,
+        None => {
+            let links = session_questions(&ctx.db, session.id)
+                .await
+                .map_err(ApiError::from)?;
+
+            let test = tests::ActiveModel {
+                user_id: Set(auth_user.user.id),
+                filter_type: Set("classroom".to_string()),
+                filter_id: Set(Some(session.id)),
+                lang: Set(session.lang.clone()),
+                filter_hash: Set(generate_filter_hash(
+                    "classroom",
+                    Some(session.id),
+                    &session.lang,
+                )),
+                total_questions: Set(links.len() as i16),
+                correct_count: Set(0),
+                status: Set("active".to_string()),
+                max_points: Set(links.iter().map(|l| l.points).sum()),
+                ..Default::default()
+            };
+            let test = test.insert(&txn).await.map_err(ApiError::from)?;
+            copy_session_questions(&txn, session.id, test.id, links).await?;
+
+            let participant = classroom_participants::ActiveModel {
+                session_id: Set(session.id),
+                user_id: Set(auth_user.user.id),
+                test_id: Set(Some(test.id)),
+                ..Default::default()
+            };
+            participant.insert(&txn).await.map_err(ApiError::from)?;
+
+            Some(test.id)
+        }
+    };
+
+    txn.commit().await.map_err(ApiError::from)?;
+
+    Ok(Json(JoinedClassroomSessionResponse {
+        session_id: session.id,
+        title: session.title,
+        status: session.status,
+        test_id,
+    })
+    .into_response())
+}
+
+/// Session report with per-student results and per-question answer distribution
+#[utoipa::path(
+    get,
+    tag = "Classroom Sessions",
+    path = "/api/classroom_sessions/{id}/report",
+    params(("id" = Uuid, Path, description = "Session ID")),
+    responses(
+        (status = 200, body = ClassroomReportResponse),
+        ApiError
+    ),
+    security(("jwt_token" = []))
+)]
+async fn report(
+    admin: AdminUser,
+    Path(id): Path<Uuid>,
+    State(ctx): State<AppContext>,
+) -> axum::response::Result<Response> {
+    let session = find_hosted_session(&ctx.db, id, admin.user.id).await?;
+
+    let rows = leaderboard(&ctx.db, session.id)
+        .await
+        .map_err(ApiError::from)?;
+    let test_ids: Vec<Uuid> = rows
+        .iter()
+        .filter_map(|(_, test)| test.as_ref().map(|t| t.id))
+        .collect();
+
+    let answered_counts: HashMap<Uuid, i16> = test_questions::Entity::find()
+        .filter(test_questions::Column::TestId.is_in(test_ids.clone()))
+        .filter(test_questions::Column::AnsweredAt.is_not_null())
+        .all(&ctx.db)
+        .await
+        .map_err(ApiError::from)?
+        .into_iter()
+        .fold(HashMap::new(), |mut counts, tq| {
+            *counts.entry(tq.test_id).or_default() += 1;
+            counts
+        });
+
+    let participants = rows
+        .into_iter()
+        .map(|(entry, test)| ClassroomParticipantResult {
+            user_id: entry.user_id,
+            username: entry.username,
+            test_id: test.as_ref().map(|t| t.id),
+            answered_count: test
+                .as_ref()
+                .and_then(|t| answered_counts.get(&t.id).copied())
+                .unwrap_or(0),
+            correct_count: entry.correct_count,
+            earned_points: entry.earned_points,
+            score_percent: test.and_then(|t| t.score_percent),
+        })
+        .collect();
+
+    let mut questions_report = Vec::new();
+    for link in session_questions(&ctx.db, session.id)
+        .await
+        .map_err(ApiError::from)?
+    {
+        let (distribution, answered_count, correct_count) =
+            question_stats(&ctx.db, &test_ids, link.question_id)
+                .await
+                .map_err(ApiError::from)?;
+        questions_report.push(ClassroomQuestionReport {
+            question_id: link.question_id,
+            question_order: link.question_order,
+            answered_count,
+            correct_count,
+            distribution,
+        });
+    }
+
+    Ok(Json(ClassroomReportResponse {
+        session: build_response(&ctx.db, session).await?,
+        participants,
+        questions: questions_report,
+    })
+    .into_response())
+}
+
+/// Classroom WebSocket for the host and joined participants.
+/// The host sends `next`, `close_question` and `finish`, participants send `answer`.
+#[utoipa::path(
+    get,
+    tag = "Classroom Sessions",
+    path = "/api/classroom_sessions/{id}/ws",
+    params(
+        ("id" = Uuid, Path, description = "Session ID"),
+        ClassroomSocketQuery
+    ),
+    responses(
+        (status = 101, description = "Switching to the WebSocket protocol"),
+        ApiError
+    )
+)]
+async fn socket(
+    Path(id): Path<Uuid>,
+    Query(query): Query<ClassroomSocketQuery>,
+    State(ctx): State<AppContext>,
+    ws: WebSocketUpgrade,
+) -> axum::response::Result<Response> {
+    let claims = Claims::from_token(&query.token)?;
+
+    let session = classroom_sessions::Entity::find_by_id(id)
+        .filter(classroom_sessions::Column::IsDeleted.eq(false))
+        .one(&ctx.db)
+        .await
+        .map_err(ApiError::from)?
+        .ok_or(ApiError::NotFound)?;
+
+    if session.status == "finished" {
+        return Err(ApiError::InvalidState.into());
+    }
+
+    if session.host_id != claims.id {
+        classroom_participants::Entity::find_by_id((session.id, claims.id))
+            .one(&ctx.db)
+            .await
+            .map_err(ApiError::from)?
+            .ok_or(ApiError::Forbidden)?;
+    }
+
+    let room = ctx.classrooms.room(&ctx.db, &session);
+
+    Ok(ws.on_upgrade(move |socket| handle_socket(socket, room, claims.id)))
+}
+
+async fn handle_socket(mut socket: WebSocket, room: Arc<ClassroomRoom>, user_id: Uuid) {
+    let (connection_id, mut events) = room.connect(user_id).await;
+
+    pump_socket(
+        &mut socket,
+        &mut events,
+        |command: ClassroomCommand| room.submit(user_id, command),
+        |message| ClassroomEvent::Error { message },
+    )
+    .await;
+
+    room.disconnect(user_id, connection_id).await;
+}
+
+pub fn routes() -> OpenApiRouter<AppContext> {
+    OpenApiRouter::new()
+        .routes(routes!(list))
+        .routes(routes!(get))
+        .routes(routes!(create))
+        .routes(routes!(join))
+        .routes(routes!(report))
+        .routes(routes!(socket))
+}
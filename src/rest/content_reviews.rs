@@ -0,0 +1,517 @@
+use crate::{
+    AppContext,
+    entities::{content_reviews, lessons, questions, users},
+    models::{
+        content_reviews::{
+            ContentReviewResponse, ExpiringQuery, ReviewDecisionParams, ReviewQueueQuery,
+        },
+        lessons::LessonResponse,
+        questions::QuestionResponse,
+    },
+    rest::{
+        answers::check_answer_set,
+        lessons::lesson_responses,
+        questions::{question_response, question_responses},
+    },
+    utils::{
+        extractors::{AdminUser, AuthUser, check_topic_access_by_id},
+        lang::PreferredLangs,
+        response::ApiError,
+    },
+};
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+    response::{IntoResponse, Response},
+};
+use sea_orm::{
+    ActiveModelTrait, ActiveValue::Set, ColumnTrait, ConnectionTrait, DbErr, EntityTrait,
+    IntoActiveModel, QueryFilter, QueryOrder, TransactionTrait,
+};
+use utoipa_axum::{router::OpenApiRouter, routes};
+use uuid::Uuid;
+use validator::Validate;
+
+/// Workflow statuses of questions and lessons
+pub(crate) const STATUSES: [&str; 4] = ["draft", "in_review", "published", "archived"];
+
+/// Status after a workflow action, `None` when the action is not allowed
+fn next_status(action: &str, status: &str) -> Option<&'static str> {
+    match (action, status) {
+        ("submit", "draft" | "archived") => Some("in_review"),
+        ("approve", "in_review") => Some("published"),
+        ("reject", "in_review") => Some("draft"),
+        ("archive", "draft" | "in_review" | "published") => Some("archived"),
+        _ => None,
+    }
+}
+
+/// Validates the reviewer decision, a rejection must explain itself
+fn decision_action(params: &ReviewDecisionParams) -> Result<&'static str, ApiError> {
+    match params.decision.as_str() {
+        "approve" => Ok("approve"),
+        "reject" if params.comment.is_none() => Err(ApiError::MissingField),
+        "reject" => Ok("reject"),
+        "archive" => Ok("archive"),
+        _ => Err(ApiError::InvalidFieldValue),
+    }
+}
+
+fn queue_status(query: &ReviewQueueQuery) -> Result<String, ApiError> {
+    let status = query.status.as_deref().unwrap_or("in_review");
+    if !STATUSES.contains(&status) {
+        return Err(ApiError::InvalidFieldValue);
+    }
+    Ok(status.to_string())
+}
+
+/// Adds a step to the review history of a question or lesson
+pub(crate) async fn log_review<C: ConnectionTrait>(
+    db: &C,
+    content_type: &str,
+    content_id: Uuid,
+    user_id: Option<Uuid>,
+    action: &str,
+    status: &str,
+    comment: Option<String>,
+) -> Result<(), DbErr> {
+    let review = content_reviews::ActiveModel {
+        content_type: Set(content_type.to_string()),
+        content_id: Set(content_id),
+        user_id: Set(user_id),
+        action: Set(action.to_string()),
+        status: Set(status.to_string()),
+        comment: Set(comment),
+        ..Default::default()
+    };
+    review.insert(db).await?;
+
+    Ok(())
+}
+
+/// Sends a published question edited by a non-editor back to review
+pub(crate) async fn reopen_edited_question<C: ConnectionTrait>(
+    db: &C,
+    question_id: Uuid,
+    user: &users::Model,
+) -> Result<(), ApiError> {
+    if user.is_editor() {
+        return Ok(());
+    }
+
+    let question = questions::Entity::find_by_id(question_id)
+        .one(db)
+        .await
+        .map_err(ApiError::from)?
+        .ok_or(ApiError::NotFound)?;
+    if question.status != "published" {
+        return Ok(());
+    }
+
+    let mut to_update = question.into_active_model();
+    to_update.status = Set("in_review".to_string());
+    to_update.update(db).await.map_err(ApiError::from)?;
+
+    log_review(
+        db,
+        "question",
+        question_id,
+        Some(user.id),
+        "edit",
+        "in_review",
+        None,
+    )
+    .await
+    .map_err(ApiError::from)
+}
+
+async fn review_log(
+    ctx: &AppContext,
+    content_type: &str,
+    content_id: Uuid,
+) -> Result<Vec<ContentReviewResponse>, ApiError> {
+    Ok(content_reviews::Entity::find()
+        .filter(content_reviews::Column::ContentType.eq(content_type))
+        .filter(content_reviews::Column::ContentId.eq(content_id))
+        .order_by_asc(content_reviews::Column::CreatedAt)
+        .all(&ctx.db)
+        .await
+        .map_err(ApiError::from)?
+        .into_iter()
+        .map(ContentReviewResponse::from)
+        .collect())
+}
+
+/// Moves a question through the workflow and logs the step
+async fn transition_question(
+    ctx: &AppContext,
+    question: questions::Model,
+    user_id: Uuid,
+    action: &str,
+    comment: Option<String>,
+) -> Result<questions::Model, ApiError> {
+    let status = next_status(action, &question.status).ok_or(ApiError::InvalidState)?;
+
+    // На ревью уходят только вопросы с ответами, подходящими к их типу
+    if action == "submit" {
+        check_answer_set(&ctx.db, &question).await?;
+    }
+
+    let txn = ctx.db.begin().await.map_err(ApiError::from)?;
+
+    let mut to_update = question.into_active_model();
+    to_update.status = Set(status.to_string());
+    // Комментарий ревьюера остаётся видимым до следующего решения
+    if action != "submit" {
+        to_update.review_comment = Set(comment.clone());
+    }
+    let question = to_update.update(&txn).await.map_err(ApiError::from)?;
+
+    log_review(
+        &txn,
+        "question",
+        question.id,
+        Some(user_id),
+        action,
+        status,
+        comment,
+    )
+    .await
+    .map_err(ApiError::from)?;
+    txn.commit().await.map_err(ApiError::from)?;
+
+    Ok(question)
+}
+
+/// Moves a lesson through the workflow and logs the step
+async fn transition_lesson(
+    ctx: &AppContext,
+    lesson: lessons::Model,
+    user_id: Uuid,
+    action: &str,
+    comment: Option<String>,
+) -> Result<lessons::Model, ApiError> {
+    let status = next_status(action, &lesson.status).ok_or(ApiError::InvalidState)?;
+    let txn = ctx.db.begin().await.map_err(ApiError::from)?;
+
+    let mut to_update = lesson.into_active_model();
+    to_update.status = Set(status.to_string());
+    if action != "submit" {
+        to_update.review_comment = Set(comment.clone());
+    }
+    let lesson = to_update.update(&txn).await.map_err(ApiError::from)?;
+
+    log_review(
+        &txn,
+        "lesson",
+        lesson.id,
+        Some(user_id),
+        action,
+        status,
+        comment,
+    )
+    .await
+    .map_err(ApiError::from)?;
+    txn.commit().await.map_err(ApiError::from)?;
+
+    Ok(lesson)
+}
+
+async fn find_question(ctx: &AppContext, id: Uuid) -> Result<questions::Model, ApiError> {
+    questions::Entity::find_by_id(id)
+        .one(&ctx.db)
+        .await
+        .map_err(ApiError::from)?
+        .ok_or(ApiError::NotFound)
+}
+
+async fn find_lesson(ctx: &AppContext, id: Uuid) -> Result<lessons::Model, ApiError> {
+    lessons::Entity::find_by_id(id)
+        .one(&ctx.db)
+        .await
+        .map_err(ApiError::from)?
+        .ok_or(ApiError::NotFound)
+}
+
+/// Questions in a workflow status (reviewers only)
+#[utoipa::path(
+    get,
+    tag = "Content Review",
+    path = "/api/review/questions",
+    params(ReviewQueueQuery),
+    responses(
+        (status = 200, body = Vec<QuestionResponse>),
+        ApiError
+    ),
+    security(("jwt_token" = []))
+)]
+async fn question_queue(
+    _admin: AdminUser,
+    State(ctx): State<AppContext>,
+    Query(query): Query<ReviewQueueQuery>,
+) -> axum::response::Result<Response> {
+    let status = queue_status(&query)?;
+
+    let questions_list = questions::Entity::find()
+        .filter(questions::Column::Status.eq(status))
+        .order_by_asc(questions::Column::Id)
+        .all(&ctx.db)
+        .await
+        .map_err(ApiError::from)?;
+    let questions_list = question_responses(&ctx.db, questions_list)
+        .await
+        .map_err(ApiError::from)?;
+
+    Ok(Json(questions_list).into_response())
+}
+
+/// Published questions whose validity ends within the window (reviewers only)
+#[utoipa::path(
+    get,
+    tag = "Content Review",
+    path = "/api/review/questions/expiring",
+    params(ExpiringQuery),
+    responses(
+        (status = 200, body = Vec<QuestionResponse>),
+        ApiError
+    ),
+    security(("jwt_token" = []))
+)]
+async fn expiring_questions(
+    _admin: AdminUser,
+    State(ctx): State<AppContext>,
+    Query(query): Query<ExpiringQuery>,
+) -> axum::response::Result<Response> {
+    query.validate().map_err(ApiError::from)?;
+
+    let today = chrono::Utc::now().date_naive();
+    let until = today + chrono::Days::new(query.days.unwrap_or(30) as u64);
+
+    let questions_list = questions::Entity::find()
+        .filter(questions::Column::Status.eq("published"))
+        .filter(questions::Column::ValidTo.between(today, until))
+        .order_by_asc(questions::Column::ValidTo)
+        .order_by_asc(questions::Column::Id)
+        .all(&ctx.db)
+        .await
+        .map_err(ApiError::from)?;
+    let questions_list = question_responses(&ctx.db, questions_list)
+        .await
+        .map_err(ApiError::from)?;
+
+    Ok(Json(questions_list).into_response())
+}
+
+/// Submit a draft question for review
+#[utoipa::path(
+    post,
+    tag = "Content Review",
+    path = "/api/questions/{id}/submit",
+    params(("id" = Uuid, Path, description = "Question ID")),
+    responses(
+        (status = 200, body = QuestionResponse),
+        ApiError
+    ),
+    security(("jwt_token" = []))
+)]
+async fn submit_question(
+    auth_user: AuthUser,
+    Path(id): Path<Uuid>,
+    State(ctx): State<AppContext>,
+) -> axum::response::Result<Response> {
+    let question = find_question(&ctx, id).await?;
+    check_topic_access_by_id(&ctx.db, auth_user.user.id, question.topic_id).await?;
+
+    let question = transition_question(&ctx, question, auth_user.user.id, "submit", None).await?;
+
+    let response = question_response(&ctx.db, question)
+        .await
+        .map_err(ApiError::from)?;
+    Ok(Json(response).into_response())
+}
+
+/// Approve, reject or archive a question (reviewers only)
+#[utoipa::path(
+    post,
+    tag = "Content Review",
+    path = "/api/questions/{id}/review",
+    params(("id" = Uuid, Path, description = "Question ID")),
+    request_body = ReviewDecisionParams,
+    responses(
+        (status = 200, body = QuestionResponse),
+        ApiError
+    ),
+    security(("jwt_token" = []))
+)]
+async fn review_question(
+    admin: AdminUser,
+    Path(id): Path<Uuid>,
+    State(ctx): State<AppContext>,
+    Json(params): Json<ReviewDecisionParams>,
+) -> axum::response::Result<Response> {
+    params.validate().map_err(ApiError::from)?;
+    let action = decision_action(&params)?;
+
+    let question = find_question(&ctx, id).await?;
+    let question =
+        transition_question(&ctx, question, admin.user.id, action, params.comment).await?;
+
+    let response = question_response(&ctx.db, question)
+        .await
+        .map_err(ApiError::from)?;
+    Ok(Json(response).into_response())
+}
+
+/// Workflow history of a question
+#[utoipa::path(
+    get,
+    tag = "Content Review",
+    path = "/api/questions/{id}/reviews",
+    params(("id" = Uuid, Path, description = "Question ID")),
+    responses(
+        (status = 200, body = Vec<ContentReviewResponse>),
+        ApiError
+    ),
+    security(("jwt_token" = []))
+)]
+async fn question_reviews(
+    auth_user: AuthUser,
+    Path(id): Path<Uuid>,
+    State(ctx): State<AppContext>,
+) -> axum::response::Result<Response> {
+    let question = find_question(&ctx, id).await?;
+    check_topic_access_by_id(&ctx.db, auth_user.user.id, question.topic_id).await?;
+
+    Ok(Json(review_log(&ctx, "question", id).await?).into_response())
+}
+
+/// Lessons in a workflow status (reviewers only)
+#[utoipa::path(
+    get,
+    tag = "Content Review",
+    path = "/api/review/lessons",
+    params(ReviewQueueQuery),
+    responses(
+        (status = 200, body = Vec<LessonResponse>),
+        ApiError
+    ),
+    security(("jwt_token" = []))
+)]
+async fn lesson_queue(
+    _admin: AdminUser,
+    State(ctx): State<AppContext>,
+    Query(query): Query<ReviewQueueQuery>,
+) -> axum::response::Result<Response> {
+    let status = queue_status(&query)?;
+
+    let lessons_list = lessons::Entity::find()
+        .filter(lessons::Column::Status.eq(status))
+        .order_by_asc(lessons::Column::Id)
+        .all(&ctx.db)
+        .await
+        .map_err(ApiError::from)?;
+    let lessons_list = lesson_responses(&ctx.db, lessons_list, &PreferredLangs::default())
+        .await
+        .map_err(ApiError::from)?;
+
+    Ok(Json(lessons_list).into_response())
+}
+
+/// Submit a draft lesson for review
+#[utoipa::path(
+    post,
+    tag = "Content Review",
+    path = "/api/lessons/{id}/submit",
+    params(("id" = Uuid, Path, description = "Lesson ID")),
+    responses(
+        (status = 200, body = LessonResponse),
+        ApiError
+    ),
+    security(("jwt_token" = []))
+)]
+async fn submit_lesson(
+    auth_user: AuthUser,
+    Path(id): Path<Uuid>,
+    State(ctx): State<AppContext>,
+) -> axum::response::Result<Response> {
+    let lesson = find_lesson(&ctx, id).await?;
+    check_topic_access_by_id(&ctx.db, auth_user.user.id, lesson.topic_id).await?;
+
+    let lesson = transition_lesson(&ctx, lesson, auth_user.user.id, "submit", None).await?;
+
+    let lesson = lesson_responses(&ctx.db, vec![lesson], &PreferredLangs::default())
+        .await
+        .map_err(ApiError::from)?
+        .remove(0);
+
+    Ok(Json(lesson).into_response())
+}
+
+/// Approve, reject or archive a lesson (reviewers only)
+#[utoipa::path(
+    post,
+    tag = "Content Review",
+    path = "/api/lessons/{id}/review",
+    params(("id" = Uuid, Path, description = "Lesson ID")),
+    request_body = ReviewDecisionParams,
+    responses(
+        (status = 200, body = LessonResponse),
+        ApiError
+    ),
+    security(("jwt_token" = []))
+)]
+async fn review_lesson(
+    admin: AdminUser,
+    Path(id): Path<Uuid>,
+    State(ctx): State<AppContext>,
+    Json(params): Json<ReviewDecisionParams>,
+) -> axum::response::Result<Response> {
+    params.validate().map_err(ApiError::from)?;
+    let action = decision_action(&params)?;
+
+    let lesson = find_lesson(&ctx, id).await?;
+    let lesson = transition_lesson(&ctx, lesson, admin.user.id, action, params.comment).await?;
+
+    let lesson = lesson_responses(&ctx.db, vec![lesson], &PreferredLangs::default())
+        .await
+        .map_err(ApiError::from)?
+        .remove(0);
+
+    Ok(Json(lesson).into_response())
+}
+
+/// Workflow history of a lesson
+#[utoipa::path(
+    get,
+    tag = "Content Review",
+    path = "/api/lessons/{id}/reviews",
+    params(("id" = Uuid, Path, description = "Lesson ID")),
+    responses(
+        (status = 200, body = Vec<ContentReviewResponse>),
+        ApiError
+    ),
+    security(("jwt_token" = []))
+)]
+async fn lesson_reviews(
+    auth_user: AuthUser,
+    Path(id): Path<Uuid>,
+    State(ctx): State<AppContext>,
+) -> axum::response::Result<Response> {
+    let lesson = find_lesson(&ctx, id).await?;
+    check_topic_access_by_id(&ctx.db, auth_user.user.id, lesson.topic_id).await?;
+
+    Ok(Json(review_log(&ctx, "lesson", id).await?).into_response())
+}
+
+pub fn routes() -> OpenApiRouter<AppContext> {
+    OpenApiRouter::new()
+        .routes(routes!(question_queue))
+        .routes(routes!(expiring_questions))
+        .routes(routes!(submit_question))
+        .routes(routes!(review_question))
+        .routes(routes!(question_reviews))
+        .routes(routes!(lesson_queue))
+        .routes(routes!(submit_lesson))
+        .routes(routes!(review_lesson))
+        .routes(routes!(lesson_reviews))
+}
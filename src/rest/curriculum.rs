@@ -0,0 +1,168 @@
+use crate::{
+    entities::{lesson_progress, lessons, topic_prerequisites, topics, users},
+    models::topics::TopicPrerequisiteParams,
+    rest::lesson_progress::topic_progress,
+    utils::{config::CURRICULUM_MODE, response::ApiError},
+};
+use sea_orm::{
+    ActiveModelTrait, ActiveValue::Set, ColumnTrait, ConnectionTrait, DbErr, EntityTrait,
+    PaginatorTrait, QueryFilter,
+};
+use std::collections::{HashMap, HashSet};
+use uuid::Uuid;
+
+/// Topics whose prerequisites the user has not met yet
+pub(crate) async fn locked_topics<C: ConnectionTrait>(
+    db: &C,
+    user: &users::Model,
+) -> Result<HashSet<Uuid>, DbErr> {
+    let prerequisites = topic_prerequisites::Entity::find().all(db).await?;
+    if prerequisites.is_empty() {
+        return Ok(HashSet::new());
+    }
+
+    let required: Vec<Uuid> = prerequisites
+        .iter()
+        .map(|p| p.required_topic_id)
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .collect();
+    let progress = topic_progress(db, user, &required).await?;
+    let completed: HashSet<Uuid> = lesson_progress::Entity::find()
+        .filter(lesson_progress::Column::UserId.eq(user.id))
+        .filter(
+            lesson_progress::Column::LessonId
+                .is_in(prerequisites.iter().filter_map(|p| p.lesson_id)),
+        )
+        .filter(lesson_progress::Column::CompletedAt.is_not_null())
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|progress| progress.lesson_id)
+        .collect();
+
+    Ok(prerequisites
+        .into_iter()
+        .filter(|prerequisite| {
+            let topic = progress.get(&prerequisite.required_topic_id);
+            let lesson_read = prerequisite
+                .lesson_id
+                .is_none_or(|lesson_id| completed.contains(&lesson_id));
+            let accurate = prerequisite.min_accuracy.is_none_or(|min| {
+                topic.is_some_and(|t| (t.accuracy * 100.0).round() as i16 >= min)
+            });
+            let all_read = prerequisite.lesson_id.is_some()
+                || prerequisite.min_accuracy.is_some()
+                || topic.is_none_or(|t| t.lessons_completed >= t.lessons_total);
+            !(lesson_read && accurate && all_read)
+        })
+        .map(|prerequisite| prerequisite.topic_id)
+        .collect())
+}
+
+/// In curriculum mode, fails for a topic whose prerequisites are not met
+pub(crate) async fn check_unlocked<C: ConnectionTrait>(
+    db: &C,
+    user_id: Uuid,
+    topic_id: Uuid,
+) -> Result<(), ApiError> {
+    if !*CURRICULUM_MODE {
+        return Ok(());
+    }
+
+    let user = users::Entity::find_by_id(user_id)
+        .one(db)
+        .await
+        .map_err(ApiError::from)?
+        .ok_or(ApiError::UserNotFound)?;
+    let locked = locked_topics(db, &user).await.map_err(ApiError::from)?;
+
+    if locked.contains(&topic_id) {
+        Err(ApiError::AccessDenied)
+    } else {
+        Ok(())
+    }
+}
+
+/// Replaces the prerequisites of a topic. Required topics must exist, a lesson
+/// must belong to its required topic and the graph must stay acyclic.
+pub(crate) async fn save_prerequisites<C: ConnectionTrait>(
+    db: &C,
+    topic_id: Uuid,
+    prerequisites: Vec<TopicPrerequisiteParams>,
+) -> Result<(), ApiError> {
+    let required: HashSet<Uuid> = prerequisites.iter().map(|p| p.required_topic_id).collect();
+    if required.contains(&topic_id) {
+        return Err(ApiError::InvalidFieldValue);
+    }
+
+    let found = topics::Entity::find()
+        .filter(topics::Column::Id.is_in(required.iter().cloned()))
+        .count(db)
+        .await
+        .map_err(ApiError::from)?;
+    if found != required.len() as u64 {
+        return Err(ApiError::NotFound);
+    }
+
+    let lesson_ids: Vec<Uuid> = prerequisites.iter().filter_map(|p| p.lesson_id).collect();
+    let lesson_topics: HashMap<Uuid, Uuid> = lessons::Entity::find()
+        .filter(lessons::Column::Id.is_in(lesson_ids))
+        .all(db)
+        .await
+        .map_err(ApiError::from)?
+        .into_iter()
+        .map(|lesson| (lesson.id, lesson.topic_id))
+        .collect();
+    for prerequisite in &prerequisites {
+        if let Some(lesson_id) = prerequisite.lesson_id
+            && lesson_topics.get(&lesson_id) != Some(&prerequisite.required_topic_id)
+        {
+            return Err(ApiError::InvalidFieldValue);
+        }
+    }
+
+    // Граф без правок этой темы плюс новые рёбра не должен вести обратно к ней
+    let mut edges: HashMap<Uuid, Vec<Uuid>> = HashMap::new();
+    for prerequisite in topic_prerequisites::Entity::find()
+        .filter(topic_prerequisites::Column::TopicId.ne(topic_id))
+        .all(db)
+        .await
+        .map_err(ApiError::from)?
+    {
+        edges
+            .entry(prerequisite.topic_id)
+            .or_default()
+            .push(prerequisite.required_topic_id);
+    }
+    let mut stack: Vec<Uuid> = required.into_iter().collect();
+    let mut visited = HashSet::new();
+    while let Some(current) = stack.pop() {
+        if current == topic_id {
+            return Err(ApiError::InvalidFieldValue);
+        }
+        if visited.insert(current) {
+            stack.extend(edges.get(&current).into_iter().flatten());
+        }
+    }
+
+    topic_prerequisites::Entity::delete_many()
+        .filter(topic_prerequisites::Column::TopicId.eq(topic_id))
+        .exec(db)
+        .await
+        .map_err(ApiError::from)?;
+    for prerequisite in prerequisites {
+        topic_prerequisites::ActiveModel {
+            topic_id: Set(topic_id),
+            required_topic_id: Set(prerequisite.required_topic_id),
+            lesson_id: Set(prerequisite.lesson_id),
+            min_accuracy: Set(prerequisite.min_accuracy),
+            ..Default::default()
+        }
+        .insert(db)
+        .await
+        .map_err(ApiError::from)?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,302 @@
+use crate::{
+    AppContext,
+    entities::{daily_challenge_questions, daily_challenges, tests, users},
+    models::daily_challenges::{
+        DailyChallengeResponse, DailyChallengesQuery, LeaderboardEntry, LeaderboardResponse,
+    },
+    tasks::daily_challenges::ensure_daily_challenge,
+    utils::{extractors::AuthUser, response::ApiError},
+};
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+    response::{IntoResponse, Response},
+};
+use chrono::NaiveDate;
+use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder};
+use std::collections::{HashMap, HashSet};
+use utoipa_axum::{router::OpenApiRouter, routes};
+use uuid::Uuid;
+
+/// Number of entries shown on the leaderboard
+const LEADERBOARD_SIZE: usize = 100;
+
+/// Question ids of the challenge in test order
+async fn load_question_ids(
+    db: &DatabaseConnection,
+    challenge_id: Uuid,
+) -> Result<Vec<Uuid>, ApiError> {
+    Ok(daily_challenge_questions::Entity::find()
+        .filter(daily_challenge_questions::Column::ChallengeId.eq(challenge_id))
+        .order_by_asc(daily_challenge_questions::Column::QuestionOrder)
+        .all(db)
+        .await
+        .map_err(ApiError::from)?
+        .into_iter()
+        .map(|dq| dq.question_id)
+        .collect())
+}
+
+/// The user's attempt at the challenge
+async fn find_attempt(
+    db: &DatabaseConnection,
+    user_id: Uuid,
+    challenge_id: Uuid,
+) -> Result<Option<tests::Model>, ApiError> {
+    tests::Entity::find()
+        .filter(tests::Column::UserId.eq(user_id))
+        .filter(tests::Column::FilterType.eq("daily"))
+        .filter(tests::Column::FilterId.eq(challenge_id))
+        .filter(tests::Column::IsDeleted.eq(false))
+        .one(db)
+        .await
+        .map_err(ApiError::from)
+}
+
+async fn build_response(
+    db: &DatabaseConnection,
+    user_id: Uuid,
+    challenge: daily_challenges::Model,
+) -> Result<DailyChallengeResponse, ApiError> {
+    let question_ids = load_question_ids(db, challenge.id).await?;
+    let attempt = find_attempt(db, user_id, challenge.id).await?;
+
+    Ok(DailyChallengeResponse::from_model(
+        challenge,
+        question_ids,
+        attempt,
+    ))
+}
+
+/// Consecutive days, up to today or yesterday, with a completed challenge
+async fn current_streak(
+    db: &DatabaseConnection,
+    user_id: Uuid,
+    today: NaiveDate,
+) -> Result<i32, ApiError> {
+    let challenge_ids: Vec<Uuid> = tests::Entity::find()
+        .filter(tests::Column::UserId.eq(user_id))
+        .filter(tests::Column::FilterType.eq("daily"))
+        .filter(tests::Column::Status.eq("completed"))
+        .all(db)
+        .await
+        .map_err(ApiError::from)?
+        .into_iter()
+        .filter_map(|t| t.filter_id)
+        .collect();
+
+    let dates: HashSet<NaiveDate> = daily_challenges::Entity::find()
+        .filter(daily_challenges::Column::Id.is_in(challenge_ids))
+        .all(db)
+        .await
+        .map_err(ApiError::from)?
+        .into_iter()
+        .map(|c| c.challenge_date)
+        .collect();
+
+    // Сегодняшний челлендж ещё может быть не пройден, серия при этом не прерывается
+    let mut day = if dates.contains(&today) {
+        today
+    } else {
+        today.pred_opt().unwrap_or(today)
+    };
+
+    let mut streak = 0;
+    while dates.contains(&day) {
+        streak += 1;
+        day = match day.pred_opt() {
+            Some(prev) => prev,
+            None => break,
+        };
+    }
+
+    Ok(streak)
+}
+
+/// List daily challenges up to today
+#[utoipa::path(
+    get,
+    tag = "Daily Challenges",
+    path = "/api/daily_challenges",
+    params(DailyChallengesQuery),
+    responses(
+        (status = 200, body = Vec<DailyChallengeResponse>),
+        ApiError
+    ),
+    security(("jwt_token" = []))
+)]
+async fn list(
+    auth_user: AuthUser,
+    State(ctx): State<AppContext>,
+    Query(query): Query<DailyChallengesQuery>,
+) -> axum::response::Result<Response> {
+    let today = chrono::Utc::now().date_naive();
+
+    let challenges = daily_challenges::Entity::find()
+        .filter(daily_challenges::Column::Lang.eq(&query.lang))
+        .filter(daily_challenges::Column::ChallengeDate.lte(today))
+        .order_by_desc(daily_challenges::Column::ChallengeDate)
+        .all(&ctx.db)
+        .await
+        .map_err(ApiError::from)?;
+
+    let mut responses = Vec::new();
+    for challenge in challenges {
+        responses.push(build_response(&ctx.db, auth_user.user.id, challenge).await?);
+    }
+
+    Ok(Json(responses).into_response())
+}
+
+/// Get today's challenge
+#[utoipa::path(
+    get,
+    tag = "Daily Challenges",
+    path = "/api/daily_challenges/today",
+    params(DailyChallengesQuery),
+    responses(
+        (status = 200, body = DailyChallengeResponse),
+        ApiError
+    ),
+    security(("jwt_token" = []))
+)]
+async fn today(
+    auth_user: AuthUser,
+    State(ctx): State<AppContext>,
+    Query(query): Query<DailyChallengesQuery>,
+) -> axum::response::Result<Response> {
+    let today = chrono::Utc::now().date_naive();
+
+    // Если задача ещё не отработала, челлендж создаётся при первом запросе
+    let challenge = ensure_daily_challenge(&ctx.db, today, &query.lang)
+        .await
+        .map_err(ApiError::from)?
+        .ok_or(ApiError::NotFound)?;
+
+    Ok(Json(build_response(&ctx.db, auth_user.user.id, challenge).await?).into_response())
+}
+
+/// Get challenge by id
+#[utoipa::path(
+    get,
+    tag = "Daily Challenges",
+    path = "/api/daily_challenges/{id}",
+    params(("id" = Uuid, Path, description = "Challenge ID")),
+    responses(
+        (status = 200, body = DailyChallengeResponse),
+        ApiError
+    ),
+    security(("jwt_token" = []))
+)]
+async fn get(
+    auth_user: AuthUser,
+    Path(id): Path<Uuid>,
+    State(ctx): State<AppContext>,
+) -> axum::response::Result<Response> {
+    let challenge = daily_challenges::Entity::find_by_id(id)
+        .one(&ctx.db)
+        .await
+        .map_err(ApiError::from)?
+        .ok_or(ApiError::NotFound)?;
+
+    Ok(Json(build_response(&ctx.db, auth_user.user.id, challenge).await?).into_response())
+}
+
+/// Get challenge leaderboard with the user's rank and streak
+#[utoipa::path(
+    get,
+    tag = "Daily Challenges",
+    path = "/api/daily_challenges/{id}/leaderboard",
+    params(("id" = Uuid, Path, description = "Challenge ID")),
+    responses(
+        (status = 200, body = LeaderboardResponse),
+        ApiError
+    ),
+    security(("jwt_token" = []))
+)]
+async fn leaderboard(
+    auth_user: AuthUser,
+    Path(id): Path<Uuid>,
+    State(ctx): State<AppContext>,
+) -> axum::response::Result<Response> {
+    let challenge = daily_challenges::Entity::find_by_id(id)
+        .one(&ctx.db)
+        .await
+        .map_err(ApiError::from)?
+        .ok_or(ApiError::NotFound)?;
+
+    let completed = tests::Entity::find()
+        .filter(tests::Column::FilterType.eq("daily"))
+        .filter(tests::Column::FilterId.eq(challenge.id))
+        .filter(tests::Column::Status.eq("completed"))
+        .filter(tests::Column::IsDeleted.eq(false))
+        .all(&ctx.db)
+        .await
+        .map_err(ApiError::from)?;
+
+    let user_ids: Vec<Uuid> = completed.iter().map(|t| t.user_id).collect();
+    let usernames: HashMap<Uuid, Option<String>> = users::Entity::find()
+        .filter(users::Column::Id.is_in(user_ids))
+        .all(&ctx.db)
+        .await
+        .map_err(ApiError::from)?
+        .into_iter()
+        .map(|u| (u.id, u.username))
+        .collect();
+
+    let mut ranked: Vec<(tests::Model, i64)> = completed
+        .into_iter()
+        .map(|t| {
+            let duration = t
+                .completed_at
+                .map(|completed_at| (completed_at - t.created_at).num_seconds())
+                .unwrap_or(i64::MAX);
+            (t, duration)
+        })
+        .collect();
+
+    // Сначала по баллам, при равенстве — кто быстрее
+    ranked.sort_by(|(a, a_duration), (b, b_duration)| {
+        b.score_percent
+            .cmp(&a.score_percent)
+            .then(b.earned_points.cmp(&a.earned_points))
+            .then(a_duration.cmp(b_duration))
+    });
+
+    let entries: Vec<LeaderboardEntry> = ranked
+        .into_iter()
+        .enumerate()
+        .map(|(index, (test, duration_seconds))| LeaderboardEntry {
+            rank: index as i64 + 1,
+            user_id: test.user_id,
+            username: usernames.get(&test.user_id).cloned().flatten(),
+            score_percent: test.score_percent.unwrap_or(0),
+            earned_points: test.earned_points,
+            duration_seconds,
+        })
+        .collect();
+
+    let me = entries
+        .iter()
+        .find(|e| e.user_id == auth_user.user.id)
+        .cloned();
+    let streak =
+        current_streak(&ctx.db, auth_user.user.id, chrono::Utc::now().date_naive()).await?;
+
+    Ok(Json(LeaderboardResponse {
+        challenge_id: challenge.id,
+        challenge_date: challenge.challenge_date,
+        entries: entries.into_iter().take(LEADERBOARD_SIZE).collect(),
+        me,
+        streak,
+    })
+    .into_response())
+}
+
+pub fn routes() -> OpenApiRouter<AppContext> {
+    OpenApiRouter::new()
+        .routes(routes!(list))
+        .routes(routes!(today))
+        .routes(routes!(get))
+        .routes(routes!(leaderboard))
+}
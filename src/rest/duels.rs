@@ -0,0 +1,490 @@
+use crate::{
+    AppContext,
+    entities::{duel_players, duels, questions, tests, topics},
+    live::{duels::DuelRoom, pump_socket},
+    models::duels::{
+        CreateDuelParams, DuelCommand, DuelEvent, DuelPlayerResponse, DuelResponse,
+        DuelSocketQuery, JoinDuelParams,
+    },
+    rest::{
+        regions::questions_in_effect,
+        tests::{generate_filter_hash, insert_test_questions},
+    },
+    utils::{extractors::AuthUser, jwt::Claims, response::ApiError},
+};
+use axum::{
+    Json,
+    extract::{
+        Path, Query, State,
+        ws::{WebSocket, WebSocketUpgrade},
+    },
+    response::{IntoResponse, Response},
+};
+use rand::{Rng, seq::SliceRandom};
+use sea_orm::{
+    ActiveModelTrait,
+    ActiveValue::Set,
+    ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait, IntoActiveModel, QueryFilter,
+    QueryOrder, QuerySelect, TransactionTrait,
+    sea_query::{LockBehavior, LockType},
+};
+use std::{collections::HashMap, sync::Arc};
+use utoipa_axum::{router::OpenApiRouter, routes};
+use uuid::Uuid;
+use validator::Validate;
+
+/// Invite code alphabet without look-alike characters
+const INVITE_ALPHABET: &[u8] = b"ABCDEFGHJKLMNPQRSTUVWXYZ23456789";
+const INVITE_LENGTH: usize = 6;
+
+fn generate_invite_code() -> String {
+    let mut rng = rand::thread_rng();
+    (0..INVITE_LENGTH)
+        .map(|_| INVITE_ALPHABET[rng.gen_range(0..INVITE_ALPHABET.len())] as char)
+        .collect()
+}
+
+/// Whether the user already takes part in a duel that is not over
+async fn has_open_duel<C: ConnectionTrait>(db: &C, user_id: Uuid) -> Result<bool, ApiError> {
+    let duel_ids: Vec<Uuid> = duel_players::Entity::find()
+        .filter(duel_players::Column::UserId.eq(user_id))
+        .all(db)
+        .await
+        .map_err(ApiError::from)?
+        .into_iter()
+        .map(|dp| dp.duel_id)
+        .collect();
+
+    let open = duels::Entity::find()
+        .filter(duels::Column::Id.is_in(duel_ids))
+        .filter(duels::Column::Status.is_in(["waiting", "ready", "active"]))
+        .one(db)
+        .await
+        .map_err(ApiError::from)?;
+
+    Ok(open.is_some())
+}
+
+/// Matched duel ready to be handed to the hub
+struct StartedDuel {
+    duel: duels::Model,
+    players: Vec<(Uuid, Uuid)>,
+    question_ids: Vec<Uuid>,
+}
+
+/// Adds the second player, picks the shared questions and creates a test for each player
+async fn start_duel<C: ConnectionTrait>(
+    db: &C,
+    duel: duels::Model,
+    user_id: Uuid,
+) -> Result<StartedDuel, ApiError> {
+    // Только бесплатные темы и общие действующие правила, чтобы вопросы подходили обоим игрокам
+    let mut question_ids: Vec<Uuid> = questions::Entity::find()
+        .inner_join(topics::Entity)
+        .filter(topics::Column::SubscriptionRequired.eq(false))
+        .filter(questions::Column::Lang.eq(&duel.lang))
+        .filter(questions::Column::Status.eq("published"))
+        .filter(questions_in_effect(None))
+        .select_only()
+        .column(questions::Column::Id)
+        .into_tuple()
+        .all(db)
+        .await
+        .map_err(ApiError::from)?;
+
+    if (question_ids.len() as i16) < duel.questions_count {
+        return Err(ApiError::InvalidInput);
+    }
+
+    {
+        let mut rng = rand::thread_rng();
+        question_ids.shuffle(&mut rng);
+    }
+    question_ids.truncate(duel.questions_count as usize);
+
+    let points: HashMap<Uuid, i16> = questions::Entity::find()
+        .filter(questions::Column::Id.is_in(question_ids.clone()))
+        .all(db)
+        .await
+        .map_err(ApiError::from)?
+        .into_iter()
+        .map(|q| (q.id, q.points))
+        .collect();
+    let selected: Vec<(Uuid, i16)> = question_ids
+        .iter()
+        .map(|id| (*id, points.get(id).copied().unwrap_or(1)))
+        .collect();
+    let max_points: i16 = selected.iter().map(|(_, p)| p).sum();
+
+    let joined = duel_players::ActiveModel {
+        duel_id: Set(duel.id),
+        user_id: Set(user_id),
+        test_id: Set(None),
+        player_order: Set(2),
+        is_winner: Set(false),
+    };
+    joined.insert(db).await.map_err(ApiError::from)?;
+
+    let duel_players_list = duel_players::Entity::find()
+        .filter(duel_players::Column::DuelId.eq(duel.id))
+        .order_by_asc(duel_players::Column::PlayerOrder)
+        .all(db)
+        .await
+        .map_err(ApiError::from)?;
+
+    let mut players = Vec::new();
+    for player in duel_players_list {
+        let test = tests::ActiveModel {
+            user_id: Set(player.user_id),
+            filter_type: Set("duel".to_string()),
+            filter_id: Set(Some(duel.id)),
+            lang: Set(duel.lang.clone()),
+            filter_hash: Set(generate_filter_hash("duel", Some(duel.id), &duel.lang)),
+            total_questions: Set(selected.len() as i16),
+            correct_count: Set(0),
+            status: Set("active".to_string()),
+            max_points: Set(max_points),
+            ..Default::default()
+        };
+        let test = test.insert(db).await.map_err(ApiError::from)?;
+        insert_test_questions(db, test.id, &selected).await?;
+
+        let user_id = player.user_id;
+        let mut player_active = player.into_active_model();
+        player_active.test_id = Set(Some(test.id));
+        player_active.update(db).await.map_err(ApiError::from)?;
+
+        players.push((user_id, test.id));
+    }
+
+    let mut duel_active = duel.into_active_model();
+    duel_active.status = Set("ready".to_string());
+    duel_active.updated_at = Set(chrono::Utc::now().into());
+    let duel = duel_active.update(db).await.map_err(ApiError::from)?;
+
+    Ok(StartedDuel {
+        duel,
+        players,
+        question_ids,
+    })
+}
+
+async fn build_response(
+    db: &DatabaseConnection,
+    duel: duels::Model,
+) -> Result<DuelResponse, ApiError> {
+    let players = duel_players::Entity::find()
+        .filter(duel_players::Column::DuelId.eq(duel.id))
+        .order_by_asc(duel_players::Column::PlayerOrder)
+        .all(db)
+        .await
+        .map_err(ApiError::from)?;
+
+    let test_ids: Vec<Uuid> = players.iter().filter_map(|p| p.test_id).collect();
+    let tests_map: HashMap<Uuid, tests::Model> = tests::Entity::find()
+        .filter(tests::Column::Id.is_in(test_ids))
+        .all(db)
+        .await
+        .map_err(ApiError::from)?
+        .into_iter()
+        .map(|t| (t.id, t))
+        .collect();
+
+    let players = players
+        .into_iter()
+        .map(|p| {
+            let test = p.test_id.and_then(|id| tests_map.get(&id));
+            DuelPlayerResponse::from_model(p, test)
+        })
+        .collect();
+
+    Ok(DuelResponse::from_model(duel, players))
+}
+
+/// Start a duel by invite or through matchmaking
+#[utoipa::path(
+    post,
+    tag = "Duels",
+    path = "/api/duels",
+    request_body = CreateDuelParams,
+    responses(
+        (status = 200, body = DuelResponse, description = "Matched with a waiting player"),
+        (status = 201, body = DuelResponse, description = "Waiting for an opponent"),
+        ApiError
+    ),
+    security(("jwt_token" = []))
+)]
+async fn create(
+    auth_user: AuthUser,
+    State(ctx): State<AppContext>,
+    Json(params): Json<CreateDuelParams>,
+) -> axum::response::Result<Response> {
+    params.validate().map_err(ApiError::from)?;
+
+    if !["invite", "matchmaking"].contains(&params.mode.as_str()) {
+        return Err(ApiError::InvalidFieldValue.into());
+    }
+
+    let txn = ctx.db.begin().await.map_err(ApiError::from)?;
+
+    if has_open_duel(&txn, auth_user.user.id).await? {
+        return Err(ApiError::AlreadyExists.into());
+    }
+
+    if params.mode == "matchmaking" {
+        // Очередь — это ожидающие дуэли без кода приглашения
+        let waiting = duels::Entity::find()
+            .filter(duels::Column::InviteCode.is_null())
+            .filter(duels::Column::Status.eq("waiting"))
+            .filter(duels::Column::Lang.eq(&params.lang))
+            .filter(duels::Column::QuestionsCount.eq(params.questions_count))
+            .filter(duels::Column::IsDeleted.eq(false))
+            .order_by_asc(duels::Column::CreatedAt)
+            .lock_with_behavior(LockType::Update, LockBehavior::SkipLocked)
+            .one(&txn)
+            .await
+            .map_err(ApiError::from)?;
+
+        if let Some(duel) = waiting {
+            let started = start_duel(&txn, duel, auth_user.user.id).await?;
+            txn.commit().await.map_err(ApiError::from)?;
+
+            ctx.duels.start(
+                ctx.db.clone(),
+                started.duel.id,
+                started.players,
+                started.question_ids,
+            );
+
+            return Ok(Json(build_response(&ctx.db, started.duel).await?).into_response());
+        }
+    }
+
+    let invite_code = if params.mode == "invite" {
+        let mut code = generate_invite_code();
+        while duels::Entity::find()
+            .filter(duels::Column::InviteCode.eq(&code))
+            .one(&txn)
+            .await
+            .map_err(ApiError::from)?
+            .is_some()
+        {
+            code = generate_invite_code();
+        }
+        Some(code)
+    } else {
+        None
+    };
+
+    let duel = duels::ActiveModel {
+        lang: Set(params.lang),
+        questions_count: Set(params.questions_count),
+        invite_code: Set(invite_code),
+        status: Set("waiting".to_string()),
+        ..Default::default()
+    };
+    let duel = duel.insert(&txn).await.map_err(ApiError::from)?;
+
+    let creator = duel_players::ActiveModel {
+        duel_id: Set(duel.id),
+        user_id: Set(auth_user.user.id),
+        test_id: Set(None),
+        player_order: Set(1),
+        is_winner: Set(false),
+    };
+    creator.insert(&txn).await.map_err(ApiError::from)?;
+
+    txn.commit().await.map_err(ApiError::from)?;
+
+    Ok((
+        axum::http::StatusCode::CREATED,
+        Json(build_response(&ctx.db, duel).await?),
+    )
+        .into_response())
+}
+
+/// Join a duel by invite code
+#[utoipa::path(
+    post,
+    tag = "Duels",
+    path = "/api/duels/join",
+    request_body = JoinDuelParams,
+    responses(
+        (status = 200, body = DuelResponse),
+        ApiError
+    ),
+    security(("jwt_token" = []))
+)]
+async fn join(
+    auth_user: AuthUser,
+    State(ctx): State<AppContext>,
+    Json(params): Json<JoinDuelParams>,
+) -> axum::response::Result<Response> {
+    params.validate().map_err(ApiError::from)?;
+
+    let txn = ctx.db.begin().await.map_err(ApiError::from)?;
+
+    let duel = duels::Entity::find()
+        .filter(duels::Column::InviteCode.eq(params.invite_code.to_uppercase()))
+        .filter(duels::Column::Status.eq("waiting"))
+        .filter(duels::Column::IsDeleted.eq(false))
+        .lock(LockType::Update)
+        .one(&txn)
+        .await
+        .map_err(ApiError::from)?
+        .ok_or(ApiError::NotFound)?;
+
+    if has_open_duel(&txn, auth_user.user.id).await? {
+        return Err(ApiError::AlreadyExists.into());
+    }
+
+    let started = start_duel(&txn, duel, auth_user.user.id).await?;
+    txn.commit().await.map_err(ApiError::from)?;
+
+    ctx.duels.start(
+        ctx.db.clone(),
+        started.duel.id,
+        started.players,
+        started.question_ids,
+    );
+
+    Ok(Json(build_response(&ctx.db, started.duel).await?).into_response())
+}
+
+/// Get duel by id
+#[utoipa::path(
+    get,
+    tag = "Duels",
+    path = "/api/duels/{id}",
+    params(("id" = Uuid, Path, description = "Duel ID")),
+    responses(
+        (status = 200, body = DuelResponse),
+        ApiError
+    ),
+    security(("jwt_token" = []))
+)]
+async fn get(
+    auth_user: AuthUser,
+    Path(id): Path<Uuid>,
+    State(ctx): State<AppContext>,
+) -> axum::response::Result<Response> {
+    let duel = duels::Entity::find_by_id(id)
+        .filter(duels::Column::IsDeleted.eq(false))
+        .one(&ctx.db)
+        .await
+        .map_err(ApiError::from)?
+        .ok_or(ApiError::NotFound)?;
+
+    let response = build_response(&ctx.db, duel).await?;
+    if !response
+        .players
+        .iter()
+        .any(|p| p.user_id == auth_user.user.id)
+    {
+        return Err(ApiError::Forbidden.into());
+    }
+
+    Ok(Json(response).into_response())
+}
+
+/// Cancel a duel that is still waiting for an opponent
+#[utoipa::path(
+    delete,
+    tag = "Duels",
+    path = "/api/duels/{id}",
+    params(("id" = Uuid, Path, description = "Duel ID")),
+    responses(
+        (status = 200),
+        ApiError
+    ),
+    security(("jwt_token" = []))
+)]
+async fn cancel(
+    auth_user: AuthUser,
+    Path(id): Path<Uuid>,
+    State(ctx): State<AppContext>,
+) -> axum::response::Result<Response> {
+    let txn = ctx.db.begin().await.map_err(ApiError::from)?;
+
+    let duel = duels::Entity::find_by_id(id)
+        .filter(duels::Column::IsDeleted.eq(false))
+        .lock(LockType::Update)
+        .one(&txn)
+        .await
+        .map_err(ApiError::from)?
+        .ok_or(ApiError::NotFound)?;
+
+    duel_players::Entity::find_by_id((duel.id, auth_user.user.id))
+        .one(&txn)
+        .await
+        .map_err(ApiError::from)?
+        .ok_or(ApiError::Forbidden)?;
+
+    if duel.status != "waiting" {
+        return Err(ApiError::InvalidState.into());
+    }
+
+    let mut to_update = duel.into_active_model();
+    to_update.status = Set("cancelled".to_string());
+    to_update.updated_at = Set(chrono::Utc::now().into());
+    to_update.update(&txn).await.map_err(ApiError::from)?;
+
+    txn.commit().await.map_err(ApiError::from)?;
+
+    Ok(().into_response())
+}
+
+/// Duel WebSocket: questions, live scores and the result.
+/// Reconnecting resumes the current question with the remaining time.
+#[utoipa::path(
+    get,
+    tag = "Duels",
+    path = "/api/duels/{id}/ws",
+    params(
+        ("id" = Uuid, Path, description = "Duel ID"),
+        DuelSocketQuery
+    ),
+    responses(
+        (status = 101, description = "Switching to the WebSocket protocol"),
+        ApiError
+    )
+)]
+async fn socket(
+    Path(id): Path<Uuid>,
+    Query(query): Query<DuelSocketQuery>,
+    State(ctx): State<AppContext>,
+    ws: WebSocketUpgrade,
+) -> axum::response::Result<Response> {
+    let claims = Claims::from_token(&query.token)?;
+
+    // Комната живёт в памяти только пока идёт дуэль
+    let room = ctx.duels.room(id).ok_or(ApiError::InvalidState)?;
+    if !room.is_player(claims.id) {
+        return Err(ApiError::Forbidden.into());
+    }
+
+    Ok(ws.on_upgrade(move |socket| handle_socket(socket, room, claims.id)))
+}
+
+async fn handle_socket(mut socket: WebSocket, room: Arc<DuelRoom>, user_id: Uuid) {
+    let (connection_id, mut events) = room.connect(user_id).await;
+
+    pump_socket(
+        &mut socket,
+        &mut events,
+        |command: DuelCommand| room.submit(user_id, command),
+        |message| DuelEvent::Error { message },
+    )
+    .await;
+
+    room.disconnect(user_id, connection_id).await;
+}
+
+pub fn routes() -> OpenApiRouter<AppContext> {
+    OpenApiRouter::new()
+        .routes(routes!(create))
+        .routes(routes!(join))
+        .routes(routes!(get))
+        .routes(routes!(cancel))
+        .routes(routes!(socket))
+}
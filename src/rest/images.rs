@@ -1,7 +1,7 @@
 use crate::{
     AppContext,
     entities::images,
-    models::images::{ImageResponse, UploadResponse},
+    models::images::{ImageLink, ImageResponse, UploadResponse},
     utils::response::ApiError,
 };
 use axum::{
@@ -11,8 +11,11 @@ use axum::{
     http::{StatusCode, header},
     response::{IntoResponse, Response},
 };
-use sea_orm::{ActiveModelTrait, ActiveValue::Set, EntityTrait};
-use std::path::PathBuf;
+use sea_orm::{
+    ActiveModelTrait, ActiveValue::Set, ColumnTrait, ConnectionTrait, DbErr, EntityTrait,
+    QueryFilter,
+};
+use std::{collections::HashMap, path::PathBuf};
 use tokio::fs;
 use tokio::io::AsyncWriteExt;
 use tokio_util::io::ReaderStream;
@@ -21,6 +24,53 @@ use uuid::Uuid;
 
 const UPLOAD_DIR: &str = "uploads/images";
 
+/// Public URL of a stored image file
+pub(crate) fn image_url(stored_name: &str) -> String {
+    format!("/api/images/file/{}", stored_name)
+}
+
+/// Links of the given images, missing images are left out
+pub(crate) async fn image_links<C: ConnectionTrait>(
+    db: &C,
+    image_ids: impl IntoIterator<Item = Uuid>,
+) -> Result<HashMap<Uuid, ImageLink>, DbErr> {
+    let image_ids: Vec<Uuid> = image_ids.into_iter().collect();
+    if image_ids.is_empty() {
+        return Ok(HashMap::new());
+    }
+
+    Ok(images::Entity::find()
+        .filter(images::Column::Id.is_in(image_ids))
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|image| {
+            (
+                image.id,
+                ImageLink {
+                    id: image.id,
+                    url: image_url(&image.stored_name),
+                },
+            )
+        })
+        .collect())
+}
+
+/// Fails when any of the images does not exist
+pub(crate) async fn check_images_exist<C: ConnectionTrait>(
+    db: &C,
+    image_ids: &[Uuid],
+) -> Result<(), ApiError> {
+    let found = image_links(db, image_ids.iter().cloned())
+        .await
+        .map_err(ApiError::from)?;
+    if image_ids.iter().all(|id| found.contains_key(id)) {
+        Ok(())
+    } else {
+        Err(ApiError::InvalidFieldValue)
+    }
+}
+
 /// Upload image
 #[utoipa::path(
     post,
@@ -42,7 +92,7 @@ async fn upload(
         ApiError::InternalServerError
     })?;
 
-    while let Some(field) = multipart.next_field().await.map_err(|e| {
+    if let Some(field) = multipart.next_field().await.map_err(|e| {
         tracing::error!("Failed to read multipart field: {}", e);
         ApiError::BadRequest
     })? {
@@ -85,7 +135,6 @@ async fn upload(
             stored_name: Set(stored_name.clone()),
             mime_type: Set(content_type),
             size: Set(size),
-            ..Default::default()
         };
 
         image.insert(&ctx.db).await.map_err(ApiError::from)?;
@@ -93,7 +142,7 @@ async fn upload(
         let response = UploadResponse {
             id,
             stored_name: stored_name.clone(),
-            url: format!("/api/images/file/{}", stored_name),
+            url: image_url(&stored_name),
         };
 
         return Ok((StatusCode::CREATED, Json(response)).into_response());
@@ -0,0 +1,285 @@
+use crate::{
+    AppContext,
+    entities::{lesson_progress, lesson_sections, lessons, users},
+    models::lesson_progress::{
+        LessonProgressParams, LessonProgressResponse, TopicProgressResponse,
+    },
+    rest::{curriculum::check_unlocked, regions::lessons_in_effect},
+    utils::{
+        extractors::{AuthUser, check_topic_access_by_id},
+        response::ApiError,
+    },
+};
+use axum::{
+    Json,
+    extract::{Path, State},
+    response::{IntoResponse, Response},
+};
+use sea_orm::{
+    ActiveModelTrait, ActiveValue::Set, ColumnTrait, ConnectionTrait, DbBackend, DbErr,
+    EntityTrait, FromQueryResult, IntoActiveModel, QueryFilter, Statement,
+};
+use std::collections::HashMap;
+use utoipa_axum::{router::OpenApiRouter, routes};
+use uuid::Uuid;
+use validator::Validate;
+
+/// Weight of the lessons read in the topic completion, the rest is practice accuracy
+const LESSONS_WEIGHT: f64 = 0.5;
+
+/// Answered test questions of a user per topic
+const PRACTICE_BY_TOPIC: &str = r#"
+    SELECT q.topic_id,
+           count(*) AS answered,
+           count(*) FILTER (WHERE tq.is_correct) AS correct
+    FROM test_questions tq
+    JOIN tests t ON t.id = tq.test_id
+    JOIN questions q ON q.id = tq.question_id
+    WHERE t.user_id = $1 AND NOT t._is_deleted AND tq.is_correct IS NOT NULL
+    GROUP BY q.topic_id"#;
+
+#[derive(Debug, FromQueryResult)]
+struct PracticeRow {
+    topic_id: Uuid,
+    answered: i64,
+    correct: i64,
+}
+
+/// Completion in percent: lessons read and practice accuracy, or accuracy
+/// alone for topics without lessons
+fn completion(lessons_total: u64, lessons_completed: u64, accuracy: f64) -> i16 {
+    let value = if lessons_total == 0 {
+        accuracy
+    } else {
+        let read = lessons_completed as f64 / lessons_total as f64;
+        LESSONS_WEIGHT * read + (1.0 - LESSONS_WEIGHT) * accuracy
+    };
+    (value * 100.0).round() as i16
+}
+
+/// Progress of the user in each of the topics
+pub(crate) async fn topic_progress<C: ConnectionTrait>(
+    db: &C,
+    user: &users::Model,
+    topic_ids: &[Uuid],
+) -> Result<HashMap<Uuid, TopicProgressResponse>, DbErr> {
+    let topic_lessons: HashMap<Uuid, Uuid> = lessons::Entity::find()
+        .filter(lessons::Column::TopicId.is_in(topic_ids.iter().cloned()))
+        .filter(lessons::Column::Status.eq("published"))
+        .filter(lessons_in_effect(user.region.as_deref()))
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|lesson| (lesson.id, lesson.topic_id))
+        .collect();
+    let completed: Vec<Uuid> = lesson_progress::Entity::find()
+        .filter(lesson_progress::Column::UserId.eq(user.id))
+        .filter(lesson_progress::Column::LessonId.is_in(topic_lessons.keys().cloned()))
+        .filter(lesson_progress::Column::CompletedAt.is_not_null())
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|progress| progress.lesson_id)
+        .collect();
+    let practice: HashMap<Uuid, PracticeRow> = PracticeRow::find_by_statement(
+        Statement::from_sql_and_values(DbBackend::Postgres, PRACTICE_BY_TOPIC, [user.id.into()]),
+    )
+    .all(db)
+    .await?
+    .into_iter()
+    .map(|row| (row.topic_id, row))
+    .collect();
+
+    Ok(topic_ids
+        .iter()
+        .map(|&topic_id| {
+            let lessons_total = topic_lessons.values().filter(|t| **t == topic_id).count() as u64;
+            let lessons_completed = completed
+                .iter()
+                .filter(|lesson_id| topic_lessons.get(lesson_id) == Some(&topic_id))
+                .count() as u64;
+            let (answered, correct) = practice
+                .get(&topic_id)
+                .map_or((0, 0), |row| (row.answered, row.correct));
+            let accuracy = if answered == 0 {
+                0.0
+            } else {
+                correct as f64 / answered as f64
+            };
+            (
+                topic_id,
+                TopicProgressResponse {
+                    topic_id,
+                    lessons_total,
+                    lessons_completed,
+                    questions_answered: answered,
+                    questions_correct: correct,
+                    accuracy,
+                    completion: completion(lessons_total, lessons_completed, accuracy),
+                },
+            )
+        })
+        .collect())
+}
+
+/// Report reading progress of a lesson, the first report marks it opened
+#[utoipa::path(
+    post,
+    tag = "Lessons",
+    path = "/api/lessons/{id}/progress",
+    params(("id" = Uuid, Path, description = "Lesson ID")),
+    request_body = LessonProgressParams,
+    responses(
+        (status = 200, body = LessonProgressResponse),
+        ApiError
+    ),
+    security(("jwt_token" = []))
+)]
+async fn report(
+    auth_user: AuthUser,
+    Path(id): Path<Uuid>,
+    State(ctx): State<AppContext>,
+    Json(params): Json<LessonProgressParams>,
+) -> axum::response::Result<Response> {
+    params.validate().map_err(ApiError::from)?;
+
+    let lesson = lessons::Entity::find_by_id(id)
+        .filter(lessons::Column::Status.eq("published"))
+        .one(&ctx.db)
+        .await
+        .map_err(ApiError::from)?
+        .ok_or(ApiError::NotFound)?;
+    check_topic_access_by_id(&ctx.db, auth_user.user.id, lesson.topic_id).await?;
+    check_unlocked(&ctx.db, auth_user.user.id, lesson.topic_id).await?;
+
+    let section = match params.section_id {
+        Some(section_id) => Some(
+            lesson_sections::Entity::find_by_id(section_id)
+                .filter(lesson_sections::Column::LessonId.eq(id))
+                .one(&ctx.db)
+                .await
+                .map_err(ApiError::from)?
+                .ok_or(ApiError::InvalidFieldValue)?,
+        ),
+        None => None,
+    };
+
+    let now = chrono::Utc::now();
+    let current = lesson_progress::Entity::find_by_id((auth_user.user.id, id))
+        .one(&ctx.db)
+        .await
+        .map_err(ApiError::from)?;
+
+    let progress = match current {
+        Some(progress) => {
+            // Раздел меняется, только если он дальше уже достигнутого
+            let reached = match (progress.section_id, &section) {
+                (Some(current_id), Some(section)) => {
+                    lesson_sections::Entity::find_by_id(current_id)
+                        .one(&ctx.db)
+                        .await
+                        .map_err(ApiError::from)?
+                        .is_none_or(|current| section.position > current.position)
+                }
+                (None, Some(_)) => true,
+                (_, None) => false,
+            };
+            let scroll_percent = params
+                .scroll_percent
+                .map_or(progress.scroll_percent, |p| p.max(progress.scroll_percent));
+            let time_spent_seconds = progress
+                .time_spent_seconds
+                .saturating_add(params.time_spent_seconds.unwrap_or(0));
+            let completed_at = progress
+                .completed_at
+                .or(params.completed.then(|| now.into()));
+
+            let mut to_update = progress.into_active_model();
+            if reached {
+                to_update.section_id = Set(section.map(|s| s.id));
+            }
+            to_update.scroll_percent = Set(scroll_percent);
+            to_update.time_spent_seconds = Set(time_spent_seconds);
+            to_update.completed_at = Set(completed_at);
+            to_update.updated_at = Set(now.into());
+            to_update.update(&ctx.db).await.map_err(ApiError::from)?
+        }
+        None => lesson_progress::ActiveModel {
+            user_id: Set(auth_user.user.id),
+            lesson_id: Set(id),
+            section_id: Set(section.map(|s| s.id)),
+            scroll_percent: Set(params.scroll_percent.unwrap_or(0)),
+            time_spent_seconds: Set(params.time_spent_seconds.unwrap_or(0)),
+            opened_at: Set(now.into()),
+            updated_at: Set(now.into()),
+            completed_at: Set(params.completed.then(|| now.into())),
+        }
+        .insert(&ctx.db)
+        .await
+        .map_err(ApiError::from)?,
+    };
+
+    Ok(Json(LessonProgressResponse::from(progress)).into_response())
+}
+
+/// Get the current user's progress in a lesson
+#[utoipa::path(
+    get,
+    tag = "Lessons",
+    path = "/api/lessons/{id}/progress",
+    params(("id" = Uuid, Path, description = "Lesson ID")),
+    responses(
+        (status = 200, body = LessonProgressResponse),
+        ApiError
+    ),
+    security(("jwt_token" = []))
+)]
+async fn get(
+    auth_user: AuthUser,
+    Path(id): Path<Uuid>,
+    State(ctx): State<AppContext>,
+) -> axum::response::Result<Response> {
+    let progress = lesson_progress::Entity::find_by_id((auth_user.user.id, id))
+        .one(&ctx.db)
+        .await
+        .map_err(ApiError::from)?
+        .ok_or(ApiError::NotFound)?;
+
+    Ok(Json(LessonProgressResponse::from(progress)).into_response())
+}
+
+/// Get the current user's completion of a topic
+#[utoipa::path(
+    get,
+    tag = "Topics",
+    path = "/api/topics/{topic_id}/progress",
+    params(("topic_id" = Uuid, Path, description = "Topic ID")),
+    responses(
+        (status = 200, body = TopicProgressResponse),
+        ApiError
+    ),
+    security(("jwt_token" = []))
+)]
+async fn get_topic(
+    auth_user: AuthUser,
+    Path(topic_id): Path<Uuid>,
+    State(ctx): State<AppContext>,
+) -> axum::response::Result<Response> {
+    check_topic_access_by_id(&ctx.db, auth_user.user.id, topic_id).await?;
+    check_unlocked(&ctx.db, auth_user.user.id, topic_id).await?;
+
+    let progress = topic_progress(&ctx.db, &auth_user.user, &[topic_id])
+        .await
+        .map_err(ApiError::from)?
+        .remove(&topic_id)
+        .ok_or(ApiError::NotFound)?;
+
+    Ok(Json(progress).into_response())
+}
+
+pub fn routes() -> OpenApiRouter<AppContext> {
+    OpenApiRouter::new()
+        .routes(routes!(report))
+        .routes(routes!(get))
+        .routes(routes!(get_topic))
+}
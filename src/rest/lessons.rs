@@ -1,9 +1,21 @@
 use crate::{
     AppContext,
-    entities::{lessons, topics},
-    models::lessons::{CreateLessonParams, LessonResponse, UpdateLessonParams},
+    entities::{
+        lesson_section_translations, lesson_sections, lesson_translations, lessons, topics,
+    },
+    models::lessons::{
+        CreateLessonParams, LessonDetailResponse, LessonResponse, LessonSectionParams,
+        LessonSectionResponse, LessonSummaryResponse, LessonTranslationParams,
+        TopicLessonsResponse, UpdateLessonParams,
+    },
+    rest::{
+        curriculum::check_unlocked,
+        regions::{check_validity, lessons_in_effect},
+    },
     utils::{
         extractors::{AuthUser, check_topic_access, check_topic_access_by_id},
+        lang::{ContentLangQuery, PreferredLangs, check_translation_langs},
+        markdown::{MarkdownRefs, ResolvedRefs, check_markdown, render_markdown},
         response::ApiError,
     },
 };
@@ -13,17 +25,298 @@ use axum::{
     response::{IntoResponse, Response},
 };
 use sea_orm::{
-    ActiveModelTrait, ActiveValue::Set, ColumnTrait, EntityTrait, IntoActiveModel, QueryFilter,
+    ActiveModelTrait, ActiveValue::Set, ColumnTrait, ConnectionTrait, DbErr, EntityTrait,
+    IntoActiveModel, QueryFilter, QueryOrder, TransactionTrait,
 };
+use std::collections::HashMap;
 use utoipa_axum::{router::OpenApiRouter, routes};
 use uuid::Uuid;
 use validator::Validate;
 
-/// List all lessons (requires auth, subscription check per topic)
+/// Reading speed behind the estimated reading time
+const WORDS_PER_MINUTE: usize = 200;
+
+/// Reading time of the introduction and the sections of a lesson
+pub(crate) fn estimate_reading_minutes<'a>(texts: impl IntoIterator<Item = &'a str>) -> i16 {
+    let words: usize = texts
+        .into_iter()
+        .map(|text| text.split_whitespace().count())
+        .sum();
+    words.div_ceil(WORDS_PER_MINUTE).clamp(1, i16::MAX as usize) as i16
+}
+
+/// Position after the last lesson of the topic
+pub(crate) async fn next_position<C: ConnectionTrait>(
+    db: &C,
+    topic_id: Uuid,
+) -> Result<i16, DbErr> {
+    let last = lessons::Entity::find()
+        .filter(lessons::Column::TopicId.eq(topic_id))
+        .order_by_desc(lessons::Column::Position)
+        .one(db)
+        .await?;
+    Ok(last.map_or(1, |lesson| lesson.position.saturating_add(1)))
+}
+
+/// Translations of the lessons in the preferred languages, by lesson and language
+async fn lesson_translation_map<C: ConnectionTrait>(
+    db: &C,
+    lesson_ids: impl IntoIterator<Item = Uuid>,
+    langs: &PreferredLangs,
+) -> Result<HashMap<Uuid, HashMap<String, lesson_translations::Model>>, DbErr> {
+    let mut translations: HashMap<Uuid, HashMap<String, lesson_translations::Model>> =
+        HashMap::new();
+    for translation in lesson_translations::Entity::find()
+        .filter(lesson_translations::Column::LessonId.is_in(lesson_ids))
+        .filter(lesson_translations::Column::Lang.is_in(langs.0.iter().cloned()))
+        .all(db)
+        .await?
+    {
+        translations
+            .entry(translation.lesson_id)
+            .or_default()
+            .insert(translation.lang.clone(), translation);
+    }
+    Ok(translations)
+}
+
+/// Lessons with their sections in the first available preferred language
+pub(crate) async fn lesson_responses<C: ConnectionTrait>(
+    db: &C,
+    lessons: Vec<lessons::Model>,
+    langs: &PreferredLangs,
+) -> Result<Vec<LessonResponse>, DbErr> {
+    let translations = lesson_translation_map(db, lessons.iter().map(|l| l.id), langs).await?;
+
+    let sections = lesson_sections::Entity::find()
+        .filter(lesson_sections::Column::LessonId.is_in(lessons.iter().map(|l| l.id)))
+        .order_by_asc(lesson_sections::Column::Position)
+        .all(db)
+        .await?;
+    let mut section_translations: HashMap<
+        Uuid,
+        HashMap<String, lesson_section_translations::Model>,
+    > = HashMap::new();
+    for translation in lesson_section_translations::Entity::find()
+        .filter(lesson_section_translations::Column::SectionId.is_in(sections.iter().map(|s| s.id)))
+        .filter(lesson_section_translations::Column::Lang.is_in(langs.0.iter().cloned()))
+        .all(db)
+        .await?
+    {
+        section_translations
+            .entry(translation.section_id)
+            .or_default()
+            .insert(translation.lang.clone(), translation);
+    }
+
+    // Тексты, сохранённые до перехода на Markdown, рендерятся при чтении
+    let stale = MarkdownRefs::collect(
+        lessons
+            .iter()
+            .filter(|l| l.content_html.is_none())
+            .map(|l| l.content.as_str())
+            .chain(
+                translations
+                    .values()
+                    .flat_map(HashMap::values)
+                    .filter(|t| t.content_html.is_none())
+                    .map(|t| t.content.as_str()),
+            )
+            .chain(
+                sections
+                    .iter()
+                    .filter(|s| s.content_html.is_none())
+                    .map(|s| s.content.as_str()),
+            )
+            .chain(
+                section_translations
+                    .values()
+                    .flat_map(HashMap::values)
+                    .filter(|t| t.content_html.is_none())
+                    .map(|t| t.content.as_str()),
+            ),
+    );
+    let resolved = ResolvedRefs::load(db, &stale).await?;
+    let html = |source: &str, html: &Option<String>| {
+        html.clone().unwrap_or_else(|| resolved.render(source))
+    };
+
+    let mut sections_by_lesson: HashMap<Uuid, Vec<LessonSectionResponse>> = HashMap::new();
+    for section in sections {
+        let lesson_id = section.lesson_id;
+        let content_html = html(&section.content, &section.content_html);
+        let mut response = LessonSectionResponse::from(section);
+        response.content_html = content_html;
+        if let Some((lang, translation)) = langs.pick(section_translations.get(&response.id)) {
+            response.lang = Some(lang.to_string());
+            response.title = translation.title.clone();
+            response.content = translation.content.clone();
+            response.content_html = html(&translation.content, &translation.content_html);
+        }
+        sections_by_lesson
+            .entry(lesson_id)
+            .or_default()
+            .push(response);
+    }
+
+    Ok(lessons
+        .into_iter()
+        .map(|lesson| {
+            let content_html = html(&lesson.content, &lesson.content_html);
+            let mut response = LessonResponse::from(lesson);
+            response.content_html = content_html;
+            if let Some((lang, translation)) = langs.pick(translations.get(&response.id)) {
+                response.lang = Some(lang.to_string());
+                response.content = translation.content.clone();
+                response.content_html = html(&translation.content, &translation.content_html);
+                if let Some(title) = &translation.title {
+                    response.title = title.clone();
+                }
+            }
+            response.sections = sections_by_lesson.remove(&response.id).unwrap_or_default();
+            response
+        })
+        .collect())
+}
+
+/// Titles of the lessons in the first available preferred language
+async fn lesson_summaries<C: ConnectionTrait>(
+    db: &C,
+    lessons: Vec<lessons::Model>,
+    langs: &PreferredLangs,
+) -> Result<Vec<LessonSummaryResponse>, DbErr> {
+    let translations = lesson_translation_map(db, lessons.iter().map(|l| l.id), langs).await?;
+
+    Ok(lessons
+        .into_iter()
+        .map(|lesson| {
+            let translated = langs
+                .pick(translations.get(&lesson.id))
+                .and_then(|(lang, t)| Some((lang.to_string(), t.title.clone()?)));
+            let (lang, title) = match translated {
+                Some((lang, title)) => (Some(lang), title),
+                None => (None, lesson.title),
+            };
+            LessonSummaryResponse {
+                id: lesson.id,
+                title,
+                lang,
+                position: lesson.position,
+                reading_minutes: lesson.reading_minutes,
+            }
+        })
+        .collect())
+}
+
+/// Published lessons of a topic in force for the region, in reading order
+async fn topic_lessons<C: ConnectionTrait>(
+    db: &C,
+    topic_id: Uuid,
+    region: Option<&str>,
+) -> Result<Vec<lessons::Model>, DbErr> {
+    lessons::Entity::find()
+        .filter(lessons::Column::TopicId.eq(topic_id))
+        .filter(lessons::Column::Status.eq("published"))
+        .filter(lessons_in_effect(region))
+        .order_by_asc(lessons::Column::Position)
+        .order_by_asc(lessons::Column::Title)
+        .all(db)
+        .await
+}
+
+/// Adds or replaces the translations of a lesson
+async fn save_translations<C: ConnectionTrait>(
+    db: &C,
+    lesson_id: Uuid,
+    translations: Vec<LessonTranslationParams>,
+) -> Result<(), DbErr> {
+    for translation in translations {
+        let lang = translation.lang.to_lowercase();
+        lesson_translations::Entity::delete_by_id((lesson_id, lang.clone()))
+            .exec(db)
+            .await?;
+        let content_html = render_markdown(db, &translation.content).await?;
+        lesson_translations::ActiveModel {
+            lesson_id: Set(lesson_id),
+            lang: Set(lang),
+            title: Set(translation.title),
+            content: Set(translation.content),
+            content_html: Set(Some(content_html)),
+        }
+        .insert(db)
+        .await?;
+    }
+    Ok(())
+}
+
+/// Checks translation languages and the references of every Markdown text
+async fn check_lesson_texts<C: ConnectionTrait>(
+    db: &C,
+    content: Option<&str>,
+    sections: &[LessonSectionParams],
+    translations: &[LessonTranslationParams],
+) -> Result<(), ApiError> {
+    check_translation_langs(translations.iter().map(|t| t.lang.as_str()))?;
+    for section in sections {
+        check_translation_langs(section.translations.iter().map(|t| t.lang.as_str()))?;
+    }
+
+    let sources: Vec<&str> = content
+        .into_iter()
+        .chain(translations.iter().map(|t| t.content.as_str()))
+        .chain(sections.iter().flat_map(|section| {
+            std::iter::once(section.content.as_str())
+                .chain(section.translations.iter().map(|t| t.content.as_str()))
+        }))
+        .collect();
+    check_markdown(db, sources).await
+}
+
+/// Replaces the sections of a lesson, numbered in the given order
+pub(crate) async fn replace_sections<C: ConnectionTrait>(
+    db: &C,
+    lesson_id: Uuid,
+    sections: Vec<LessonSectionParams>,
+) -> Result<(), DbErr> {
+    lesson_sections::Entity::delete_many()
+        .filter(lesson_sections::Column::LessonId.eq(lesson_id))
+        .exec(db)
+        .await?;
+
+    for (position, section) in (1..).zip(sections) {
+        let content_html = render_markdown(db, &section.content).await?;
+        let model = lesson_sections::ActiveModel {
+            lesson_id: Set(lesson_id),
+            position: Set(position),
+            title: Set(section.title),
+            content: Set(section.content),
+            content_html: Set(Some(content_html)),
+            ..Default::default()
+        }
+        .insert(db)
+        .await?;
+        for translation in section.translations {
+            let content_html = render_markdown(db, &translation.content).await?;
+            lesson_section_translations::ActiveModel {
+                section_id: Set(model.id),
+                lang: Set(translation.lang.to_lowercase()),
+                title: Set(translation.title),
+                content: Set(translation.content),
+                content_html: Set(Some(content_html)),
+            }
+            .insert(db)
+            .await?;
+        }
+    }
+    Ok(())
+}
+
+/// List published lessons in force for the user's region (requires auth, subscription check per topic)
 #[utoipa::path(
     get,
     tag = "Lessons",
     path = "/api/lessons",
+    params(ContentLangQuery),
     responses(
         (status = 200, body = Vec<LessonResponse>),
         ApiError
@@ -31,38 +324,48 @@ use validator::Validate;
     security(("jwt_token" = []))
 )]
 async fn list(
-    _auth_user: AuthUser,
+    auth_user: AuthUser,
+    langs: PreferredLangs,
     State(ctx): State<AppContext>,
 ) -> axum::response::Result<Response> {
     let lessons = lessons::Entity::find()
+        .filter(lessons::Column::Status.eq("published"))
+        .filter(lessons_in_effect(auth_user.user.region.as_deref()))
+        .order_by_asc(lessons::Column::TopicId)
+        .order_by_asc(lessons::Column::Position)
         .all(&ctx.db)
         .await
-        .map_err(ApiError::from)?
-        .into_iter()
-        .map(LessonResponse::from)
-        .collect::<Vec<_>>();
+        .map_err(ApiError::from)?;
+    let lessons = lesson_responses(&ctx.db, lessons, &langs)
+        .await
+        .map_err(ApiError::from)?;
 
     Ok(Json(lessons).into_response())
 }
 
-/// Get lesson by id (requires auth, subscription if topic requires)
+/// Get published lesson by id with the previous and next lesson of its topic (requires auth, subscription if topic requires)
 #[utoipa::path(
     get,
     tag = "Lessons",
     path = "/api/lessons/{id}",
-    params(("id" = Uuid, Path, description = "Lesson ID")),
+    params(
+        ("id" = Uuid, Path, description = "Lesson ID"),
+        ContentLangQuery
+    ),
     responses(
-        (status = 200, body = LessonResponse),
+        (status = 200, body = LessonDetailResponse),
         ApiError
     ),
     security(("jwt_token" = []))
 )]
 async fn get(
     auth_user: AuthUser,
+    langs: PreferredLangs,
     Path(id): Path<Uuid>,
     State(ctx): State<AppContext>,
 ) -> axum::response::Result<Response> {
     let lesson = lessons::Entity::find_by_id(id)
+        .filter(lessons::Column::Status.eq("published"))
         .one(&ctx.db)
         .await
         .map_err(ApiError::from)?
@@ -70,8 +373,39 @@ async fn get(
 
     // Проверяем доступ к topic
     check_topic_access_by_id(&ctx.db, auth_user.user.id, lesson.topic_id).await?;
+    check_unlocked(&ctx.db, auth_user.user.id, lesson.topic_id).await?;
 
-    Ok(Json(LessonResponse::from(lesson)).into_response())
+    // Соседи считаются среди уроков, которые видит пользователь
+    let siblings = topic_lessons(&ctx.db, lesson.topic_id, auth_user.user.region.as_deref())
+        .await
+        .map_err(ApiError::from)?;
+    let index = siblings.iter().position(|l| l.id == id);
+    let previous_id = index.and_then(|i| i.checked_sub(1)).map(|i| siblings[i].id);
+    let next_id = index.and_then(|i| siblings.get(i + 1)).map(|l| l.id);
+    let neighbours = siblings
+        .into_iter()
+        .filter(|l| Some(l.id) == previous_id || Some(l.id) == next_id)
+        .collect();
+    let mut neighbours = lesson_summaries(&ctx.db, neighbours, &langs)
+        .await
+        .map_err(ApiError::from)?;
+    let next = match neighbours.last() {
+        Some(l) if Some(l.id) == next_id => neighbours.pop(),
+        _ => None,
+    };
+    let previous = neighbours.pop();
+
+    let lesson = lesson_responses(&ctx.db, vec![lesson], &langs)
+        .await
+        .map_err(ApiError::from)?
+        .remove(0);
+
+    Ok(Json(LessonDetailResponse {
+        lesson,
+        previous,
+        next,
+    })
+    .into_response())
 }
 
 /// Create lesson (requires auth, subscription if topic requires)
@@ -95,20 +429,64 @@ async fn create(
 
     // Проверяем доступ к topic
     check_topic_access_by_id(&ctx.db, auth_user.user.id, params.topic_id).await?;
+    check_validity(params.valid_from, params.valid_to)?;
+    check_lesson_texts(
+        &ctx.db,
+        Some(&params.content),
+        &params.sections,
+        &params.translations,
+    )
+    .await?;
+
+    let reading_minutes = params.reading_minutes.unwrap_or_else(|| {
+        estimate_reading_minutes(
+            std::iter::once(params.content.as_str())
+                .chain(params.sections.iter().map(|s| s.content.as_str())),
+        )
+    });
+
+    let txn = ctx.db.begin().await.map_err(ApiError::from)?;
+
+    let position = match params.position {
+        Some(position) => position,
+        None => next_position(&txn, params.topic_id)
+            .await
+            .map_err(ApiError::from)?,
+    };
+
+    let content_html = render_markdown(&txn, &params.content)
+        .await
+        .map_err(ApiError::from)?;
 
     let lesson = lessons::ActiveModel {
         topic_id: Set(params.topic_id),
+        title: Set(params.title),
+        position: Set(position),
+        reading_minutes: Set(reading_minutes),
         content: Set(params.content),
+        content_html: Set(Some(content_html)),
+        valid_from: Set(params.valid_from),
+        valid_to: Set(params.valid_to),
+        region: Set(params.region),
         ..Default::default()
     };
 
-    let lesson = lesson.insert(&ctx.db).await.map_err(ApiError::from)?;
+    let lesson = lesson.insert(&txn).await.map_err(ApiError::from)?;
+    replace_sections(&txn, lesson.id, params.sections)
+        .await
+        .map_err(ApiError::from)?;
+    save_translations(&txn, lesson.id, params.translations)
+        .await
+        .map_err(ApiError::from)?;
 
-    Ok((
-        axum::http::StatusCode::CREATED,
-        Json(LessonResponse::from(lesson)),
-    )
-        .into_response())
+    txn.commit().await.map_err(ApiError::from)?;
+
+    let lesson = lesson_responses(&ctx.db, vec![lesson], &PreferredLangs::default())
+        .await
+        .map_err(ApiError::from)?
+        .remove(0);
+
+    Ok((axum::http::StatusCode::CREATED, Json(lesson)).into_response())
 }
 
 /// Update lesson by id (requires auth, subscription if topic requires)
@@ -141,20 +519,103 @@ async fn update(
     // Проверяем доступ к текущему topic
     check_topic_access_by_id(&ctx.db, auth_user.user.id, lesson.topic_id).await?;
 
+    check_validity(
+        params.valid_from.or(lesson.valid_from),
+        params.valid_to.or(lesson.valid_to),
+    )?;
+    check_lesson_texts(
+        &ctx.db,
+        params.content.as_deref(),
+        params.sections.as_deref().unwrap_or_default(),
+        params.translations.as_deref().unwrap_or_default(),
+    )
+    .await?;
+
+    // Время чтения пересчитывается, если текст изменился и не задан явно
+    let reading_minutes = match params.reading_minutes {
+        Some(minutes) => Some(minutes),
+        None if params.content.is_some() || params.sections.is_some() => {
+            let section_texts: Vec<String> = match &params.sections {
+                Some(sections) => sections.iter().map(|s| s.content.clone()).collect(),
+                None => lesson_sections::Entity::find()
+                    .filter(lesson_sections::Column::LessonId.eq(id))
+                    .all(&ctx.db)
+                    .await
+                    .map_err(ApiError::from)?
+                    .into_iter()
+                    .map(|s| s.content)
+                    .collect(),
+            };
+            let content = params.content.as_deref().unwrap_or(&lesson.content);
+            Some(estimate_reading_minutes(
+                std::iter::once(content).chain(section_texts.iter().map(String::as_str)),
+            ))
+        }
+        None => None,
+    };
+
+    let txn = ctx.db.begin().await.map_err(ApiError::from)?;
+
+    let current_topic = lesson.topic_id;
     let mut to_update = lesson.into_active_model();
 
     if let Some(topic_id) = params.topic_id {
         // Проверяем доступ к новому topic
         check_topic_access_by_id(&ctx.db, auth_user.user.id, topic_id).await?;
         to_update.topic_id = Set(topic_id);
+        if topic_id != current_topic && params.position.is_none() {
+            let position = next_position(&txn, topic_id)
+                .await
+                .map_err(ApiError::from)?;
+            to_update.position = Set(position);
+        }
+    }
+    if let Some(title) = params.title {
+        to_update.title = Set(title);
+    }
+    if let Some(position) = params.position {
+        to_update.position = Set(position);
+    }
+    if let Some(reading_minutes) = reading_minutes {
+        to_update.reading_minutes = Set(reading_minutes);
     }
     if let Some(content) = params.content {
+        let content_html = render_markdown(&txn, &content)
+            .await
+            .map_err(ApiError::from)?;
         to_update.content = Set(content);
+        to_update.content_html = Set(Some(content_html));
+    }
+    if params.valid_from.is_some() {
+        to_update.valid_from = Set(params.valid_from);
+    }
+    if params.valid_to.is_some() {
+        to_update.valid_to = Set(params.valid_to);
+    }
+    if let Some(region) = params.region {
+        to_update.region = Set(Some(region).filter(|region| !region.is_empty()));
+    }
+
+    let lesson = to_update.update(&txn).await.map_err(ApiError::from)?;
+    if let Some(sections) = params.sections {
+        replace_sections(&txn, lesson.id, sections)
+            .await
+            .map_err(ApiError::from)?;
     }
+    if let Some(translations) = params.translations {
+        save_translations(&txn, lesson.id, translations)
+            .await
+            .map_err(ApiError::from)?;
+    }
+
+    txn.commit().await.map_err(ApiError::from)?;
 
-    let lesson = to_update.update(&ctx.db).await.map_err(ApiError::from)?;
+    let lesson = lesson_responses(&ctx.db, vec![lesson], &PreferredLangs::default())
+        .await
+        .map_err(ApiError::from)?
+        .remove(0);
 
-    Ok(Json(LessonResponse::from(lesson)).into_response())
+    Ok(Json(lesson).into_response())
 }
 
 /// Delete lesson by id (requires auth, subscription if topic requires)
@@ -191,20 +652,24 @@ async fn delete(
     Ok(().into_response())
 }
 
-/// Get lesson by topic id (requires auth, subscription if topic requires)
+/// List published lessons of a topic in reading order (requires auth, subscription if topic requires)
 #[utoipa::path(
     get,
     tag = "Lessons",
-    path = "/api/topics/{topic_id}/lesson",
-    params(("topic_id" = Uuid, Path, description = "Topic ID")),
+    path = "/api/topics/{topic_id}/lessons",
+    params(
+        ("topic_id" = Uuid, Path, description = "Topic ID"),
+        ContentLangQuery
+    ),
     responses(
-        (status = 200, body = LessonResponse),
+        (status = 200, body = TopicLessonsResponse),
         ApiError
     ),
     security(("jwt_token" = []))
 )]
-async fn get_by_topic(
+async fn list_by_topic(
     auth_user: AuthUser,
+    langs: PreferredLangs,
     Path(topic_id): Path<Uuid>,
     State(ctx): State<AppContext>,
 ) -> axum::response::Result<Response> {
@@ -216,15 +681,21 @@ async fn get_by_topic(
         .ok_or(ApiError::NotFound)?;
 
     check_topic_access(&ctx.db, auth_user.user.id, &topic).await?;
+    check_unlocked(&ctx.db, auth_user.user.id, topic.id).await?;
 
-    let lesson = lessons::Entity::find()
-        .filter(lessons::Column::TopicId.eq(topic_id))
-        .one(&ctx.db)
+    let lessons = topic_lessons(&ctx.db, topic_id, auth_user.user.region.as_deref())
         .await
-        .map_err(ApiError::from)?
-        .ok_or(ApiError::NotFound)?;
+        .map_err(ApiError::from)?;
+    let lessons = lesson_summaries(&ctx.db, lessons, &langs)
+        .await
+        .map_err(ApiError::from)?;
 
-    Ok(Json(LessonResponse::from(lesson)).into_response())
+    Ok(Json(TopicLessonsResponse {
+        topic_id,
+        reading_minutes: lessons.iter().map(|l| i32::from(l.reading_minutes)).sum(),
+        lessons,
+    })
+    .into_response())
 }
 
 pub fn routes() -> OpenApiRouter<AppContext> {
@@ -234,5 +705,5 @@ pub fn routes() -> OpenApiRouter<AppContext> {
         .routes(routes!(create))
         .routes(routes!(update))
         .routes(routes!(delete))
-        .routes(routes!(get_by_topic))
+        .routes(routes!(list_by_topic))
 }
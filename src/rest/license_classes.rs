@@ -0,0 +1,141 @@
+use crate::{
+    AppContext,
+    entities::{license_classes, question_license_classes, questions},
+    models::license_classes::LicenseClassResponse,
+    utils::{extractors::AuthUser, response::ApiError},
+};
+use axum::{
+    Json,
+    extract::State,
+    response::{IntoResponse, Response},
+};
+use sea_orm::{
+    ActiveModelTrait, ActiveValue::Set, ColumnTrait, Condition, ConnectionTrait, DbErr,
+    EntityTrait, PaginatorTrait, QueryFilter, QueryOrder, sea_query,
+};
+use std::collections::{HashMap, HashSet};
+use utoipa_axum::{router::OpenApiRouter, routes};
+use uuid::Uuid;
+
+/// Questions that apply to the class: tagged with it or not tagged at all.
+/// Without a class nothing is filtered out.
+pub(crate) fn license_class_condition(license_class: Option<&str>) -> Condition {
+    let Some(license_class) = license_class else {
+        return Condition::all();
+    };
+
+    let tagged = sea_query::Query::select()
+        .column(question_license_classes::Column::QuestionId)
+        .from(question_license_classes::Entity)
+        .to_owned();
+    let tagged_with_class = sea_query::Query::select()
+        .column(question_license_classes::Column::QuestionId)
+        .from(question_license_classes::Entity)
+        .and_where(question_license_classes::Column::LicenseClass.eq(license_class))
+        .to_owned();
+
+    Condition::any()
+        .add(questions::Column::Id.not_in_subquery(tagged))
+        .add(questions::Column::Id.in_subquery(tagged_with_class))
+}
+
+/// Fails with `InvalidFieldValue` if any of the codes is not a known class
+pub(crate) async fn check_license_classes_exist<C: ConnectionTrait>(
+    db: &C,
+    codes: &[String],
+) -> Result<(), ApiError> {
+    if codes.is_empty() {
+        return Ok(());
+    }
+
+    let unique: HashSet<&String> = codes.iter().collect();
+    let found = license_classes::Entity::find()
+        .filter(license_classes::Column::Code.is_in(unique.iter().map(|c| c.as_str())))
+        .count(db)
+        .await
+        .map_err(ApiError::from)?;
+
+    if found as usize != unique.len() {
+        return Err(ApiError::InvalidFieldValue);
+    }
+
+    Ok(())
+}
+
+/// Class codes of each question, questions without tags are absent
+pub(crate) async fn question_license_classes<C: ConnectionTrait>(
+    db: &C,
+    question_ids: &[Uuid],
+) -> Result<HashMap<Uuid, Vec<String>>, DbErr> {
+    let mut classes: HashMap<Uuid, Vec<String>> = HashMap::new();
+    if question_ids.is_empty() {
+        return Ok(classes);
+    }
+
+    for link in question_license_classes::Entity::find()
+        .filter(question_license_classes::Column::QuestionId.is_in(question_ids.iter().cloned()))
+        .order_by_asc(question_license_classes::Column::LicenseClass)
+        .all(db)
+        .await?
+    {
+        classes
+            .entry(link.question_id)
+            .or_default()
+            .push(link.license_class);
+    }
+    Ok(classes)
+}
+
+/// Replaces the class tags of a question
+pub(crate) async fn set_question_license_classes<C: ConnectionTrait>(
+    db: &C,
+    question_id: Uuid,
+    codes: &[String],
+) -> Result<(), DbErr> {
+    question_license_classes::Entity::delete_many()
+        .filter(question_license_classes::Column::QuestionId.eq(question_id))
+        .exec(db)
+        .await?;
+
+    let unique: HashSet<&String> = codes.iter().collect();
+    for code in unique {
+        question_license_classes::ActiveModel {
+            question_id: Set(question_id),
+            license_class: Set(code.clone()),
+        }
+        .insert(db)
+        .await?;
+    }
+    Ok(())
+}
+
+/// List licence classes
+#[utoipa::path(
+    get,
+    tag = "License Classes",
+    path = "/api/license-classes",
+    responses(
+        (status = 200, body = Vec<LicenseClassResponse>),
+        ApiError
+    ),
+    security(("jwt_token" = []))
+)]
+async fn list(
+    _auth_user: AuthUser,
+    State(ctx): State<AppContext>,
+) -> axum::response::Result<Response> {
+    let classes = license_classes::Entity::find()
+        .order_by_asc(license_classes::Column::Position)
+        .all(&ctx.db)
+        .await
+        .map_err(ApiError::from)?
+        .into_iter()
+        .map(LicenseClassResponse::from)
+        .collect::<Vec<_>>();
+
+    Ok(Json(classes).into_response())
+}
+
+pub fn routes() -> OpenApiRouter<AppContext> {
+    OpenApiRouter::new().routes(routes!(list))
+}
@@ -1,10 +1,28 @@
 pub mod answers;
 pub mod auth;
 pub mod categories;
+pub mod classroom_sessions;
+pub mod content_reviews;
+pub mod curriculum;
+pub mod daily_challenges;
+pub mod duels;
 pub mod images;
+pub mod lesson_progress;
 pub mod lessons;
+pub mod license_classes;
+pub mod notifications;
 pub mod question_categories;
+pub mod question_export;
+pub mod question_import;
+pub mod question_reports;
+pub mod question_revisions;
+pub mod question_translations;
 pub mod questions;
+pub mod regions;
+pub mod road_sign_links;
+pub mod road_signs;
+pub mod search;
+pub mod test_templates;
 pub mod tests;
 pub mod topics;
 pub mod user_favorite_questions;
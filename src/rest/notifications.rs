@@ -0,0 +1,113 @@
+use crate::{
+    AppContext,
+    entities::notifications,
+    models::notifications::{NotificationResponse, NotificationsQuery},
+    utils::{extractors::AuthUser, response::ApiError},
+};
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+    response::{IntoResponse, Response},
+};
+use sea_orm::{
+    ActiveModelTrait, ActiveValue::Set, ColumnTrait, ConnectionTrait, DbErr, EntityTrait,
+    IntoActiveModel, QueryFilter, QueryOrder,
+};
+use utoipa_axum::{router::OpenApiRouter, routes};
+use uuid::Uuid;
+
+/// Leaves an in-app notification for a user
+pub(crate) async fn notify<C: ConnectionTrait>(
+    db: &C,
+    user_id: Uuid,
+    kind: &str,
+    message: String,
+    reference_id: Option<Uuid>,
+) -> Result<(), DbErr> {
+    notifications::ActiveModel {
+        user_id: Set(user_id),
+        kind: Set(kind.to_string()),
+        message: Set(message),
+        reference_id: Set(reference_id),
+        ..Default::default()
+    }
+    .insert(db)
+    .await?;
+    Ok(())
+}
+
+/// Notifications of the current user, newest first
+#[utoipa::path(
+    get,
+    tag = "Notifications",
+    path = "/api/notifications",
+    params(NotificationsQuery),
+    responses(
+        (status = 200, body = Vec<NotificationResponse>),
+        ApiError
+    ),
+    security(("jwt_token" = []))
+)]
+async fn list(
+    auth_user: AuthUser,
+    State(ctx): State<AppContext>,
+    Query(query): Query<NotificationsQuery>,
+) -> axum::response::Result<Response> {
+    let mut select =
+        notifications::Entity::find().filter(notifications::Column::UserId.eq(auth_user.user.id));
+    if query.unread {
+        select = select.filter(notifications::Column::ReadAt.is_null());
+    }
+
+    let notifications_list: Vec<NotificationResponse> = select
+        .order_by_desc(notifications::Column::CreatedAt)
+        .all(&ctx.db)
+        .await
+        .map_err(ApiError::from)?
+        .into_iter()
+        .map(NotificationResponse::from)
+        .collect();
+
+    Ok(Json(notifications_list).into_response())
+}
+
+/// Mark a notification as read
+#[utoipa::path(
+    post,
+    tag = "Notifications",
+    path = "/api/notifications/{id}/read",
+    params(("id" = Uuid, Path, description = "Notification ID")),
+    responses(
+        (status = 200, body = NotificationResponse),
+        ApiError
+    ),
+    security(("jwt_token" = []))
+)]
+async fn mark_read(
+    auth_user: AuthUser,
+    Path(id): Path<Uuid>,
+    State(ctx): State<AppContext>,
+) -> axum::response::Result<Response> {
+    let notification = notifications::Entity::find_by_id(id)
+        .filter(notifications::Column::UserId.eq(auth_user.user.id))
+        .one(&ctx.db)
+        .await
+        .map_err(ApiError::from)?
+        .ok_or(ApiError::NotFound)?;
+
+    let notification = if notification.read_at.is_none() {
+        let mut to_update = notification.into_active_model();
+        to_update.read_at = Set(Some(chrono::Utc::now().into()));
+        to_update.update(&ctx.db).await.map_err(ApiError::from)?
+    } else {
+        notification
+    };
+
+    Ok(Json(NotificationResponse::from(notification)).into_response())
+}
+
+pub fn routes() -> OpenApiRouter<AppContext> {
+    OpenApiRouter::new()
+        .routes(routes!(list))
+        .routes(routes!(mark_read))
+}
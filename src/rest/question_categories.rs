@@ -2,6 +2,7 @@ use crate::{
     AppContext,
     entities::{categories, question_categories, questions},
     models::{categories::CategoryResponse, question_categories::QuestionCategoryResponse},
+    rest::{content_reviews::reopen_edited_question, question_revisions::record_revision},
     utils::{extractors::AuthUser, response::ApiError},
 };
 use axum::{
@@ -11,6 +12,7 @@ use axum::{
 };
 use sea_orm::{
     ActiveModelTrait, ActiveValue::Set, ColumnTrait, EntityTrait, ModelTrait, QueryFilter,
+    TransactionTrait,
 };
 use utoipa_axum::{router::OpenApiRouter, routes};
 use uuid::Uuid;
@@ -76,7 +78,7 @@ async fn get_question_categories(
     security(("jwt_token" = []))
 )]
 async fn add_category_to_question(
-    _auth_user: AuthUser,
+    auth_user: AuthUser,
     Path((question_id, category_id)): Path<(Uuid, Uuid)>,
     State(ctx): State<AppContext>,
 ) -> axum::response::Result<Response> {
@@ -99,7 +101,13 @@ async fn add_category_to_question(
         category_id: Set(category_id),
     };
 
-    link.insert(&ctx.db).await.map_err(ApiError::from)?;
+    let txn = ctx.db.begin().await.map_err(ApiError::from)?;
+    link.insert(&txn).await.map_err(ApiError::from)?;
+    reopen_edited_question(&txn, question_id, &auth_user.user).await?;
+    record_revision(&txn, question_id, Some(auth_user.user.id), "categories")
+        .await
+        .map_err(ApiError::from)?;
+    txn.commit().await.map_err(ApiError::from)?;
 
     Ok((
         axum::http::StatusCode::CREATED,
@@ -127,7 +135,7 @@ async fn add_category_to_question(
     security(("jwt_token" = []))
 )]
 async fn remove_category_from_question(
-    _auth_user: AuthUser,
+    auth_user: AuthUser,
     Path((question_id, category_id)): Path<(Uuid, Uuid)>,
     State(ctx): State<AppContext>,
 ) -> axum::response::Result<Response> {
@@ -137,7 +145,13 @@ async fn remove_category_from_question(
         .map_err(ApiError::from)?
         .ok_or(ApiError::NotFound)?;
 
-    link.delete(&ctx.db).await.map_err(ApiError::from)?;
+    let txn = ctx.db.begin().await.map_err(ApiError::from)?;
+    link.delete(&txn).await.map_err(ApiError::from)?;
+    reopen_edited_question(&txn, question_id, &auth_user.user).await?;
+    record_revision(&txn, question_id, Some(auth_user.user.id), "categories")
+        .await
+        .map_err(ApiError::from)?;
+    txn.commit().await.map_err(ApiError::from)?;
 
     Ok(().into_response())
 }
@@ -0,0 +1,60 @@
+use crate::{
+    AppContext,
+    models::question_import::ExportQuery,
+    tasks::question_export::{ExportFilter, ExportFormat, export_stream},
+    utils::{extractors::AdminUser, response::ApiError},
+};
+use axum::{
+    body::Body,
+    extract::{Query, State},
+    http::{StatusCode, header},
+    response::Response,
+};
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+/// Export the question bank in the import format.
+/// JSON also includes topics and lessons, CSV holds only questions.
+#[utoipa::path(
+    get,
+    tag = "Questions",
+    path = "/api/questions/export",
+    params(ExportQuery),
+    responses(
+        (status = 200, content_type = "application/json"),
+        (status = 200, content_type = "text/csv"),
+        ApiError
+    ),
+    security(("jwt_token" = []))
+)]
+async fn export(
+    _admin: AdminUser,
+    State(ctx): State<AppContext>,
+    Query(query): Query<ExportQuery>,
+) -> axum::response::Result<Response> {
+    let format: ExportFormat = query.format.parse().map_err(|_| ApiError::InvalidFormat)?;
+
+    let filter = ExportFilter {
+        lang: query.lang,
+        topic_id: query.topic_id,
+        status: query.status,
+    };
+
+    let body = Body::from_stream(export_stream(ctx.db.clone(), format, filter));
+
+    Ok(Response::builder()
+        .status(StatusCode::OK)
+        .header(header::CONTENT_TYPE, format.content_type())
+        .header(
+            header::CONTENT_DISPOSITION,
+            format!(
+                "attachment; filename=\"question-bank.{}\"",
+                format.extension()
+            ),
+        )
+        .body(body)
+        .unwrap())
+}
+
+pub fn routes() -> OpenApiRouter<AppContext> {
+    OpenApiRouter::new().routes(routes!(export))
+}
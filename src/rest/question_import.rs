@@ -0,0 +1,53 @@
+use crate::{
+    AppContext,
+    models::question_import::{ImportQuery, ImportReport},
+    tasks::question_import::{import_records, parse_records},
+    utils::{extractors::AdminUser, response::ApiError},
+};
+use axum::{
+    Json,
+    extract::{DefaultBodyLimit, Query, State},
+    response::{IntoResponse, Response},
+};
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+/// Spreadsheets of a whole question bank exceed the default body limit
+const IMPORT_BODY_LIMIT: usize = 32 * 1024 * 1024;
+
+/// Import questions with answers and categories from CSV or JSON.
+/// Without `commit` only the validation report is returned.
+#[utoipa::path(
+    post,
+    tag = "Questions",
+    path = "/api/questions/import",
+    params(ImportQuery),
+    request_body(content = String, content_type = "text/plain"),
+    responses(
+        (status = 200, body = ImportReport),
+        ApiError
+    ),
+    security(("jwt_token" = []))
+)]
+async fn import(
+    admin: AdminUser,
+    State(ctx): State<AppContext>,
+    Query(query): Query<ImportQuery>,
+    body: String,
+) -> axum::response::Result<Response> {
+    let parsed = parse_records(&query.format, &body).map_err(|err| {
+        tracing::warn!("question import: {err}");
+        ApiError::InvalidFormat
+    })?;
+
+    let report = import_records(&ctx.db, parsed, query.commit, Some(admin.user.id))
+        .await
+        .map_err(ApiError::from)?;
+
+    Ok(Json(report).into_response())
+}
+
+pub fn routes() -> OpenApiRouter<AppContext> {
+    OpenApiRouter::new()
+        .routes(routes!(import))
+        .layer(DefaultBodyLimit::max(IMPORT_BODY_LIMIT))
+}
@@ -0,0 +1,282 @@
+use crate::{
+    AppContext,
+    entities::{answers, question_reports, questions},
+    models::question_reports::{
+        QuestionReportCountResponse, QuestionReportResponse, ReportQuestionParams,
+        ReportQueueQuery, ResolveReportParams,
+    },
+    rest::{curriculum::check_unlocked, notifications::notify},
+    utils::{
+        extractors::{AdminUser, AuthUser, check_topic_access_by_id},
+        response::ApiError,
+    },
+};
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+    response::{IntoResponse, Response},
+};
+use sea_orm::{
+    ActiveModelTrait, ActiveValue::Set, ColumnTrait, DbBackend, EntityTrait, FromQueryResult,
+    IntoActiveModel, QueryFilter, QueryOrder, Statement, TransactionTrait,
+};
+use utoipa_axum::{router::OpenApiRouter, routes};
+use uuid::Uuid;
+use validator::Validate;
+
+const REASONS: [&str; 6] = [
+    "wrong_answer",
+    "ambiguous",
+    "outdated",
+    "typo",
+    "translation",
+    "other",
+];
+
+/// Moderation statuses of reports
+const STATUSES: [&str; 4] = ["open", "accepted", "rejected", "fixed"];
+
+/// Statuses after which the reporter is notified
+const RESOLVED: [&str; 2] = ["rejected", "fixed"];
+
+/// Reports per question, most unresolved first
+const REPORT_COUNTS: &str = r#"
+    SELECT question_id,
+           count(*) FILTER (WHERE status IN ('open', 'accepted')) AS unresolved,
+           count(*) AS total
+    FROM question_reports
+    GROUP BY question_id
+    ORDER BY unresolved DESC, total DESC"#;
+
+#[derive(Debug, FromQueryResult)]
+struct ReportCountRow {
+    question_id: Uuid,
+    unresolved: i64,
+    total: i64,
+}
+
+/// Whether an editor may move a report from `status` to `next`
+fn can_resolve(status: &str, next: &str) -> bool {
+    matches!(
+        (status, next),
+        ("open", "accepted" | "rejected" | "fixed") | ("accepted", "rejected" | "fixed")
+    )
+}
+
+/// Report a wrong or ambiguous question or answer option
+#[utoipa::path(
+    post,
+    tag = "Questions",
+    path = "/api/questions/{id}/reports",
+    params(("id" = Uuid, Path, description = "Question ID")),
+    request_body = ReportQuestionParams,
+    responses(
+        (status = 201, body = QuestionReportResponse),
+        ApiError
+    ),
+    security(("jwt_token" = []))
+)]
+async fn report(
+    auth_user: AuthUser,
+    Path(id): Path<Uuid>,
+    State(ctx): State<AppContext>,
+    Json(params): Json<ReportQuestionParams>,
+) -> axum::response::Result<Response> {
+    params.validate().map_err(ApiError::from)?;
+    if !REASONS.contains(&params.reason.as_str()) {
+        return Err(ApiError::InvalidFieldValue.into());
+    }
+
+    let question = questions::Entity::find_by_id(id)
+        .filter(questions::Column::Status.eq("published"))
+        .one(&ctx.db)
+        .await
+        .map_err(ApiError::from)?
+        .ok_or(ApiError::NotFound)?;
+    check_topic_access_by_id(&ctx.db, auth_user.user.id, question.topic_id).await?;
+    check_unlocked(&ctx.db, auth_user.user.id, question.topic_id).await?;
+
+    if let Some(answer_id) = params.answer_id {
+        answers::Entity::find_by_id(answer_id)
+            .filter(answers::Column::QuestionId.eq(id))
+            .one(&ctx.db)
+            .await
+            .map_err(ApiError::from)?
+            .ok_or(ApiError::InvalidFieldValue)?;
+    }
+
+    // Одна открытая жалоба пользователя на вопрос
+    let existing = question_reports::Entity::find()
+        .filter(question_reports::Column::QuestionId.eq(id))
+        .filter(question_reports::Column::UserId.eq(auth_user.user.id))
+        .filter(question_reports::Column::Status.is_in(["open", "accepted"]))
+        .one(&ctx.db)
+        .await
+        .map_err(ApiError::from)?;
+    if existing.is_some() {
+        return Err(ApiError::AlreadyExists.into());
+    }
+
+    let report = question_reports::ActiveModel {
+        question_id: Set(id),
+        answer_id: Set(params.answer_id),
+        user_id: Set(Some(auth_user.user.id)),
+        reason: Set(params.reason),
+        comment: Set(params.comment),
+        ..Default::default()
+    }
+    .insert(&ctx.db)
+    .await
+    .map_err(ApiError::from)?;
+
+    Ok((
+        axum::http::StatusCode::CREATED,
+        Json(QuestionReportResponse::from(report)),
+    )
+        .into_response())
+}
+
+/// Reports in a moderation status, oldest first (editors only)
+#[utoipa::path(
+    get,
+    tag = "Content Review",
+    path = "/api/review/reports",
+    params(ReportQueueQuery),
+    responses(
+        (status = 200, body = Vec<QuestionReportResponse>),
+        ApiError
+    ),
+    security(("jwt_token" = []))
+)]
+async fn queue(
+    _admin: AdminUser,
+    State(ctx): State<AppContext>,
+    Query(query): Query<ReportQueueQuery>,
+) -> axum::response::Result<Response> {
+    let status = query.status.as_deref().unwrap_or("open");
+    if !STATUSES.contains(&status) {
+        return Err(ApiError::InvalidFieldValue.into());
+    }
+
+    let mut select =
+        question_reports::Entity::find().filter(question_reports::Column::Status.eq(status));
+    if let Some(question_id) = query.question_id {
+        select = select.filter(question_reports::Column::QuestionId.eq(question_id));
+    }
+
+    let reports: Vec<QuestionReportResponse> = select
+        .order_by_asc(question_reports::Column::CreatedAt)
+        .all(&ctx.db)
+        .await
+        .map_err(ApiError::from)?
+        .into_iter()
+        .map(QuestionReportResponse::from)
+        .collect();
+
+    Ok(Json(reports).into_response())
+}
+
+/// Report counts per question, most unresolved first (editors only)
+#[utoipa::path(
+    get,
+    tag = "Content Review",
+    path = "/api/review/reports/counts",
+    responses(
+        (status = 200, body = Vec<QuestionReportCountResponse>),
+        ApiError
+    ),
+    security(("jwt_token" = []))
+)]
+async fn counts(
+    _admin: AdminUser,
+    State(ctx): State<AppContext>,
+) -> axum::response::Result<Response> {
+    let counts: Vec<QuestionReportCountResponse> = ReportCountRow::find_by_statement(
+        Statement::from_string(DbBackend::Postgres, REPORT_COUNTS),
+    )
+    .all(&ctx.db)
+    .await
+    .map_err(ApiError::from)?
+    .into_iter()
+    .map(|row| QuestionReportCountResponse {
+        question_id: row.question_id,
+        unresolved: row.unresolved,
+        total: row.total,
+    })
+    .collect();
+
+    Ok(Json(counts).into_response())
+}
+
+/// Accept, reject or mark a report fixed; the reporter is notified once it
+/// is rejected or fixed (editors only)
+#[utoipa::path(
+    post,
+    tag = "Content Review",
+    path = "/api/review/reports/{id}",
+    params(("id" = Uuid, Path, description = "Report ID")),
+    request_body = ResolveReportParams,
+    responses(
+        (status = 200, body = QuestionReportResponse),
+        ApiError
+    ),
+    security(("jwt_token" = []))
+)]
+async fn resolve(
+    admin: AdminUser,
+    Path(id): Path<Uuid>,
+    State(ctx): State<AppContext>,
+    Json(params): Json<ResolveReportParams>,
+) -> axum::response::Result<Response> {
+    params.validate().map_err(ApiError::from)?;
+    if !STATUSES.contains(&params.status.as_str()) {
+        return Err(ApiError::InvalidFieldValue.into());
+    }
+    if params.status == "rejected" && params.comment.is_none() {
+        return Err(ApiError::MissingField.into());
+    }
+
+    let txn = ctx.db.begin().await.map_err(ApiError::from)?;
+
+    let report = question_reports::Entity::find_by_id(id)
+        .one(&txn)
+        .await
+        .map_err(ApiError::from)?
+        .ok_or(ApiError::NotFound)?;
+    if !can_resolve(&report.status, &params.status) {
+        return Err(ApiError::InvalidState.into());
+    }
+
+    let resolved = RESOLVED.contains(&params.status.as_str());
+    let mut to_update = report.into_active_model();
+    to_update.status = Set(params.status.clone());
+    to_update.resolution_comment = Set(params.comment.clone());
+    to_update.resolved_by = Set(Some(admin.user.id));
+    if resolved {
+        to_update.resolved_at = Set(Some(chrono::Utc::now().into()));
+    }
+    let report = to_update.update(&txn).await.map_err(ApiError::from)?;
+
+    if resolved && let Some(user_id) = report.user_id {
+        let mut message = format!("Your report on a question was {}", report.status);
+        if let Some(comment) = &params.comment {
+            message.push_str(": ");
+            message.push_str(comment);
+        }
+        notify(&txn, user_id, "question_report", message, Some(report.id))
+            .await
+            .map_err(ApiError::from)?;
+    }
+
+    txn.commit().await.map_err(ApiError::from)?;
+
+    Ok(Json(QuestionReportResponse::from(report)).into_response())
+}
+
+pub fn routes() -> OpenApiRouter<AppContext> {
+    OpenApiRouter::new()
+        .routes(routes!(report))
+        .routes(routes!(queue))
+        .routes(routes!(counts))
+        .routes(routes!(resolve))
+}
@@ -0,0 +1,438 @@
+use crate::{
+    AppContext,
+    entities::{
+        answers, categories, images, question_categories, question_revisions, questions, topics,
+    },
+    models::{
+        question_revisions::{
+            AnswerSnapshot, QuestionRevisionDiffResponse, QuestionRevisionResponse,
+            QuestionRevisionSummary, QuestionSnapshot, RevisionDiffQuery,
+        },
+        question_types::AnswerPayload,
+        questions::QuestionResponse,
+    },
+    rest::{
+        answers::check_answer_set,
+        content_reviews::log_review,
+        questions::{question_image_ids, question_response, set_question_images},
+    },
+    utils::{
+        extractors::{AdminUser, AuthUser, check_topic_access_by_id},
+        response::ApiError,
+    },
+};
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+    response::{IntoResponse, Response},
+};
+use sea_orm::{
+    ActiveModelTrait,
+    ActiveValue::{NotSet, Set},
+    ColumnTrait, ConnectionTrait, DbErr, EntityTrait, IntoActiveModel, QueryFilter, QueryOrder,
+    TransactionTrait,
+};
+use std::collections::HashSet;
+use utoipa_axum::{router::OpenApiRouter, routes};
+use uuid::Uuid;
+
+/// Current state of the question with its answers and categories
+async fn current_snapshot<C: ConnectionTrait>(
+    db: &C,
+    question_id: Uuid,
+) -> Result<Option<QuestionSnapshot>, DbErr> {
+    let Some(question) = questions::Entity::find_by_id(question_id).one(db).await? else {
+        return Ok(None);
+    };
+
+    let answers_list = answers::Entity::find()
+        .filter(answers::Column::QuestionId.eq(question_id))
+        .order_by_asc(answers::Column::Id)
+        .all(db)
+        .await?;
+
+    let category_ids = question_categories::Entity::find()
+        .filter(question_categories::Column::QuestionId.eq(question_id))
+        .order_by_asc(question_categories::Column::CategoryId)
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|qc| qc.category_id)
+        .collect();
+
+    let image_ids = question_image_ids(db, &[question_id])
+        .await?
+        .remove(&question_id)
+        .unwrap_or_default();
+
+    Ok(Some(QuestionSnapshot {
+        topic_id: question.topic_id,
+        name: question.name,
+        lang: question.lang,
+        content: question.content,
+        explanation: question.explanation,
+        points: question.points,
+        image_ids,
+        question_type: question.question_type,
+        answers: answers_list
+            .into_iter()
+            .map(|a| AnswerSnapshot {
+                id: a.id,
+                payload: AnswerPayload::from_json(a.payload.as_ref()),
+                image_id: a.image_id,
+                value: a.value,
+                is_correct: a.is_correct,
+            })
+            .collect(),
+        category_ids,
+        valid_from: question.valid_from,
+        valid_to: question.valid_to,
+        region: question.region,
+    }))
+}
+
+/// Appends a revision with the current state of the question.
+/// Nothing is written when the question is gone or did not change.
+pub(crate) async fn record_revision<C: ConnectionTrait>(
+    db: &C,
+    question_id: Uuid,
+    author_id: Option<Uuid>,
+    action: &str,
+) -> Result<(), DbErr> {
+    let Some(snapshot) = current_snapshot(db, question_id).await? else {
+        return Ok(());
+    };
+
+    let latest = question_revisions::Entity::find()
+        .filter(question_revisions::Column::QuestionId.eq(question_id))
+        .order_by_desc(question_revisions::Column::Revision)
+        .one(db)
+        .await?;
+
+    if let Some(latest) = &latest
+        && serde_json::from_value::<QuestionSnapshot>(latest.snapshot.clone()).ok()
+            == Some(snapshot.clone())
+    {
+        return Ok(());
+    }
+
+    let revision = question_revisions::ActiveModel {
+        question_id: Set(question_id),
+        revision: Set(latest.map(|r| r.revision + 1).unwrap_or(1)),
+        author_id: Set(author_id),
+        action: Set(action.to_string()),
+        snapshot: Set(
+            serde_json::to_value(&snapshot).map_err(|err| DbErr::Custom(err.to_string()))?
+        ),
+        ..Default::default()
+    };
+    revision.insert(db).await?;
+
+    Ok(())
+}
+
+async fn find_revision<C: ConnectionTrait>(
+    db: &C,
+    question_id: Uuid,
+    revision: i32,
+) -> Result<(question_revisions::Model, QuestionSnapshot), ApiError> {
+    let model = question_revisions::Entity::find()
+        .filter(question_revisions::Column::QuestionId.eq(question_id))
+        .filter(question_revisions::Column::Revision.eq(revision))
+        .one(db)
+        .await
+        .map_err(ApiError::from)?
+        .ok_or(ApiError::NotFound)?;
+
+    let snapshot = serde_json::from_value::<QuestionSnapshot>(model.snapshot.clone())
+        .map_err(|_| ApiError::InternalServerError)?;
+
+    Ok((model, snapshot))
+}
+
+/// Checks the question exists and the user may access its topic
+async fn check_question_access(
+    ctx: &AppContext,
+    user_id: Uuid,
+    question_id: Uuid,
+) -> Result<questions::Model, ApiError> {
+    let question = questions::Entity::find_by_id(question_id)
+        .one(&ctx.db)
+        .await
+        .map_err(ApiError::from)?
+        .ok_or(ApiError::NotFound)?;
+
+    check_topic_access_by_id(&ctx.db, user_id, question.topic_id).await?;
+
+    Ok(question)
+}
+
+/// List revisions of a question, newest first
+#[utoipa::path(
+    get,
+    tag = "Question Revisions",
+    path = "/api/questions/{question_id}/revisions",
+    params(("question_id" = Uuid, Path, description = "Question ID")),
+    responses(
+        (status = 200, body = Vec<QuestionRevisionSummary>),
+        ApiError
+    ),
+    security(("jwt_token" = []))
+)]
+async fn list(
+    auth_user: AuthUser,
+    Path(question_id): Path<Uuid>,
+    State(ctx): State<AppContext>,
+) -> axum::response::Result<Response> {
+    check_question_access(&ctx, auth_user.user.id, question_id).await?;
+
+    let revisions = question_revisions::Entity::find()
+        .filter(question_revisions::Column::QuestionId.eq(question_id))
+        .order_by_desc(question_revisions::Column::Revision)
+        .all(&ctx.db)
+        .await
+        .map_err(ApiError::from)?
+        .into_iter()
+        .map(QuestionRevisionSummary::from)
+        .collect::<Vec<_>>();
+
+    Ok(Json(revisions).into_response())
+}
+
+/// Get a question revision with its content
+#[utoipa::path(
+    get,
+    tag = "Question Revisions",
+    path = "/api/questions/{question_id}/revisions/{revision}",
+    params(
+        ("question_id" = Uuid, Path, description = "Question ID"),
+        ("revision" = i32, Path, description = "Revision number")
+    ),
+    responses(
+        (status = 200, body = QuestionRevisionResponse),
+        ApiError
+    ),
+    security(("jwt_token" = []))
+)]
+async fn get(
+    auth_user: AuthUser,
+    Path((question_id, revision)): Path<(Uuid, i32)>,
+    State(ctx): State<AppContext>,
+) -> axum::response::Result<Response> {
+    check_question_access(&ctx, auth_user.user.id, question_id).await?;
+
+    let (model, snapshot) = find_revision(&ctx.db, question_id, revision).await?;
+
+    Ok(Json(QuestionRevisionResponse {
+        revision: QuestionRevisionSummary::from(model),
+        snapshot,
+    })
+    .into_response())
+}
+
+/// Diff two revisions of a question
+#[utoipa::path(
+    get,
+    tag = "Question Revisions",
+    path = "/api/questions/{question_id}/revisions/diff",
+    params(
+        ("question_id" = Uuid, Path, description = "Question ID"),
+        RevisionDiffQuery
+    ),
+    responses(
+        (status = 200, body = QuestionRevisionDiffResponse),
+        ApiError
+    ),
+    security(("jwt_token" = []))
+)]
+async fn diff(
+    auth_user: AuthUser,
+    Path(question_id): Path<Uuid>,
+    State(ctx): State<AppContext>,
+    Query(query): Query<RevisionDiffQuery>,
+) -> axum::response::Result<Response> {
+    check_question_access(&ctx, auth_user.user.id, question_id).await?;
+
+    let (_, from) = find_revision(&ctx.db, question_id, query.from).await?;
+    let (_, to) = find_revision(&ctx.db, question_id, query.to).await?;
+
+    Ok(Json(QuestionRevisionDiffResponse {
+        question_id,
+        from_revision: query.from,
+        to_revision: query.to,
+        diff: from.diff(&to),
+    })
+    .into_response())
+}
+
+/// Restore a question, its answers and categories to an earlier revision
+/// (editors only). A question in review or published goes back to review.
+#[utoipa::path(
+    post,
+    tag = "Question Revisions",
+    path = "/api/questions/{question_id}/revisions/{revision}/revert",
+    params(
+        ("question_id" = Uuid, Path, description = "Question ID"),
+        ("revision" = i32, Path, description = "Revision number")
+    ),
+    responses(
+        (status = 200, body = QuestionResponse),
+        ApiError
+    ),
+    security(("jwt_token" = []))
+)]
+async fn revert(
+    auth_user: AdminUser,
+    Path((question_id, revision)): Path<(Uuid, i32)>,
+    State(ctx): State<AppContext>,
+) -> axum::response::Result<Response> {
+    let question = check_question_access(&ctx, auth_user.user.id, question_id).await?;
+
+    let txn = ctx.db.begin().await.map_err(ApiError::from)?;
+    let (_, snapshot) = find_revision(&txn, question_id, revision).await?;
+
+    // Тема могла быть удалена после этой ревизии
+    if snapshot.topic_id != question.topic_id {
+        topics::Entity::find_by_id(snapshot.topic_id)
+            .one(&txn)
+            .await
+            .map_err(ApiError::from)?
+            .ok_or(ApiError::InvalidState)?;
+        check_topic_access_by_id(&ctx.db, auth_user.user.id, snapshot.topic_id).await?;
+    }
+
+    // Восстановленное содержимое заново проходит ревью
+    let reopened = matches!(question.status.as_str(), "in_review" | "published");
+    let mut to_update = question.into_active_model();
+    to_update.topic_id = Set(snapshot.topic_id);
+    to_update.name = Set(snapshot.name);
+    to_update.lang = Set(snapshot.lang);
+    to_update.content = Set(snapshot.content);
+    to_update.explanation = Set(snapshot.explanation);
+    to_update.points = Set(snapshot.points);
+    to_update.question_type = Set(snapshot.question_type);
+    to_update.valid_from = Set(snapshot.valid_from);
+    to_update.valid_to = Set(snapshot.valid_to);
+    to_update.region = Set(snapshot.region);
+    if reopened {
+        to_update.status = Set("in_review".to_string());
+    }
+    let question = to_update.update(&txn).await.map_err(ApiError::from)?;
+
+    // Удалённые с тех пор изображения пропускаются
+    let referenced: Vec<Uuid> = snapshot
+        .image_ids
+        .iter()
+        .cloned()
+        .chain(snapshot.answers.iter().filter_map(|a| a.image_id))
+        .collect();
+    let existing_images: HashSet<Uuid> = images::Entity::find()
+        .filter(images::Column::Id.is_in(referenced))
+        .all(&txn)
+        .await
+        .map_err(ApiError::from)?
+        .into_iter()
+        .map(|i| i.id)
+        .collect();
+    let image_ids: Vec<Uuid> = snapshot
+        .image_ids
+        .into_iter()
+        .filter(|id| existing_images.contains(id))
+        .collect();
+    set_question_images(&txn, question_id, &image_ids)
+        .await
+        .map_err(ApiError::from)?;
+
+    // Ответы восстанавливаются с прежними id
+    let existing: HashSet<Uuid> = answers::Entity::find()
+        .filter(answers::Column::QuestionId.eq(question_id))
+        .all(&txn)
+        .await
+        .map_err(ApiError::from)?
+        .into_iter()
+        .map(|a| a.id)
+        .collect();
+    let kept: Vec<Uuid> = snapshot.answers.iter().map(|a| a.id).collect();
+
+    answers::Entity::delete_many()
+        .filter(answers::Column::QuestionId.eq(question_id))
+        .filter(answers::Column::Id.is_not_in(kept))
+        .exec(&txn)
+        .await
+        .map_err(ApiError::from)?;
+
+    for answer in snapshot.answers {
+        let model = answers::ActiveModel {
+            id: Set(answer.id),
+            question_id: Set(question_id),
+            value: Set(answer.value),
+            is_correct: Set(answer.is_correct),
+            payload: Set(answer.payload.map(|p| p.to_json())),
+            image_id: Set(answer.image_id.filter(|id| existing_images.contains(id))),
+            // Связи переводов не входят в ревизию и остаются как есть
+            translation_group_id: NotSet,
+        };
+        if existing.contains(&answer.id) {
+            model.update(&txn).await.map_err(ApiError::from)?;
+        } else {
+            model.insert(&txn).await.map_err(ApiError::from)?;
+        }
+    }
+
+    // Удалённые с тех пор категории пропускаются
+    question_categories::Entity::delete_many()
+        .filter(question_categories::Column::QuestionId.eq(question_id))
+        .exec(&txn)
+        .await
+        .map_err(ApiError::from)?;
+
+    let category_ids: Vec<Uuid> = categories::Entity::find()
+        .filter(categories::Column::Id.is_in(snapshot.category_ids))
+        .all(&txn)
+        .await
+        .map_err(ApiError::from)?
+        .into_iter()
+        .map(|c| c.id)
+        .collect();
+
+    for category_id in category_ids {
+        let link = question_categories::ActiveModel {
+            question_id: Set(question_id),
+            category_id: Set(category_id),
+        };
+        link.insert(&txn).await.map_err(ApiError::from)?;
+    }
+
+    if reopened {
+        check_answer_set(&txn, &question).await?;
+        log_review(
+            &txn,
+            "question",
+            question_id,
+            Some(auth_user.user.id),
+            "revert",
+            &question.status,
+            Some(format!("reverted to revision {revision}")),
+        )
+        .await
+        .map_err(ApiError::from)?;
+    }
+    record_revision(&txn, question_id, Some(auth_user.user.id), "revert")
+        .await
+        .map_err(ApiError::from)?;
+
+    txn.commit().await.map_err(ApiError::from)?;
+
+    let response = question_response(&ctx.db, question)
+        .await
+        .map_err(ApiError::from)?;
+    Ok(Json(response).into_response())
+}
+
+pub fn routes() -> OpenApiRouter<AppContext> {
+    OpenApiRouter::new()
+        .routes(routes!(list))
+        .routes(routes!(diff))
+        .routes(routes!(get))
+        .routes(routes!(revert))
+}
@@ -0,0 +1,366 @@
+use crate::{
+    AppContext,
+    entities::{answers, questions},
+    models::{
+        question_translations::{
+            LinkTranslationParams, MissingTranslationResponse, MissingTranslationsQuery,
+        },
+        questions::QuestionResponse,
+    },
+    rest::{
+        curriculum::check_unlocked,
+        questions::{question_response, question_responses},
+    },
+    utils::{
+        extractors::{AdminUser, AuthUser, check_topic_access_by_id},
+        response::ApiError,
+    },
+};
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+    response::{IntoResponse, Response},
+};
+use sea_orm::{
+    ActiveModelTrait, ActiveValue::Set, ColumnTrait, ConnectionTrait, DbErr, EntityTrait,
+    IntoActiveModel, PaginatorTrait, QueryFilter, QueryOrder, TransactionTrait, sea_query::Expr,
+};
+use std::collections::BTreeMap;
+use utoipa_axum::{router::OpenApiRouter, routes};
+use uuid::Uuid;
+
+/// Translation groups of the questions, used to carry favorites and
+/// progress over to other languages
+pub(crate) async fn translation_groups<C: ConnectionTrait>(
+    db: &C,
+    question_ids: Vec<Uuid>,
+) -> Result<Vec<Uuid>, DbErr> {
+    if question_ids.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    Ok(questions::Entity::find()
+        .filter(questions::Column::Id.is_in(question_ids))
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|q| q.translation_group_id)
+        .collect())
+}
+
+/// Checks that the group exists and has no other variant in `lang`
+pub(crate) async fn check_translation_slot<C: ConnectionTrait>(
+    db: &C,
+    translation_group_id: Uuid,
+    lang: &str,
+    question_id: Option<Uuid>,
+) -> Result<(), ApiError> {
+    let variants = questions::Entity::find()
+        .filter(questions::Column::TranslationGroupId.eq(translation_group_id))
+        .all(db)
+        .await
+        .map_err(ApiError::from)?;
+
+    if variants.iter().all(|q| Some(q.id) == question_id) {
+        return Err(ApiError::InvalidFieldValue);
+    }
+    if variants
+        .iter()
+        .any(|q| q.lang == lang && Some(q.id) != question_id)
+    {
+        return Err(ApiError::Conflict);
+    }
+
+    Ok(())
+}
+
+/// Checks that an answer may join the group: the group belongs to an answer
+/// of another variant of the question and no other answer of the question
+/// is in it yet
+pub(crate) async fn check_answer_translation<C: ConnectionTrait>(
+    db: &C,
+    question_id: Uuid,
+    translation_group_id: Uuid,
+    answer_id: Option<Uuid>,
+) -> Result<(), ApiError> {
+    let question = questions::Entity::find_by_id(question_id)
+        .one(db)
+        .await
+        .map_err(ApiError::from)?
+        .ok_or(ApiError::NotFound)?;
+
+    let linked = answers::Entity::find()
+        .filter(answers::Column::TranslationGroupId.eq(translation_group_id))
+        .all(db)
+        .await
+        .map_err(ApiError::from)?;
+
+    if linked
+        .iter()
+        .any(|a| a.question_id == question_id && Some(a.id) != answer_id)
+    {
+        return Err(ApiError::Conflict);
+    }
+
+    let variant_count = questions::Entity::find()
+        .filter(questions::Column::TranslationGroupId.eq(question.translation_group_id))
+        .filter(questions::Column::Id.ne(question_id))
+        .filter(questions::Column::Id.is_in(linked.iter().map(|a| a.question_id)))
+        .count(db)
+        .await
+        .map_err(ApiError::from)?;
+
+    if variant_count == 0 {
+        return Err(ApiError::InvalidFieldValue);
+    }
+
+    Ok(())
+}
+
+/// Get the published variant of a question in another language
+#[utoipa::path(
+    get,
+    tag = "Question Translations",
+    path = "/api/questions/{id}/translations/{lang}",
+    params(
+        ("id" = Uuid, Path, description = "Question ID in any language"),
+        ("lang" = String, Path, description = "Language code (e.g., \"en\", \"ru\")")
+    ),
+    responses(
+        (status = 200, body = QuestionResponse),
+        ApiError
+    ),
+    security(("jwt_token" = []))
+)]
+async fn get_translation(
+    auth_user: AuthUser,
+    Path((id, lang)): Path<(Uuid, String)>,
+    State(ctx): State<AppContext>,
+) -> axum::response::Result<Response> {
+    let question = questions::Entity::find_by_id(id)
+        .one(&ctx.db)
+        .await
+        .map_err(ApiError::from)?
+        .ok_or(ApiError::NotFound)?;
+
+    let translation = questions::Entity::find()
+        .filter(questions::Column::TranslationGroupId.eq(question.translation_group_id))
+        .filter(questions::Column::Lang.eq(lang))
+        .filter(questions::Column::Status.eq("published"))
+        .one(&ctx.db)
+        .await
+        .map_err(ApiError::from)?
+        .ok_or(ApiError::NotFound)?;
+
+    // Проверяем доступ к topic
+    check_topic_access_by_id(&ctx.db, auth_user.user.id, translation.topic_id).await?;
+    check_unlocked(&ctx.db, auth_user.user.id, translation.topic_id).await?;
+
+    let response = question_response(&ctx.db, translation)
+        .await
+        .map_err(ApiError::from)?;
+    Ok(Json(response).into_response())
+}
+
+/// List the published variants of a question in every language
+#[utoipa::path(
+    get,
+    tag = "Question Translations",
+    path = "/api/questions/{id}/translations",
+    params(("id" = Uuid, Path, description = "Question ID in any language")),
+    responses(
+        (status = 200, body = Vec<QuestionResponse>),
+        ApiError
+    ),
+    security(("jwt_token" = []))
+)]
+async fn list_translations(
+    auth_user: AuthUser,
+    Path(id): Path<Uuid>,
+    State(ctx): State<AppContext>,
+) -> axum::response::Result<Response> {
+    let question = questions::Entity::find_by_id(id)
+        .one(&ctx.db)
+        .await
+        .map_err(ApiError::from)?
+        .ok_or(ApiError::NotFound)?;
+
+    check_topic_access_by_id(&ctx.db, auth_user.user.id, question.topic_id).await?;
+
+    let variants = questions::Entity::find()
+        .filter(questions::Column::TranslationGroupId.eq(question.translation_group_id))
+        .filter(questions::Column::Status.eq("published"))
+        .order_by_asc(questions::Column::Lang)
+        .all(&ctx.db)
+        .await
+        .map_err(ApiError::from)?;
+    let variants = question_responses(&ctx.db, variants)
+        .await
+        .map_err(ApiError::from)?;
+
+    Ok(Json(variants).into_response())
+}
+
+/// Link a question to the translation group of another question
+#[utoipa::path(
+    put,
+    tag = "Question Translations",
+    path = "/api/questions/{id}/translation-group",
+    params(("id" = Uuid, Path, description = "Question ID")),
+    request_body = LinkTranslationParams,
+    responses(
+        (status = 200, body = QuestionResponse),
+        ApiError
+    ),
+    security(("jwt_token" = []))
+)]
+async fn link_translation(
+    auth_user: AuthUser,
+    Path(id): Path<Uuid>,
+    State(ctx): State<AppContext>,
+    Json(params): Json<LinkTranslationParams>,
+) -> axum::response::Result<Response> {
+    let txn = ctx.db.begin().await.map_err(ApiError::from)?;
+
+    let question = questions::Entity::find_by_id(id)
+        .one(&txn)
+        .await
+        .map_err(ApiError::from)?
+        .ok_or(ApiError::NotFound)?;
+    check_topic_access_by_id(&ctx.db, auth_user.user.id, question.topic_id).await?;
+
+    let target = questions::Entity::find_by_id(params.question_id)
+        .one(&txn)
+        .await
+        .map_err(ApiError::from)?
+        .ok_or(ApiError::NotFound)?;
+
+    check_translation_slot(
+        &txn,
+        target.translation_group_id,
+        &question.lang,
+        Some(question.id),
+    )
+    .await?;
+
+    let mut to_update = question.into_active_model();
+    to_update.translation_group_id = Set(target.translation_group_id);
+    let question = to_update.update(&txn).await.map_err(ApiError::from)?;
+
+    txn.commit().await.map_err(ApiError::from)?;
+
+    let response = question_response(&ctx.db, question)
+        .await
+        .map_err(ApiError::from)?;
+    Ok(Json(response).into_response())
+}
+
+/// Detach a question and its answers from their translation groups
+#[utoipa::path(
+    delete,
+    tag = "Question Translations",
+    path = "/api/questions/{id}/translation-group",
+    params(("id" = Uuid, Path, description = "Question ID")),
+    responses(
+        (status = 200, body = QuestionResponse),
+        ApiError
+    ),
+    security(("jwt_token" = []))
+)]
+async fn unlink_translation(
+    auth_user: AuthUser,
+    Path(id): Path<Uuid>,
+    State(ctx): State<AppContext>,
+) -> axum::response::Result<Response> {
+    let txn = ctx.db.begin().await.map_err(ApiError::from)?;
+
+    let question = questions::Entity::find_by_id(id)
+        .one(&txn)
+        .await
+        .map_err(ApiError::from)?
+        .ok_or(ApiError::NotFound)?;
+    check_topic_access_by_id(&ctx.db, auth_user.user.id, question.topic_id).await?;
+
+    // Ответы снова образуют группы из одного элемента
+    answers::Entity::update_many()
+        .col_expr(
+            answers::Column::TranslationGroupId,
+            Expr::col(answers::Column::Id),
+        )
+        .filter(answers::Column::QuestionId.eq(question.id))
+        .exec(&txn)
+        .await
+        .map_err(ApiError::from)?;
+
+    let mut to_update = question.into_active_model();
+    to_update.translation_group_id = Set(Uuid::new_v4());
+    let question = to_update.update(&txn).await.map_err(ApiError::from)?;
+
+    txn.commit().await.map_err(ApiError::from)?;
+
+    let response = question_response(&ctx.db, question)
+        .await
+        .map_err(ApiError::from)?;
+    Ok(Json(response).into_response())
+}
+
+/// Published translation groups without a variant in the language (reviewers only)
+#[utoipa::path(
+    get,
+    tag = "Content Review",
+    path = "/api/review/translations/missing",
+    params(MissingTranslationsQuery),
+    responses(
+        (status = 200, body = Vec<MissingTranslationResponse>),
+        ApiError
+    ),
+    security(("jwt_token" = []))
+)]
+async fn missing_translations(
+    _admin: AdminUser,
+    State(ctx): State<AppContext>,
+    Query(query): Query<MissingTranslationsQuery>,
+) -> axum::response::Result<Response> {
+    // Черновик перевода тоже считается: он уже в работе
+    let questions_list = questions::Entity::find()
+        .filter(questions::Column::Status.ne("archived"))
+        .order_by_asc(questions::Column::Lang)
+        .all(&ctx.db)
+        .await
+        .map_err(ApiError::from)?;
+
+    let mut groups: BTreeMap<Uuid, Vec<questions::Model>> = BTreeMap::new();
+    for question in questions_list {
+        groups
+            .entry(question.translation_group_id)
+            .or_default()
+            .push(question);
+    }
+
+    let missing = groups
+        .into_iter()
+        .filter(|(_, variants)| variants.iter().all(|q| q.lang != query.lang))
+        .filter_map(|(translation_group_id, variants)| {
+            let published: Vec<&questions::Model> = variants
+                .iter()
+                .filter(|q| q.status == "published")
+                .collect();
+            (!published.is_empty()).then(|| MissingTranslationResponse {
+                translation_group_id,
+                langs: published.iter().map(|q| q.lang.clone()).collect(),
+                question_ids: published.iter().map(|q| q.id).collect(),
+            })
+        })
+        .collect::<Vec<_>>();
+
+    Ok(Json(missing).into_response())
+}
+
+pub fn routes() -> OpenApiRouter<AppContext> {
+    OpenApiRouter::new()
+        .routes(routes!(get_translation))
+        .routes(routes!(list_translations))
+        .routes(routes!(link_translation))
+        .routes(routes!(unlink_translation))
+        .routes(routes!(missing_translations))
+}
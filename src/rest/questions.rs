@@ -1,7 +1,27 @@
 use crate::{
     AppContext,
-    entities::{categories, question_categories, questions, topics, user_favorite_questions},
-    models::questions::{CreateQuestionParams, LangQuery, QuestionResponse, UpdateQuestionParams},
+    entities::{
+        categories, question_categories, question_images, questions, topics,
+        user_favorite_questions,
+    },
+    models::{
+        question_types::QuestionType,
+        questions::{CreateQuestionParams, LangQuery, QuestionResponse, UpdateQuestionParams},
+    },
+    rest::{
+        answers::check_answer_set,
+        categories::category_subtree,
+        content_reviews::log_review,
+        curriculum::check_unlocked,
+        images::{check_images_exist, image_links},
+        license_classes::{
+            check_license_classes_exist, license_class_condition, question_license_classes,
+            set_question_license_classes,
+        },
+        question_revisions::record_revision,
+        question_translations::{check_translation_slot, translation_groups},
+        regions::{check_validity, questions_in_effect},
+    },
     utils::{
         extractors::{AuthUser, check_topic_access, check_topic_access_by_id},
         response::ApiError,
@@ -13,13 +33,100 @@ use axum::{
     response::{IntoResponse, Response},
 };
 use sea_orm::{
-    ActiveModelTrait, ActiveValue::Set, ColumnTrait, EntityTrait, IntoActiveModel, QueryFilter,
+    ActiveModelTrait, ActiveValue::Set, ColumnTrait, ConnectionTrait, DbErr, EntityTrait,
+    IntoActiveModel, QueryFilter, QueryOrder, TransactionTrait,
 };
+use std::collections::HashMap;
 use utoipa_axum::{router::OpenApiRouter, routes};
 use uuid::Uuid;
 use validator::Validate;
 
-/// List all questions by lang (requires auth)
+/// Image IDs of the questions in display order
+pub(crate) async fn question_image_ids<C: ConnectionTrait>(
+    db: &C,
+    question_ids: &[Uuid],
+) -> Result<HashMap<Uuid, Vec<Uuid>>, DbErr> {
+    let mut image_ids: HashMap<Uuid, Vec<Uuid>> = HashMap::new();
+    if question_ids.is_empty() {
+        return Ok(image_ids);
+    }
+
+    for link in question_images::Entity::find()
+        .filter(question_images::Column::QuestionId.is_in(question_ids.to_vec()))
+        .order_by_asc(question_images::Column::Position)
+        .all(db)
+        .await?
+    {
+        image_ids
+            .entry(link.question_id)
+            .or_default()
+            .push(link.image_id);
+    }
+    Ok(image_ids)
+}
+
+/// Replaces the images of a question, keeping the given order
+pub(crate) async fn set_question_images<C: ConnectionTrait>(
+    db: &C,
+    question_id: Uuid,
+    image_ids: &[Uuid],
+) -> Result<(), DbErr> {
+    question_images::Entity::delete_many()
+        .filter(question_images::Column::QuestionId.eq(question_id))
+        .exec(db)
+        .await?;
+
+    for (position, image_id) in image_ids.iter().enumerate() {
+        question_images::ActiveModel {
+            question_id: Set(question_id),
+            image_id: Set(*image_id),
+            position: Set(position as i16),
+        }
+        .insert(db)
+        .await?;
+    }
+    Ok(())
+}
+
+/// Builds responses with the image URLs and licence classes resolved
+pub(crate) async fn question_responses<C: ConnectionTrait>(
+    db: &C,
+    questions_list: Vec<questions::Model>,
+) -> Result<Vec<QuestionResponse>, DbErr> {
+    let question_ids: Vec<Uuid> = questions_list.iter().map(|q| q.id).collect();
+    let mut image_ids = question_image_ids(db, &question_ids).await?;
+    let links = image_links(db, image_ids.values().flatten().cloned()).await?;
+    let mut license_classes = question_license_classes(db, &question_ids).await?;
+
+    Ok(questions_list
+        .into_iter()
+        .map(|question| {
+            let images = image_ids
+                .remove(&question.id)
+                .unwrap_or_default()
+                .iter()
+                .filter_map(|id| links.get(id).cloned())
+                .collect();
+            QuestionResponse {
+                images,
+                license_classes: license_classes.remove(&question.id).unwrap_or_default(),
+                ..QuestionResponse::from(question)
+            }
+        })
+        .collect())
+}
+
+pub(crate) async fn question_response<C: ConnectionTrait>(
+    db: &C,
+    question: questions::Model,
+) -> Result<QuestionResponse, DbErr> {
+    Ok(question_responses(db, vec![question])
+        .await?
+        .pop()
+        .expect("one response per question"))
+}
+
+/// List published questions by lang in force for the user's licence class and region (requires auth)
 #[utoipa::path(
     get,
     tag = "Questions",
@@ -32,23 +139,28 @@ use validator::Validate;
     security(("jwt_token" = []))
 )]
 async fn list(
-    _auth_user: AuthUser,
+    auth_user: AuthUser,
     State(ctx): State<AppContext>,
     Query(query): Query<LangQuery>,
 ) -> axum::response::Result<Response> {
     let questions = questions::Entity::find()
         .filter(questions::Column::Lang.eq(&query.lang))
+        .filter(questions::Column::Status.eq("published"))
+        .filter(license_class_condition(
+            auth_user.user.license_class.as_deref(),
+        ))
+        .filter(questions_in_effect(auth_user.user.region.as_deref()))
         .all(&ctx.db)
         .await
-        .map_err(ApiError::from)?
-        .into_iter()
-        .map(QuestionResponse::from)
-        .collect::<Vec<_>>();
+        .map_err(ApiError::from)?;
+    let questions = question_responses(&ctx.db, questions)
+        .await
+        .map_err(ApiError::from)?;
 
     Ok(Json(questions).into_response())
 }
 
-/// Get question by id and lang (requires auth, subscription if topic requires)
+/// Get published question by id and lang (requires auth, subscription if topic requires)
 #[utoipa::path(
     get,
     tag = "Questions",
@@ -71,6 +183,7 @@ async fn get(
 ) -> axum::response::Result<Response> {
     let question = questions::Entity::find_by_id(id)
         .filter(questions::Column::Lang.eq(&query.lang))
+        .filter(questions::Column::Status.eq("published"))
         .one(&ctx.db)
         .await
         .map_err(ApiError::from)?
@@ -78,8 +191,12 @@ async fn get(
 
     // Проверяем доступ к topic
     check_topic_access_by_id(&ctx.db, auth_user.user.id, question.topic_id).await?;
+    check_unlocked(&ctx.db, auth_user.user.id, question.topic_id).await?;
 
-    Ok(Json(QuestionResponse::from(question)).into_response())
+    let response = question_response(&ctx.db, question)
+        .await
+        .map_err(ApiError::from)?;
+    Ok(Json(response).into_response())
 }
 
 /// Create question (requires auth, subscription if topic requires)
@@ -101,25 +218,57 @@ async fn create(
 ) -> axum::response::Result<Response> {
     params.validate().map_err(ApiError::from)?;
 
+    let question_type = params
+        .question_type
+        .as_deref()
+        .unwrap_or(QuestionType::Choice.as_str())
+        .parse::<QuestionType>()
+        .map_err(|_| ApiError::InvalidFieldValue)?;
+
     // Проверяем доступ к topic
     check_topic_access_by_id(&ctx.db, auth_user.user.id, params.topic_id).await?;
 
+    let image_ids = params.image_ids.unwrap_or_default();
+    check_images_exist(&ctx.db, &image_ids).await?;
+    let license_classes = params.license_classes.unwrap_or_default();
+    check_license_classes_exist(&ctx.db, &license_classes).await?;
+    check_validity(params.valid_from, params.valid_to)?;
+    if let Some(translation_group_id) = params.translation_group_id {
+        check_translation_slot(&ctx.db, translation_group_id, &params.lang, None).await?;
+    }
+
     let question = questions::ActiveModel {
         topic_id: Set(params.topic_id),
         name: Set(params.name),
         lang: Set(params.lang),
         content: Set(params.content),
         explanation: Set(params.explanation),
+        points: Set(params.points.unwrap_or(1)),
+        question_type: Set(question_type.as_str().to_string()),
+        valid_from: Set(params.valid_from),
+        valid_to: Set(params.valid_to),
+        region: Set(params.region),
+        translation_group_id: params.translation_group_id.map(Set).unwrap_or_default(),
         ..Default::default()
     };
 
-    let question = question.insert(&ctx.db).await.map_err(ApiError::from)?;
+    let txn = ctx.db.begin().await.map_err(ApiError::from)?;
+    let question = question.insert(&txn).await.map_err(ApiError::from)?;
+    set_question_images(&txn, question.id, &image_ids)
+        .await
+        .map_err(ApiError::from)?;
+    set_question_license_classes(&txn, question.id, &license_classes)
+        .await
+        .map_err(ApiError::from)?;
+    record_revision(&txn, question.id, Some(auth_user.user.id), "create")
+        .await
+        .map_err(ApiError::from)?;
+    txn.commit().await.map_err(ApiError::from)?;
 
-    Ok((
-        axum::http::StatusCode::CREATED,
-        Json(QuestionResponse::from(question)),
-    )
-        .into_response())
+    let response = question_response(&ctx.db, question)
+        .await
+        .map_err(ApiError::from)?;
+    Ok((axum::http::StatusCode::CREATED, Json(response)).into_response())
 }
 
 /// Update question by id (requires auth, subscription if topic requires)
@@ -152,7 +301,18 @@ async fn update(
     // Проверяем доступ к текущему topic
     check_topic_access_by_id(&ctx.db, auth_user.user.id, question.topic_id).await?;
 
+    check_validity(
+        params.valid_from.or(question.valid_from),
+        params.valid_to.or(question.valid_to),
+    )?;
+
+    let previous_type = question.question_type.clone();
+    // Правка опубликованного вопроса не редактором уходит на ревью
+    let reopened = question.status == "published" && !auth_user.user.is_editor();
     let mut to_update = question.into_active_model();
+    if reopened {
+        to_update.status = Set("in_review".to_string());
+    }
 
     if let Some(topic_id) = params.topic_id {
         // Проверяем доступ к новому topic
@@ -171,10 +331,72 @@ async fn update(
     if let Some(explanation) = params.explanation {
         to_update.explanation = Set(explanation);
     }
+    if let Some(points) = params.points {
+        to_update.points = Set(points);
+    }
+    // Ответы проверяются на соответствие типу при отправке на ревью,
+    // а у вопросов на ревью и опубликованных ещё и при смене типа
+    if let Some(question_type) = params.question_type {
+        let question_type = question_type
+            .parse::<QuestionType>()
+            .map_err(|_| ApiError::InvalidFieldValue)?;
+        to_update.question_type = Set(question_type.as_str().to_string());
+    }
+    if params.valid_from.is_some() {
+        to_update.valid_from = Set(params.valid_from);
+    }
+    if params.valid_to.is_some() {
+        to_update.valid_to = Set(params.valid_to);
+    }
+    if let Some(region) = params.region {
+        to_update.region = Set(Some(region).filter(|region| !region.is_empty()));
+    }
+    if let Some(image_ids) = &params.image_ids {
+        check_images_exist(&ctx.db, image_ids).await?;
+    }
+    if let Some(license_classes) = &params.license_classes {
+        check_license_classes_exist(&ctx.db, license_classes).await?;
+    }
 
-    let question = to_update.update(&ctx.db).await.map_err(ApiError::from)?;
+    let txn = ctx.db.begin().await.map_err(ApiError::from)?;
+    let question = to_update.update(&txn).await.map_err(ApiError::from)?;
+    if question.question_type != previous_type
+        && matches!(question.status.as_str(), "in_review" | "published")
+    {
+        check_answer_set(&txn, &question).await?;
+    }
+    if let Some(image_ids) = &params.image_ids {
+        set_question_images(&txn, question.id, image_ids)
+            .await
+            .map_err(ApiError::from)?;
+    }
+    if let Some(license_classes) = &params.license_classes {
+        set_question_license_classes(&txn, question.id, license_classes)
+            .await
+            .map_err(ApiError::from)?;
+    }
+    if reopened {
+        log_review(
+            &txn,
+            "question",
+            question.id,
+            Some(auth_user.user.id),
+            "edit",
+            &question.status,
+            None,
+        )
+        .await
+        .map_err(ApiError::from)?;
+    }
+    record_revision(&txn, question.id, Some(auth_user.user.id), "update")
+        .await
+        .map_err(ApiError::from)?;
+    txn.commit().await.map_err(ApiError::from)?;
 
-    Ok(Json(QuestionResponse::from(question)).into_response())
+    let response = question_response(&ctx.db, question)
+        .await
+        .map_err(ApiError::from)?;
+    Ok(Json(response).into_response())
 }
 
 /// Delete question by id (requires auth, subscription if topic requires)
@@ -211,7 +433,7 @@ async fn delete(
     Ok(().into_response())
 }
 
-/// Get questions by topic id and lang (requires auth, subscription if topic requires)
+/// Get published questions by topic id and lang (requires auth, subscription if topic requires)
 #[utoipa::path(
     get,
     tag = "Questions",
@@ -240,21 +462,27 @@ async fn get_by_topic(
         .ok_or(ApiError::NotFound)?;
 
     check_topic_access(&ctx.db, auth_user.user.id, &topic).await?;
+    check_unlocked(&ctx.db, auth_user.user.id, topic.id).await?;
 
     let questions = questions::Entity::find()
         .filter(questions::Column::TopicId.eq(topic_id))
         .filter(questions::Column::Lang.eq(&query.lang))
+        .filter(questions::Column::Status.eq("published"))
+        .filter(license_class_condition(
+            auth_user.user.license_class.as_deref(),
+        ))
+        .filter(questions_in_effect(auth_user.user.region.as_deref()))
         .all(&ctx.db)
         .await
-        .map_err(ApiError::from)?
-        .into_iter()
-        .map(QuestionResponse::from)
-        .collect::<Vec<_>>();
+        .map_err(ApiError::from)?;
+    let questions = question_responses(&ctx.db, questions)
+        .await
+        .map_err(ApiError::from)?;
 
     Ok(Json(questions).into_response())
 }
 
-/// Get questions by category id and lang (requires auth)
+/// Get published questions of a category and its subcategories by lang (requires auth)
 #[utoipa::path(
     get,
     tag = "Questions",
@@ -270,7 +498,7 @@ async fn get_by_topic(
     security(("jwt_token" = []))
 )]
 async fn get_by_category(
-    _auth_user: AuthUser,
+    auth_user: AuthUser,
     Path(category_id): Path<Uuid>,
     State(ctx): State<AppContext>,
     Query(query): Query<LangQuery>,
@@ -282,9 +510,12 @@ async fn get_by_category(
         .map_err(ApiError::from)?
         .ok_or(ApiError::NotFound)?;
 
-    // Получаем question_ids для этой категории
+    // Вопросы этой категории и всех вложенных
+    let category_ids = category_subtree(&ctx.db, category_id)
+        .await
+        .map_err(ApiError::from)?;
     let question_ids: Vec<Uuid> = question_categories::Entity::find()
-        .filter(question_categories::Column::CategoryId.eq(category_id))
+        .filter(question_categories::Column::CategoryId.is_in(category_ids))
         .all(&ctx.db)
         .await
         .map_err(ApiError::from)?
@@ -292,21 +523,26 @@ async fn get_by_category(
         .map(|qc| qc.question_id)
         .collect();
 
-    // Получаем вопросы по ids и lang
-    let questions_list = questions::Entity::find()
+    // Получаем вопросы по ids и lang для класса прав пользователя
+    let questions = questions::Entity::find()
         .filter(questions::Column::Id.is_in(question_ids))
         .filter(questions::Column::Lang.eq(&query.lang))
+        .filter(questions::Column::Status.eq("published"))
+        .filter(license_class_condition(
+            auth_user.user.license_class.as_deref(),
+        ))
+        .filter(questions_in_effect(auth_user.user.region.as_deref()))
         .all(&ctx.db)
         .await
-        .map_err(ApiError::from)?
-        .into_iter()
-        .map(QuestionResponse::from)
-        .collect::<Vec<_>>();
+        .map_err(ApiError::from)?;
+    let questions = question_responses(&ctx.db, questions)
+        .await
+        .map_err(ApiError::from)?;
 
-    Ok(Json(questions_list).into_response())
+    Ok(Json(questions).into_response())
 }
 
-/// Get user's favorite questions by lang (requires auth)
+/// Get user's published favorite questions by lang (requires auth)
 #[utoipa::path(
     get,
     tag = "Questions",
@@ -333,18 +569,28 @@ async fn get_favorites(
         .map(|fq| fq.question_id)
         .collect();
 
-    // Получаем вопросы по ids и lang
-    let questions_list = questions::Entity::find()
-        .filter(questions::Column::Id.is_in(question_ids))
+    // Избранное следует за группой переводов, а не за конкретным языком
+    let groups = translation_groups(&ctx.db, question_ids)
+        .await
+        .map_err(ApiError::from)?;
+
+    // Получаем вопросы по группам и lang
+    let questions = questions::Entity::find()
+        .filter(questions::Column::TranslationGroupId.is_in(groups))
         .filter(questions::Column::Lang.eq(&query.lang))
+        .filter(questions::Column::Status.eq("published"))
+        .filter(license_class_condition(
+            auth_user.user.license_class.as_deref(),
+        ))
+        .filter(questions_in_effect(auth_user.user.region.as_deref()))
         .all(&ctx.db)
         .await
-        .map_err(ApiError::from)?
-        .into_iter()
-        .map(QuestionResponse::from)
-        .collect::<Vec<_>>();
+        .map_err(ApiError::from)?;
+    let questions = question_responses(&ctx.db, questions)
+        .await
+        .map_err(ApiError::from)?;
 
-    Ok(Json(questions_list).into_response())
+    Ok(Json(questions).into_response())
 }
 
 pub fn routes() -> OpenApiRouter<AppContext> {
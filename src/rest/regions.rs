@@ -0,0 +1,128 @@
+use crate::{
+    AppContext,
+    entities::{lessons, questions},
+    utils::{extractors::AuthUser, response::ApiError},
+};
+use axum::{
+    Json,
+    extract::State,
+    response::{IntoResponse, Response},
+};
+use chrono::NaiveDate;
+use sea_orm::{ColumnTrait, Condition, EntityTrait, QueryFilter, QuerySelect};
+use std::collections::BTreeSet;
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+/// Content in force on `today` for a user of `region`: the dates are open or
+/// cover the day, and the content is general or specific to the region
+fn in_effect<C: ColumnTrait>(
+    (valid_from, valid_to, content_region): (C, C, C),
+    today: NaiveDate,
+    region: Option<&str>,
+) -> Condition {
+    let regional = match region {
+        Some(region) => Condition::any()
+            .add(content_region.is_null())
+            .add(content_region.eq(region)),
+        None => Condition::all().add(content_region.is_null()),
+    };
+
+    Condition::all()
+        .add(
+            Condition::any()
+                .add(valid_from.is_null())
+                .add(valid_from.lte(today)),
+        )
+        .add(
+            Condition::any()
+                .add(valid_to.is_null())
+                .add(valid_to.gte(today)),
+        )
+        .add(regional)
+}
+
+/// Questions in force today for a user of the region
+pub(crate) fn questions_in_effect(region: Option<&str>) -> Condition {
+    in_effect(
+        (
+            questions::Column::ValidFrom,
+            questions::Column::ValidTo,
+            questions::Column::Region,
+        ),
+        chrono::Utc::now().date_naive(),
+        region,
+    )
+}
+
+/// Lessons in force today for a user of the region
+pub(crate) fn lessons_in_effect(region: Option<&str>) -> Condition {
+    in_effect(
+        (
+            lessons::Column::ValidFrom,
+            lessons::Column::ValidTo,
+            lessons::Column::Region,
+        ),
+        chrono::Utc::now().date_naive(),
+        region,
+    )
+}
+
+/// Checks that the validity period is not inverted
+pub(crate) fn check_validity(
+    valid_from: Option<NaiveDate>,
+    valid_to: Option<NaiveDate>,
+) -> Result<(), ApiError> {
+    match (valid_from, valid_to) {
+        (Some(from), Some(to)) if from > to => Err(ApiError::InvalidInput),
+        _ => Ok(()),
+    }
+}
+
+/// List regions with their own rules
+#[utoipa::path(
+    get,
+    tag = "Regions",
+    path = "/api/regions",
+    responses(
+        (status = 200, body = Vec<String>),
+        ApiError
+    ),
+    security(("jwt_token" = []))
+)]
+async fn list(
+    _auth_user: AuthUser,
+    State(ctx): State<AppContext>,
+) -> axum::response::Result<Response> {
+    let question_regions: Vec<Option<String>> = questions::Entity::find()
+        .select_only()
+        .column(questions::Column::Region)
+        .filter(questions::Column::Region.is_not_null())
+        .filter(questions::Column::Status.eq("published"))
+        .distinct()
+        .into_tuple()
+        .all(&ctx.db)
+        .await
+        .map_err(ApiError::from)?;
+    let lesson_regions: Vec<Option<String>> = lessons::Entity::find()
+        .select_only()
+        .column(lessons::Column::Region)
+        .filter(lessons::Column::Region.is_not_null())
+        .filter(lessons::Column::Status.eq("published"))
+        .distinct()
+        .into_tuple()
+        .all(&ctx.db)
+        .await
+        .map_err(ApiError::from)?;
+
+    let regions: BTreeSet<String> = question_regions
+        .into_iter()
+        .chain(lesson_regions)
+        .flatten()
+        .collect();
+
+    Ok(Json(regions.into_iter().collect::<Vec<_>>()).into_response())
+}
+
+pub fn routes() -> OpenApiRouter<AppContext> {
+    OpenApiRouter::new().routes(routes!(list))
+}
@@ -0,0 +1,264 @@
+use crate::{
+    AppContext,
+    entities::{lesson_road_signs, lessons, question_road_signs, questions, road_signs},
+    models::{
+        questions::LangQuery,
+        road_signs::{RoadSignLinkResponse, RoadSignResponse},
+    },
+    rest::road_signs::sign_responses,
+    utils::{extractors::AuthUser, response::ApiError},
+};
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+    response::{IntoResponse, Response},
+};
+use sea_orm::{ActiveModelTrait, ActiveValue::Set, EntityTrait, ModelTrait};
+use utoipa_axum::{router::OpenApiRouter, routes};
+use uuid::Uuid;
+
+async fn find_sign(ctx: &AppContext, sign_id: Uuid) -> Result<road_signs::Model, ApiError> {
+    road_signs::Entity::find_by_id(sign_id)
+        .one(&ctx.db)
+        .await
+        .map_err(ApiError::from)?
+        .ok_or(ApiError::NotFound)
+}
+
+/// Get signs linked to a question
+#[utoipa::path(
+    get,
+    tag = "Road Signs",
+    path = "/api/questions/{question_id}/road-signs",
+    params(
+        ("question_id" = Uuid, Path, description = "Question ID"),
+        LangQuery
+    ),
+    responses(
+        (status = 200, body = Vec<RoadSignResponse>),
+        ApiError
+    ),
+    security(("jwt_token" = []))
+)]
+async fn get_question_signs(
+    _auth_user: AuthUser,
+    Path(question_id): Path<Uuid>,
+    State(ctx): State<AppContext>,
+    Query(query): Query<LangQuery>,
+) -> axum::response::Result<Response> {
+    let question = questions::Entity::find_by_id(question_id)
+        .one(&ctx.db)
+        .await
+        .map_err(ApiError::from)?
+        .ok_or(ApiError::NotFound)?;
+
+    let signs = question
+        .find_related(road_signs::Entity)
+        .all(&ctx.db)
+        .await
+        .map_err(ApiError::from)?;
+    let signs = sign_responses(&ctx.db, signs, &query.lang)
+        .await
+        .map_err(ApiError::from)?;
+
+    Ok(Json(signs).into_response())
+}
+
+/// Link a sign to a question
+#[utoipa::path(
+    post,
+    tag = "Road Signs",
+    path = "/api/questions/{question_id}/road-signs/{sign_id}",
+    params(
+        ("question_id" = Uuid, Path, description = "Question ID"),
+        ("sign_id" = Uuid, Path, description = "Road sign ID")
+    ),
+    responses(
+        (status = 201, body = RoadSignLinkResponse),
+        ApiError
+    ),
+    security(("jwt_token" = []))
+)]
+async fn add_sign_to_question(
+    _auth_user: AuthUser,
+    Path((question_id, sign_id)): Path<(Uuid, Uuid)>,
+    State(ctx): State<AppContext>,
+) -> axum::response::Result<Response> {
+    // Проверяем что вопрос существует
+    questions::Entity::find_by_id(question_id)
+        .one(&ctx.db)
+        .await
+        .map_err(ApiError::from)?
+        .ok_or(ApiError::NotFound)?;
+    find_sign(&ctx, sign_id).await?;
+
+    let link = question_road_signs::ActiveModel {
+        question_id: Set(question_id),
+        sign_id: Set(sign_id),
+    };
+    link.insert(&ctx.db).await.map_err(ApiError::from)?;
+
+    Ok((
+        axum::http::StatusCode::CREATED,
+        Json(RoadSignLinkResponse {
+            sign_id,
+            target_id: question_id,
+        }),
+    )
+        .into_response())
+}
+
+/// Unlink a sign from a question
+#[utoipa::path(
+    delete,
+    tag = "Road Signs",
+    path = "/api/questions/{question_id}/road-signs/{sign_id}",
+    params(
+        ("question_id" = Uuid, Path, description = "Question ID"),
+        ("sign_id" = Uuid, Path, description = "Road sign ID")
+    ),
+    responses(
+        (status = 200, description = "Sign unlinked from the question"),
+        ApiError
+    ),
+    security(("jwt_token" = []))
+)]
+async fn remove_sign_from_question(
+    _auth_user: AuthUser,
+    Path((question_id, sign_id)): Path<(Uuid, Uuid)>,
+    State(ctx): State<AppContext>,
+) -> axum::response::Result<Response> {
+    let link = question_road_signs::Entity::find_by_id((question_id, sign_id))
+        .one(&ctx.db)
+        .await
+        .map_err(ApiError::from)?
+        .ok_or(ApiError::NotFound)?;
+
+    link.delete(&ctx.db).await.map_err(ApiError::from)?;
+
+    Ok(().into_response())
+}
+
+/// Get signs linked to a lesson
+#[utoipa::path(
+    get,
+    tag = "Road Signs",
+    path = "/api/lessons/{lesson_id}/road-signs",
+    params(
+        ("lesson_id" = Uuid, Path, description = "Lesson ID"),
+        LangQuery
+    ),
+    responses(
+        (status = 200, body = Vec<RoadSignResponse>),
+        ApiError
+    ),
+    security(("jwt_token" = []))
+)]
+async fn get_lesson_signs(
+    _auth_user: AuthUser,
+    Path(lesson_id): Path<Uuid>,
+    State(ctx): State<AppContext>,
+    Query(query): Query<LangQuery>,
+) -> axum::response::Result<Response> {
+    let lesson = lessons::Entity::find_by_id(lesson_id)
+        .one(&ctx.db)
+        .await
+        .map_err(ApiError::from)?
+        .ok_or(ApiError::NotFound)?;
+
+    let signs = lesson
+        .find_related(road_signs::Entity)
+        .all(&ctx.db)
+        .await
+        .map_err(ApiError::from)?;
+    let signs = sign_responses(&ctx.db, signs, &query.lang)
+        .await
+        .map_err(ApiError::from)?;
+
+    Ok(Json(signs).into_response())
+}
+
+/// Link a sign to a lesson
+#[utoipa::path(
+    post,
+    tag = "Road Signs",
+    path = "/api/lessons/{lesson_id}/road-signs/{sign_id}",
+    params(
+        ("lesson_id" = Uuid, Path, description = "Lesson ID"),
+        ("sign_id" = Uuid, Path, description = "Road sign ID")
+    ),
+    responses(
+        (status = 201, body = RoadSignLinkResponse),
+        ApiError
+    ),
+    security(("jwt_token" = []))
+)]
+async fn add_sign_to_lesson(
+    _auth_user: AuthUser,
+    Path((lesson_id, sign_id)): Path<(Uuid, Uuid)>,
+    State(ctx): State<AppContext>,
+) -> axum::response::Result<Response> {
+    // Проверяем что урок существует
+    lessons::Entity::find_by_id(lesson_id)
+        .one(&ctx.db)
+        .await
+        .map_err(ApiError::from)?
+        .ok_or(ApiError::NotFound)?;
+    find_sign(&ctx, sign_id).await?;
+
+    let link = lesson_road_signs::ActiveModel {
+        lesson_id: Set(lesson_id),
+        sign_id: Set(sign_id),
+    };
+    link.insert(&ctx.db).await.map_err(ApiError::from)?;
+
+    Ok((
+        axum::http::StatusCode::CREATED,
+        Json(RoadSignLinkResponse {
+            sign_id,
+            target_id: lesson_id,
+        }),
+    )
+        .into_response())
+}
+
+/// Unlink a sign from a lesson
+#[utoipa::path(
+    delete,
+    tag = "Road Signs",
+    path = "/api/lessons/{lesson_id}/road-signs/{sign_id}",
+    params(
+        ("lesson_id" = Uuid, Path, description = "Lesson ID"),
+        ("sign_id" = Uuid, Path, description = "Road sign ID")
+    ),
+    responses(
+        (status = 200, description = "Sign unlinked from the lesson"),
+        ApiError
+    ),
+    security(("jwt_token" = []))
+)]
+async fn remove_sign_from_lesson(
+    _auth_user: AuthUser,
+    Path((lesson_id, sign_id)): Path<(Uuid, Uuid)>,
+    State(ctx): State<AppContext>,
+) -> axum::response::Result<Response> {
+    let link = lesson_road_signs::Entity::find_by_id((lesson_id, sign_id))
+        .one(&ctx.db)
+        .await
+        .map_err(ApiError::from)?
+        .ok_or(ApiError::NotFound)?;
+
+    link.delete(&ctx.db).await.map_err(ApiError::from)?;
+
+    Ok(().into_response())
+}
+
+pub fn routes() -> OpenApiRouter<AppContext> {
+    OpenApiRouter::new()
+        .routes(routes!(get_question_signs))
+        .routes(routes!(add_sign_to_question))
+        .routes(routes!(remove_sign_from_question))
+        .routes(routes!(get_lesson_signs))
+        .routes(routes!(add_sign_to_lesson))
+        .routes(routes!(remove_sign_from_lesson))
+}
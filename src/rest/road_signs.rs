@@ -0,0 +1,629 @@
+use crate::{
+    AppContext,
+    entities::{
+        lesson_road_signs, lessons, question_road_signs, questions, road_sign_translations,
+        road_signs, test_question_options, test_questions,
+    },
+    models::{
+        question_types::QuestionType,
+        road_signs::{
+            CreateRoadSignParams, RoadSignDetailResponse, RoadSignResponse, RoadSignSearchQuery,
+            RoadSignTranslationParams, RoadSignTranslationResponse, RoadSignsQuery,
+            UpdateRoadSignParams,
+        },
+    },
+    rest::images::{check_images_exist, image_links},
+    utils::{
+        extractors::{AdminUser, AuthUser},
+        response::ApiError,
+    },
+};
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+    response::{IntoResponse, Response},
+};
+use rand::seq::SliceRandom;
+use sea_orm::{
+    ActiveModelTrait,
+    ActiveValue::Set,
+    ColumnTrait, Condition, ConnectionTrait, DbErr, EntityTrait, IntoActiveModel, QueryFilter,
+    TransactionTrait,
+    sea_query::{Expr, LikeExpr, extension::postgres::PgExpr},
+};
+use std::collections::{HashMap, HashSet};
+use utoipa_axum::{router::OpenApiRouter, routes};
+use uuid::Uuid;
+use validator::Validate;
+
+/// Groups of the sign catalogue
+pub(crate) const SIGN_GROUPS: [&str; 8] = [
+    "warning",
+    "priority",
+    "prohibitory",
+    "mandatory",
+    "special",
+    "information",
+    "service",
+    "additional",
+];
+
+/// Answer options of a generated recognition question
+const SIGN_OPTIONS: usize = 4;
+
+/// Sort key putting "1.2" before "1.10"
+fn code_key(code: &str) -> Vec<(u32, String)> {
+    code.split('.')
+        .map(|part| (part.parse().unwrap_or(u32::MAX), part.to_string()))
+        .collect()
+}
+
+/// Question text of generated recognition questions
+fn recognition_prompt(lang: &str) -> &'static str {
+    match lang {
+        "ru" => "Что означает этот знак?",
+        "de" => "Was bedeutet dieses Verkehrszeichen?",
+        "fr" => "Que signifie ce panneau ?",
+        "es" => "¿Qué significa esta señal?",
+        _ => "What does this sign mean?",
+    }
+}
+
+/// Signs with their translation to `lang`, signs without one are left out
+pub(crate) async fn sign_responses<C: ConnectionTrait>(
+    db: &C,
+    signs: Vec<road_signs::Model>,
+    lang: &str,
+) -> Result<Vec<RoadSignResponse>, DbErr> {
+    let mut translations: HashMap<Uuid, road_sign_translations::Model> =
+        road_sign_translations::Entity::find()
+            .filter(road_sign_translations::Column::SignId.is_in(signs.iter().map(|s| s.id)))
+            .filter(road_sign_translations::Column::Lang.eq(lang))
+            .all(db)
+            .await?
+            .into_iter()
+            .map(|t| (t.sign_id, t))
+            .collect();
+    let links = image_links(db, signs.iter().filter_map(|s| s.image_id)).await?;
+
+    let mut responses: Vec<RoadSignResponse> = signs
+        .into_iter()
+        .filter_map(|sign| {
+            let translation = translations.remove(&sign.id)?;
+            Some(RoadSignResponse {
+                id: sign.id,
+                image: sign.image_id.and_then(|id| links.get(&id).cloned()),
+                code: sign.code,
+                group: sign.sign_group,
+                lang: translation.lang,
+                name: translation.name,
+                meaning: translation.meaning,
+            })
+        })
+        .collect();
+    responses.sort_by_key(|s| code_key(&s.code));
+    Ok(responses)
+}
+
+async fn detail_response<C: ConnectionTrait>(
+    db: &C,
+    sign: road_signs::Model,
+) -> Result<RoadSignDetailResponse, DbErr> {
+    let translations = road_sign_translations::Entity::find()
+        .filter(road_sign_translations::Column::SignId.eq(sign.id))
+        .all(db)
+        .await?
+        .into_iter()
+        .map(RoadSignTranslationResponse::from)
+        .collect();
+
+    let linked_questions: Vec<Uuid> = question_road_signs::Entity::find()
+        .filter(question_road_signs::Column::SignId.eq(sign.id))
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|link| link.question_id)
+        .collect();
+    let question_ids = questions::Entity::find()
+        .filter(questions::Column::Id.is_in(linked_questions))
+        .filter(questions::Column::Status.eq("published"))
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|q| q.id)
+        .collect();
+
+    let linked_lessons: Vec<Uuid> = lesson_road_signs::Entity::find()
+        .filter(lesson_road_signs::Column::SignId.eq(sign.id))
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|link| link.lesson_id)
+        .collect();
+    let lesson_ids = lessons::Entity::find()
+        .filter(lessons::Column::Id.is_in(linked_lessons))
+        .filter(lessons::Column::Status.eq("published"))
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|l| l.id)
+        .collect();
+
+    let image = match sign.image_id {
+        Some(image_id) => image_links(db, [image_id]).await?.remove(&image_id),
+        None => None,
+    };
+
+    Ok(RoadSignDetailResponse {
+        id: sign.id,
+        code: sign.code,
+        group: sign.sign_group,
+        image,
+        translations,
+        question_ids,
+        lesson_ids,
+    })
+}
+
+fn check_group(group: &str) -> Result<(), ApiError> {
+    if SIGN_GROUPS.contains(&group) {
+        Ok(())
+    } else {
+        Err(ApiError::InvalidFieldValue)
+    }
+}
+
+fn check_translations(translations: &[RoadSignTranslationParams]) -> Result<(), ApiError> {
+    let mut langs = HashSet::new();
+    for translation in translations {
+        translation.validate().map_err(ApiError::from)?;
+        if !langs.insert(translation.lang.as_str()) {
+            return Err(ApiError::InvalidFieldValue);
+        }
+    }
+    Ok(())
+}
+
+async fn check_code_free<C: ConnectionTrait>(
+    db: &C,
+    code: &str,
+    sign_id: Option<Uuid>,
+) -> Result<(), ApiError> {
+    let taken = road_signs::Entity::find()
+        .filter(road_signs::Column::Code.eq(code))
+        .one(db)
+        .await
+        .map_err(ApiError::from)?;
+    match taken {
+        Some(sign) if Some(sign.id) != sign_id => Err(ApiError::AlreadyExists),
+        _ => Ok(()),
+    }
+}
+
+/// Adds or replaces the translations of a sign
+async fn save_translations<C: ConnectionTrait>(
+    db: &C,
+    sign_id: Uuid,
+    translations: Vec<RoadSignTranslationParams>,
+) -> Result<(), DbErr> {
+    for translation in translations {
+        road_sign_translations::Entity::delete_by_id((sign_id, translation.lang.clone()))
+            .exec(db)
+            .await?;
+        road_sign_translations::ActiveModel {
+            sign_id: Set(sign_id),
+            lang: Set(translation.lang),
+            name: Set(translation.name),
+            meaning: Set(translation.meaning),
+        }
+        .insert(db)
+        .await?;
+    }
+    Ok(())
+}
+
+/// Signs usable for recognition questions: with an image and a name in `lang`
+pub(crate) async fn recognizable_signs<C: ConnectionTrait>(
+    db: &C,
+    lang: &str,
+) -> Result<Vec<(road_signs::Model, road_sign_translations::Model)>, DbErr> {
+    let mut translations: HashMap<Uuid, road_sign_translations::Model> =
+        road_sign_translations::Entity::find()
+            .filter(road_sign_translations::Column::Lang.eq(lang))
+            .all(db)
+            .await?
+            .into_iter()
+            .map(|t| (t.sign_id, t))
+            .collect();
+
+    Ok(road_signs::Entity::find()
+        .filter(road_signs::Column::ImageId.is_not_null())
+        .all(db)
+        .await?
+        .into_iter()
+        .filter_map(|sign| {
+            let translation = translations.remove(&sign.id)?;
+            Some((sign, translation))
+        })
+        .collect())
+}
+
+/// Snapshots a recognition question for every given sign into the test.
+/// The options are the names of other signs, preferably of the same group;
+/// option IDs are sign IDs, so the correct option has the ID of the question.
+pub(crate) async fn insert_sign_questions<C: ConnectionTrait>(
+    db: &C,
+    test_id: Uuid,
+    lang: &str,
+    sign_ids: &[Uuid],
+) -> Result<(), DbErr> {
+    let catalogue = recognizable_signs(db, lang).await?;
+
+    let mut questions_list = Vec::new();
+    let mut options_list = Vec::new();
+    {
+        let mut rng = rand::thread_rng();
+        for (order, sign_id) in sign_ids.iter().enumerate() {
+            let Some((sign, translation)) = catalogue.iter().find(|(s, _)| s.id == *sign_id) else {
+                continue;
+            };
+
+            let mut same_group: Vec<&(road_signs::Model, road_sign_translations::Model)> =
+                catalogue
+                    .iter()
+                    .filter(|(s, t)| {
+                        s.id != sign.id
+                            && s.sign_group == sign.sign_group
+                            && t.name != translation.name
+                    })
+                    .collect();
+            let mut other_groups: Vec<&(road_signs::Model, road_sign_translations::Model)> =
+                catalogue
+                    .iter()
+                    .filter(|(s, t)| s.sign_group != sign.sign_group && t.name != translation.name)
+                    .collect();
+            same_group.shuffle(&mut rng);
+            other_groups.shuffle(&mut rng);
+
+            // Варианты получают собственные id: id знака совпадает с id вопроса
+            let mut options = vec![(Uuid::new_v4(), translation.name.clone(), true)];
+            for (_, other_translation) in same_group.into_iter().chain(other_groups) {
+                if options.len() == SIGN_OPTIONS {
+                    break;
+                }
+                if options
+                    .iter()
+                    .all(|(_, name, _)| *name != other_translation.name)
+                {
+                    options.push((Uuid::new_v4(), other_translation.name.clone(), false));
+                }
+            }
+            options.shuffle(&mut rng);
+
+            questions_list.push(test_questions::ActiveModel {
+                test_id: Set(test_id),
+                question_id: Set(sign.id),
+                question_order: Set((order + 1) as i16),
+                is_correct: Set(None),
+                answered_at: Set(None),
+                points: Set(1),
+                earned_points: Set(None),
+                question_name: Set(recognition_prompt(lang).to_string()),
+                question_content: Set(None),
+                explanation: Set(format!(
+                    "{} {}: {}",
+                    sign.code, translation.name, translation.meaning
+                )),
+                question_type: Set(QuestionType::Choice.as_str().to_string()),
+                response: Set(None),
+                image_ids: Set(Some(serde_json::json!(
+                    sign.image_id.into_iter().collect::<Vec<_>>()
+                ))),
+            });
+            for (option_order, (answer_id, value, is_correct)) in options.into_iter().enumerate() {
+                options_list.push(test_question_options::ActiveModel {
+                    test_id: Set(test_id),
+                    question_id: Set(sign.id),
+                    answer_id: Set(answer_id),
+                    value: Set(value),
+                    is_correct: Set(is_correct),
+                    option_order: Set((option_order + 1) as i16),
+                    payload: Set(None),
+                    image_id: Set(None),
+                });
+            }
+        }
+    }
+
+    for question in questions_list {
+        question.insert(db).await?;
+    }
+    for option in options_list {
+        option.insert(db).await?;
+    }
+
+    Ok(())
+}
+
+/// Browse the sign catalogue in a language, ordered by code
+#[utoipa::path(
+    get,
+    tag = "Road Signs",
+    path = "/api/road-signs",
+    params(RoadSignsQuery),
+    responses(
+        (status = 200, body = Vec<RoadSignResponse>),
+        ApiError
+    ),
+    security(("jwt_token" = []))
+)]
+async fn list(
+    _auth_user: AuthUser,
+    State(ctx): State<AppContext>,
+    Query(query): Query<RoadSignsQuery>,
+) -> axum::response::Result<Response> {
+    let mut signs_query = road_signs::Entity::find();
+    if let Some(group) = &query.group {
+        check_group(group)?;
+        signs_query = signs_query.filter(road_signs::Column::SignGroup.eq(group));
+    }
+
+    let signs = signs_query.all(&ctx.db).await.map_err(ApiError::from)?;
+    let signs = sign_responses(&ctx.db, signs, &query.lang)
+        .await
+        .map_err(ApiError::from)?;
+
+    Ok(Json(signs).into_response())
+}
+
+/// Search signs by code prefix, name or meaning
+#[utoipa::path(
+    get,
+    tag = "Road Signs",
+    path = "/api/road-signs/search",
+    params(RoadSignSearchQuery),
+    responses(
+        (status = 200, body = Vec<RoadSignResponse>),
+        ApiError
+    ),
+    security(("jwt_token" = []))
+)]
+async fn search(
+    _auth_user: AuthUser,
+    State(ctx): State<AppContext>,
+    Query(query): Query<RoadSignSearchQuery>,
+) -> axum::response::Result<Response> {
+    query.validate().map_err(ApiError::from)?;
+
+    let phrase = query.q.trim().to_lowercase();
+    let escaped = phrase
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_");
+    let contains = LikeExpr::new(format!("%{escaped}%")).escape('\\');
+    let prefix = LikeExpr::new(format!("{escaped}%")).escape('\\');
+
+    let matched: HashSet<Uuid> = road_sign_translations::Entity::find()
+        .filter(road_sign_translations::Column::Lang.eq(&query.lang))
+        .filter(
+            Condition::any()
+                .add(Expr::col(road_sign_translations::Column::Name).ilike(contains.clone()))
+                .add(Expr::col(road_sign_translations::Column::Meaning).ilike(contains)),
+        )
+        .all(&ctx.db)
+        .await
+        .map_err(ApiError::from)?
+        .into_iter()
+        .map(|t| t.sign_id)
+        .collect();
+
+    let signs = road_signs::Entity::find()
+        .filter(
+            Condition::any()
+                .add(road_signs::Column::Id.is_in(matched))
+                .add(Expr::col(road_signs::Column::Code).ilike(prefix)),
+        )
+        .all(&ctx.db)
+        .await
+        .map_err(ApiError::from)?;
+
+    // Совпадение кода важнее совпадения в названии
+    let mut signs = sign_responses(&ctx.db, signs, &query.lang)
+        .await
+        .map_err(ApiError::from)?;
+    signs.sort_by_key(|s| {
+        let rank = if s.code.to_lowercase() == phrase {
+            0
+        } else if s.code.to_lowercase().starts_with(&phrase) {
+            1
+        } else if s.name.to_lowercase().starts_with(&phrase) {
+            2
+        } else {
+            3
+        };
+        (rank, code_key(&s.code))
+    });
+    signs.truncate(query.limit.unwrap_or(20) as usize);
+
+    Ok(Json(signs).into_response())
+}
+
+/// Get a sign with all translations and linked content
+#[utoipa::path(
+    get,
+    tag = "Road Signs",
+    path = "/api/road-signs/{id}",
+    params(("id" = Uuid, Path, description = "Road sign ID")),
+    responses(
+        (status = 200, body = RoadSignDetailResponse),
+        ApiError
+    ),
+    security(("jwt_token" = []))
+)]
+async fn get(
+    _auth_user: AuthUser,
+    Path(id): Path<Uuid>,
+    State(ctx): State<AppContext>,
+) -> axum::response::Result<Response> {
+    let sign = road_signs::Entity::find_by_id(id)
+        .one(&ctx.db)
+        .await
+        .map_err(ApiError::from)?
+        .ok_or(ApiError::NotFound)?;
+
+    let response = detail_response(&ctx.db, sign)
+        .await
+        .map_err(ApiError::from)?;
+    Ok(Json(response).into_response())
+}
+
+/// Add a sign to the catalogue (admins only)
+#[utoipa::path(
+    post,
+    tag = "Road Signs",
+    path = "/api/road-signs",
+    request_body = CreateRoadSignParams,
+    responses(
+        (status = 201, body = RoadSignDetailResponse),
+        ApiError
+    ),
+    security(("jwt_token" = []))
+)]
+async fn create(
+    _admin: AdminUser,
+    State(ctx): State<AppContext>,
+    Json(params): Json<CreateRoadSignParams>,
+) -> axum::response::Result<Response> {
+    params.validate().map_err(ApiError::from)?;
+    check_group(&params.group)?;
+    check_translations(&params.translations)?;
+    if let Some(image_id) = params.image_id {
+        check_images_exist(&ctx.db, &[image_id]).await?;
+    }
+
+    let txn = ctx.db.begin().await.map_err(ApiError::from)?;
+    check_code_free(&txn, &params.code, None).await?;
+
+    let sign = road_signs::ActiveModel {
+        code: Set(params.code),
+        sign_group: Set(params.group),
+        image_id: Set(params.image_id),
+        ..Default::default()
+    }
+    .insert(&txn)
+    .await
+    .map_err(ApiError::from)?;
+    save_translations(&txn, sign.id, params.translations)
+        .await
+        .map_err(ApiError::from)?;
+
+    txn.commit().await.map_err(ApiError::from)?;
+
+    let response = detail_response(&ctx.db, sign)
+        .await
+        .map_err(ApiError::from)?;
+    Ok((axum::http::StatusCode::CREATED, Json(response)).into_response())
+}
+
+/// Update a sign (admins only)
+#[utoipa::path(
+    patch,
+    tag = "Road Signs",
+    path = "/api/road-signs/{id}",
+    params(("id" = Uuid, Path, description = "Road sign ID")),
+    request_body = UpdateRoadSignParams,
+    responses(
+        (status = 200, body = RoadSignDetailResponse),
+        ApiError
+    ),
+    security(("jwt_token" = []))
+)]
+async fn update(
+    _admin: AdminUser,
+    Path(id): Path<Uuid>,
+    State(ctx): State<AppContext>,
+    Json(params): Json<UpdateRoadSignParams>,
+) -> axum::response::Result<Response> {
+    params.validate().map_err(ApiError::from)?;
+    if let Some(group) = &params.group {
+        check_group(group)?;
+    }
+    if let Some(translations) = &params.translations {
+        check_translations(translations)?;
+    }
+    if let Some(image_id) = params.image_id {
+        check_images_exist(&ctx.db, &[image_id]).await?;
+    }
+
+    let txn = ctx.db.begin().await.map_err(ApiError::from)?;
+    let sign = road_signs::Entity::find_by_id(id)
+        .one(&txn)
+        .await
+        .map_err(ApiError::from)?
+        .ok_or(ApiError::NotFound)?;
+
+    let mut to_update = sign.into_active_model();
+    if let Some(code) = params.code {
+        check_code_free(&txn, &code, Some(id)).await?;
+        to_update.code = Set(code);
+    }
+    if let Some(group) = params.group {
+        to_update.sign_group = Set(group);
+    }
+    if let Some(image_id) = params.image_id {
+        to_update.image_id = Set(Some(image_id));
+    }
+    let sign = to_update.update(&txn).await.map_err(ApiError::from)?;
+
+    if let Some(translations) = params.translations {
+        save_translations(&txn, sign.id, translations)
+            .await
+            .map_err(ApiError::from)?;
+    }
+
+    txn.commit().await.map_err(ApiError::from)?;
+
+    let response = detail_response(&ctx.db, sign)
+        .await
+        .map_err(ApiError::from)?;
+    Ok(Json(response).into_response())
+}
+
+/// Remove a sign from the catalogue (admins only)
+#[utoipa::path(
+    delete,
+    tag = "Road Signs",
+    path = "/api/road-signs/{id}",
+    params(("id" = Uuid, Path, description = "Road sign ID")),
+    responses(
+        (status = 200),
+        ApiError
+    ),
+    security(("jwt_token" = []))
+)]
+async fn delete(
+    _admin: AdminUser,
+    Path(id): Path<Uuid>,
+    State(ctx): State<AppContext>,
+) -> axum::response::Result<Response> {
+    let result = road_signs::Entity::delete_by_id(id)
+        .exec(&ctx.db)
+        .await
+        .map_err(ApiError::from)?;
+
+    if result.rows_affected == 0 {
+        return Err(ApiError::NotFound.into());
+    }
+
+    Ok(().into_response())
+}
+
+pub fn routes() -> OpenApiRouter<AppContext> {
+    OpenApiRouter::new()
+        .routes(routes!(list))
+        .routes(routes!(search))
+        .routes(routes!(get))
+        .routes(routes!(create))
+        .routes(routes!(update))
+        .routes(routes!(delete))
+}
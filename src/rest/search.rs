@@ -0,0 +1,237 @@
+use crate::{
+    AppContext,
+    models::search::{FacetCount, SearchFacets, SearchHit, SearchQuery, SearchResponse},
+    rest::curriculum::locked_topics,
+    utils::{
+        config::CURRICULUM_MODE,
+        extractors::{AuthUser, has_active_subscription},
+        response::ApiError,
+    },
+};
+use axum::{
+    Json,
+    extract::{Query, State},
+    response::{IntoResponse, Response},
+};
+use sea_orm::{DatabaseConnection, DbBackend, DbErr, FromQueryResult, Statement, Value};
+use utoipa_axum::{router::OpenApiRouter, routes};
+use uuid::Uuid;
+use validator::Validate;
+
+const DEFAULT_LIMIT: u64 = 20;
+
+/// Options of `ts_headline`, matches are wrapped in `<mark>`
+const HEADLINE_OPTIONS: &str =
+    "StartSel=<mark>, StopSel=</mark>, MaxFragments=2, MaxWords=25, MinWords=8";
+
+/// Published questions matching the phrase by full text or by trigram
+/// similarity of the name. Parameters: $1 phrase, $2 lang, $3 topic,
+/// $4 category, $5 whether the user has a subscription, $6 licence class,
+/// $7 region, $8 JSON array of locked topics. Questions out of force are
+/// skipped.
+const QUESTIONS_MATCH: &str = r#"
+    FROM questions q
+    JOIN topics t ON t.id = q.topic_id
+    WHERE q.status = 'published'
+      AND (q.search_vector @@ websearch_to_tsquery(search_config(q.lang), $1) OR $1 <% q.name)
+      AND ($2::text IS NULL OR q.lang = $2)
+      AND ($3::uuid IS NULL OR q.topic_id = $3)
+      AND ($4::uuid IS NULL OR EXISTS (
+          SELECT 1 FROM question_categories qc
+          WHERE qc.question_id = q.id AND qc.category_id = $4))
+      AND (NOT t.subscription_required OR $5)
+      AND ($6::text IS NULL OR NOT EXISTS (
+          SELECT 1 FROM question_license_classes qlc WHERE qlc.question_id = q.id)
+        OR EXISTS (
+          SELECT 1 FROM question_license_classes qlc
+          WHERE qlc.question_id = q.id AND qlc.license_class = $6))
+      AND (q.valid_from IS NULL OR q.valid_from <= CURRENT_DATE)
+      AND (q.valid_to IS NULL OR q.valid_to >= CURRENT_DATE)
+      AND (q.region IS NULL OR q.region = $7)
+      AND q.topic_id::text NOT IN (SELECT jsonb_array_elements_text($8))"#;
+
+/// Published lessons matching the phrase. Parameters: $1 phrase, $2 topic,
+/// $3 whether the user has a subscription, $4 region, $5 JSON array of
+/// locked topics. Lessons out of force are skipped.
+const LESSONS_MATCH: &str = r#"
+    FROM lessons l
+    JOIN topics t ON t.id = l.topic_id
+    WHERE l.status = 'published'
+      AND (l.search_vector @@ websearch_to_tsquery('simple', $1) OR $1 <% l.content)
+      AND ($2::uuid IS NULL OR l.topic_id = $2)
+      AND (NOT t.subscription_required OR $3)
+      AND (l.valid_from IS NULL OR l.valid_from <= CURRENT_DATE)
+      AND (l.valid_to IS NULL OR l.valid_to >= CURRENT_DATE)
+      AND (l.region IS NULL OR l.region = $4)
+      AND l.topic_id::text NOT IN (SELECT jsonb_array_elements_text($5))"#;
+
+#[derive(Debug, FromQueryResult)]
+struct CountRow {
+    count: i64,
+}
+
+fn statement(sql: String, values: Vec<Value>) -> Statement {
+    Statement::from_sql_and_values(DbBackend::Postgres, sql, values)
+}
+
+async fn count(db: &DatabaseConnection, from: &str, values: Vec<Value>) -> Result<i64, DbErr> {
+    Ok(CountRow::find_by_statement(statement(
+        format!("SELECT count(*) AS count {from}"),
+        values,
+    ))
+    .one(db)
+    .await?
+    .map(|row| row.count)
+    .unwrap_or(0))
+}
+
+/// Search published questions and lessons.
+/// Topics that need a subscription are skipped for users without one,
+/// questions follow the user's licence class and only content in force
+/// for the user's region is returned. In curriculum mode locked topics
+/// are skipped.
+#[utoipa::path(
+    get,
+    tag = "Search",
+    path = "/api/search",
+    params(SearchQuery),
+    responses(
+        (status = 200, body = SearchResponse),
+        ApiError
+    ),
+    security(("jwt_token" = []))
+)]
+async fn search(
+    auth_user: AuthUser,
+    State(ctx): State<AppContext>,
+    Query(query): Query<SearchQuery>,
+) -> axum::response::Result<Response> {
+    query.validate().map_err(ApiError::from)?;
+
+    let subscribed = has_active_subscription(&ctx.db, auth_user.user.id).await?;
+    let limit = query.limit.unwrap_or(DEFAULT_LIMIT) as i64;
+    let offset = query.offset as i64;
+    let phrase = query.q.trim().to_string();
+    let locked: Vec<Uuid> = if *CURRICULUM_MODE {
+        locked_topics(&ctx.db, &auth_user.user)
+            .await
+            .map_err(ApiError::from)?
+            .into_iter()
+            .collect()
+    } else {
+        Vec::new()
+    };
+    let locked = serde_json::json!(locked);
+
+    let question_values: Vec<Value> = vec![
+        phrase.clone().into(),
+        query.lang.clone().into(),
+        query.topic_id.into(),
+        query.category_id.into(),
+        subscribed.into(),
+        auth_user.user.license_class.clone().into(),
+        auth_user.user.region.clone().into(),
+        locked.clone().into(),
+    ];
+
+    let mut values = question_values.clone();
+    values.extend([HEADLINE_OPTIONS.into(), limit.into(), offset.into()]);
+    let questions = SearchHit::find_by_statement(statement(
+        format!(
+            r#"SELECT q.id, q.topic_id, t.name AS topic_name, q.name AS title,
+                ts_headline(search_config(q.lang), concat_ws(' ', q.name, q.content, q.explanation),
+                    websearch_to_tsquery(search_config(q.lang), $1), $9) AS snippet,
+                (ts_rank(q.search_vector, websearch_to_tsquery(search_config(q.lang), $1))
+                    + word_similarity($1, q.name))::float8 AS rank
+            {QUESTIONS_MATCH}
+            ORDER BY rank DESC, q.id
+            LIMIT $10 OFFSET $11"#
+        ),
+        values,
+    ))
+    .all(&ctx.db)
+    .await
+    .map_err(ApiError::from)?;
+
+    let total_questions = count(&ctx.db, QUESTIONS_MATCH, question_values.clone())
+        .await
+        .map_err(ApiError::from)?;
+
+    let topics = FacetCount::find_by_statement(statement(
+        format!(
+            r#"SELECT t.id, t.name, count(*) AS count
+            {QUESTIONS_MATCH}
+            GROUP BY t.id, t.name
+            ORDER BY count DESC, t.name"#
+        ),
+        question_values.clone(),
+    ))
+    .all(&ctx.db)
+    .await
+    .map_err(ApiError::from)?;
+
+    let categories = FacetCount::find_by_statement(statement(
+        format!(
+            r#"SELECT c.id, c.name, count(*) AS count
+            FROM question_categories link
+            JOIN categories c ON c.id = link.category_id
+            WHERE link.question_id IN (SELECT q.id {QUESTIONS_MATCH})
+            GROUP BY c.id, c.name
+            ORDER BY count DESC, c.name"#
+        ),
+        question_values,
+    ))
+    .all(&ctx.db)
+    .await
+    .map_err(ApiError::from)?;
+
+    // У уроков нет категорий
+    let (lessons, total_lessons) = if query.category_id.is_some() {
+        (Vec::new(), 0)
+    } else {
+        let lesson_values: Vec<Value> = vec![
+            phrase.into(),
+            query.topic_id.into(),
+            subscribed.into(),
+            auth_user.user.region.clone().into(),
+            locked.into(),
+        ];
+
+        let mut values = lesson_values.clone();
+        values.extend([HEADLINE_OPTIONS.into(), limit.into(), offset.into()]);
+        let lessons = SearchHit::find_by_statement(statement(
+            format!(
+                r#"SELECT l.id, l.topic_id, t.name AS topic_name, l.title,
+                    ts_headline('simple', l.content, websearch_to_tsquery('simple', $1), $6) AS snippet,
+                    (ts_rank(l.search_vector, websearch_to_tsquery('simple', $1))
+                        + word_similarity($1, l.content))::float8 AS rank
+                {LESSONS_MATCH}
+                ORDER BY rank DESC, l.id
+                LIMIT $7 OFFSET $8"#
+            ),
+            values,
+        ))
+        .all(&ctx.db)
+        .await
+        .map_err(ApiError::from)?;
+
+        let total = count(&ctx.db, LESSONS_MATCH, lesson_values)
+            .await
+            .map_err(ApiError::from)?;
+
+        (lessons, total)
+    };
+
+    Ok(Json(SearchResponse {
+        questions,
+        total_questions,
+        lessons,
+        total_lessons,
+        facets: SearchFacets { topics, categories },
+    })
+    .into_response())
+}
+
+pub fn routes() -> OpenApiRouter<AppContext> {
+    OpenApiRouter::new().routes(routes!(search))
+}
@@ -0,0 +1,403 @@
+use crate::{
+    AppContext,
+    entities::{questions, test_template_questions, test_templates},
+    models::test_templates::{
+        CreateTestTemplateParams, TemplatesQuery, TestTemplateResponse, UpdateTestTemplateParams,
+    },
+    rest::{
+        curriculum::locked_topics,
+        license_classes::{check_license_classes_exist, license_class_condition},
+    },
+    utils::{
+        config::CURRICULUM_MODE,
+        extractors::{AdminUser, AuthUser, check_template_access},
+        response::ApiError,
+    },
+};
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+    response::{IntoResponse, Response},
+};
+use sea_orm::{
+    ActiveModelTrait, ActiveValue::Set, ColumnTrait, ConnectionTrait, EntityTrait, IntoActiveModel,
+    PaginatorTrait, QueryFilter, QueryOrder, TransactionTrait,
+};
+use std::collections::HashSet;
+use utoipa_axum::{router::OpenApiRouter, routes};
+use uuid::Uuid;
+use validator::Validate;
+
+/// Question ids of the template in test order
+async fn load_question_ids<C: ConnectionTrait>(
+    db: &C,
+    template_id: Uuid,
+) -> Result<Vec<Uuid>, ApiError> {
+    Ok(test_template_questions::Entity::find()
+        .filter(test_template_questions::Column::TemplateId.eq(template_id))
+        .order_by_asc(test_template_questions::Column::QuestionOrder)
+        .all(db)
+        .await
+        .map_err(ApiError::from)?
+        .into_iter()
+        .map(|tq| tq.question_id)
+        .collect())
+}
+
+/// Whether a user with the given licence class may take the template
+pub(crate) fn template_matches_class(
+    template: &test_templates::Model,
+    license_class: Option<&str>,
+) -> bool {
+    match (template.license_class.as_deref(), license_class) {
+        (Some(template_class), Some(user_class)) => template_class == user_class,
+        _ => true,
+    }
+}
+
+/// Checks that questions are unique, exist and match the template language
+/// and licence class
+pub(crate) async fn validate_questions<C: ConnectionTrait>(
+    db: &C,
+    question_ids: &[Uuid],
+    lang: &str,
+    license_class: Option<&str>,
+) -> Result<(), ApiError> {
+    let unique: HashSet<Uuid> = question_ids.iter().cloned().collect();
+    if unique.len() != question_ids.len() {
+        return Err(ApiError::InvalidInput);
+    }
+
+    let found = questions::Entity::find()
+        .filter(questions::Column::Id.is_in(unique))
+        .filter(questions::Column::Lang.eq(lang))
+        .filter(questions::Column::Status.eq("published"))
+        .filter(license_class_condition(license_class))
+        .count(db)
+        .await
+        .map_err(ApiError::from)?;
+
+    if found as usize != question_ids.len() {
+        return Err(ApiError::InvalidFieldValue);
+    }
+
+    Ok(())
+}
+
+/// Replaces the template question list
+async fn replace_questions<C: ConnectionTrait>(
+    db: &C,
+    template_id: Uuid,
+    question_ids: &[Uuid],
+) -> Result<(), ApiError> {
+    test_template_questions::Entity::delete_many()
+        .filter(test_template_questions::Column::TemplateId.eq(template_id))
+        .exec(db)
+        .await
+        .map_err(ApiError::from)?;
+
+    for (order, question_id) in question_ids.iter().enumerate() {
+        let link = test_template_questions::ActiveModel {
+            template_id: Set(template_id),
+            question_id: Set(*question_id),
+            question_order: Set((order + 1) as i16),
+        };
+        link.insert(db).await.map_err(ApiError::from)?;
+    }
+
+    Ok(())
+}
+
+/// List all templates (admin)
+#[utoipa::path(
+    get,
+    tag = "Test Templates",
+    path = "/api/test_templates",
+    responses(
+        (status = 200, body = Vec<TestTemplateResponse>),
+        ApiError
+    ),
+    security(("jwt_token" = []))
+)]
+async fn list(
+    _admin: AdminUser,
+    State(ctx): State<AppContext>,
+) -> axum::response::Result<Response> {
+    let templates = test_templates::Entity::find()
+        .filter(test_templates::Column::IsDeleted.eq(false))
+        .order_by_desc(test_templates::Column::CreatedAt)
+        .all(&ctx.db)
+        .await
+        .map_err(ApiError::from)?;
+
+    let mut responses = Vec::new();
+    for template in templates {
+        let question_ids = load_question_ids(&ctx.db, template.id).await?;
+        responses.push(TestTemplateResponse::from_model(template, question_ids));
+    }
+
+    Ok(Json(responses).into_response())
+}
+
+/// List published templates available to the user and their licence class
+#[utoipa::path(
+    get,
+    tag = "Test Templates",
+    path = "/api/test_templates/published",
+    params(TemplatesQuery),
+    responses(
+        (status = 200, body = Vec<TestTemplateResponse>),
+        ApiError
+    ),
+    security(("jwt_token" = []))
+)]
+async fn list_published(
+    auth_user: AuthUser,
+    State(ctx): State<AppContext>,
+    Query(query): Query<TemplatesQuery>,
+) -> axum::response::Result<Response> {
+    let mut templates_query = test_templates::Entity::find()
+        .filter(test_templates::Column::IsDeleted.eq(false))
+        .filter(test_templates::Column::IsPublished.eq(true));
+
+    if let Some(lang) = query.lang {
+        templates_query = templates_query.filter(test_templates::Column::Lang.eq(lang));
+    }
+
+    let templates = templates_query
+        .order_by_asc(test_templates::Column::Title)
+        .all(&ctx.db)
+        .await
+        .map_err(ApiError::from)?;
+
+    // В режиме учебной программы шаблоны с закрытыми темами не показываем
+    let locked = if *CURRICULUM_MODE {
+        locked_topics(&ctx.db, &auth_user.user)
+            .await
+            .map_err(ApiError::from)?
+    } else {
+        HashSet::new()
+    };
+
+    let mut responses = Vec::new();
+    for template in templates {
+        // Шаблоны другого класса прав не показываем
+        if !template_matches_class(&template, auth_user.user.license_class.as_deref()) {
+            continue;
+        }
+
+        // Шаблоны с темами по подписке показываем только подписчикам
+        match check_template_access(&ctx.db, auth_user.user.id, template.id).await {
+            Ok(topic_ids) if topic_ids.is_disjoint(&locked) => {}
+            Ok(_) | Err(ApiError::PaymentRequired) => continue,
+            Err(err) => return Err(err.into()),
+        }
+
+        let question_ids = load_question_ids(&ctx.db, template.id).await?;
+        responses.push(TestTemplateResponse::from_model(template, question_ids));
+    }
+
+    Ok(Json(responses).into_response())
+}
+
+/// Get template by id (admin)
+#[utoipa::path(
+    get,
+    tag = "Test Templates",
+    path = "/api/test_templates/{id}",
+    params(("id" = Uuid, Path, description = "Template ID")),
+    responses(
+        (status = 200, body = TestTemplateResponse),
+        ApiError
+    ),
+    security(("jwt_token" = []))
+)]
+async fn get(
+    _admin: AdminUser,
+    Path(id): Path<Uuid>,
+    State(ctx): State<AppContext>,
+) -> axum::response::Result<Response> {
+    let template = test_templates::Entity::find_by_id(id)
+        .filter(test_templates::Column::IsDeleted.eq(false))
+        .one(&ctx.db)
+        .await
+        .map_err(ApiError::from)?
+        .ok_or(ApiError::NotFound)?;
+
+    let question_ids = load_question_ids(&ctx.db, template.id).await?;
+
+    Ok(Json(TestTemplateResponse::from_model(template, question_ids)).into_response())
+}
+
+/// Create template (admin)
+#[utoipa::path(
+    post,
+    tag = "Test Templates",
+    path = "/api/test_templates",
+    request_body = CreateTestTemplateParams,
+    responses(
+        (status = 201, body = TestTemplateResponse),
+        ApiError
+    ),
+    security(("jwt_token" = []))
+)]
+async fn create(
+    admin: AdminUser,
+    State(ctx): State<AppContext>,
+    Json(params): Json<CreateTestTemplateParams>,
+) -> axum::response::Result<Response> {
+    params.validate().map_err(ApiError::from)?;
+
+    let txn = ctx.db.begin().await.map_err(ApiError::from)?;
+
+    if let Some(license_class) = &params.license_class {
+        check_license_classes_exist(&txn, std::slice::from_ref(license_class)).await?;
+    }
+    validate_questions(
+        &txn,
+        &params.question_ids,
+        &params.lang,
+        params.license_class.as_deref(),
+    )
+    .await?;
+
+    let template = test_templates::ActiveModel {
+        title: Set(params.title),
+        lang: Set(params.lang),
+        time_limit_seconds: Set(params.time_limit_seconds),
+        is_published: Set(params.is_published),
+        created_by: Set(Some(admin.user.id)),
+        license_class: Set(params.license_class),
+        ..Default::default()
+    };
+
+    let template = template.insert(&txn).await.map_err(ApiError::from)?;
+
+    replace_questions(&txn, template.id, &params.question_ids).await?;
+
+    txn.commit().await.map_err(ApiError::from)?;
+
+    Ok((
+        axum::http::StatusCode::CREATED,
+        Json(TestTemplateResponse::from_model(
+            template,
+            params.question_ids,
+        )),
+    )
+        .into_response())
+}
+
+/// Update template by id (admin)
+#[utoipa::path(
+    patch,
+    tag = "Test Templates",
+    path = "/api/test_templates/{id}",
+    params(("id" = Uuid, Path, description = "Template ID")),
+    request_body = UpdateTestTemplateParams,
+    responses(
+        (status = 200, body = TestTemplateResponse),
+        ApiError
+    ),
+    security(("jwt_token" = []))
+)]
+async fn update(
+    _admin: AdminUser,
+    Path(id): Path<Uuid>,
+    State(ctx): State<AppContext>,
+    Json(params): Json<UpdateTestTemplateParams>,
+) -> axum::response::Result<Response> {
+    params.validate().map_err(ApiError::from)?;
+
+    let txn = ctx.db.begin().await.map_err(ApiError::from)?;
+
+    let template = test_templates::Entity::find_by_id(id)
+        .filter(test_templates::Column::IsDeleted.eq(false))
+        .one(&txn)
+        .await
+        .map_err(ApiError::from)?
+        .ok_or(ApiError::NotFound)?;
+
+    let lang = params.lang.clone().unwrap_or(template.lang.clone());
+    let question_ids = match params.question_ids {
+        Some(question_ids) => question_ids,
+        None => load_question_ids(&txn, template.id).await?,
+    };
+
+    let license_class = match params.license_class {
+        Some(license_class) if license_class.is_empty() => None,
+        Some(license_class) => {
+            check_license_classes_exist(&txn, std::slice::from_ref(&license_class)).await?;
+            Some(license_class)
+        }
+        None => template.license_class.clone(),
+    };
+
+    // Язык, класс прав и список вопросов должны оставаться согласованными
+    validate_questions(&txn, &question_ids, &lang, license_class.as_deref()).await?;
+
+    let mut to_update = template.into_active_model();
+
+    if let Some(title) = params.title {
+        to_update.title = Set(title);
+    }
+    if let Some(lang) = params.lang {
+        to_update.lang = Set(lang);
+    }
+    if let Some(time_limit_seconds) = params.time_limit_seconds {
+        to_update.time_limit_seconds = Set(Some(time_limit_seconds).filter(|&s| s > 0));
+    }
+    if let Some(is_published) = params.is_published {
+        to_update.is_published = Set(is_published);
+    }
+    to_update.license_class = Set(license_class);
+    to_update.updated_at = Set(chrono::Utc::now().into());
+
+    let template = to_update.update(&txn).await.map_err(ApiError::from)?;
+
+    replace_questions(&txn, template.id, &question_ids).await?;
+
+    txn.commit().await.map_err(ApiError::from)?;
+
+    Ok(Json(TestTemplateResponse::from_model(template, question_ids)).into_response())
+}
+
+/// Delete (soft delete) template by id (admin)
+#[utoipa::path(
+    delete,
+    tag = "Test Templates",
+    path = "/api/test_templates/{id}",
+    params(("id" = Uuid, Path, description = "Template ID")),
+    responses(
+        (status = 200),
+        ApiError
+    ),
+    security(("jwt_token" = []))
+)]
+async fn delete(
+    _admin: AdminUser,
+    Path(id): Path<Uuid>,
+    State(ctx): State<AppContext>,
+) -> axum::response::Result<Response> {
+    let template = test_templates::Entity::find_by_id(id)
+        .filter(test_templates::Column::IsDeleted.eq(false))
+        .one(&ctx.db)
+        .await
+        .map_err(ApiError::from)?
+        .ok_or(ApiError::NotFound)?;
+
+    let mut to_update = template.into_active_model();
+    to_update.is_deleted = Set(true);
+    to_update.update(&ctx.db).await.map_err(ApiError::from)?;
+
+    Ok(().into_response())
+}
+
+pub fn routes() -> OpenApiRouter<AppContext> {
+    OpenApiRouter::new()
+        .routes(routes!(list))
+        .routes(routes!(list_published))
+        .routes(routes!(get))
+        .routes(routes!(create))
+        .routes(routes!(update))
+        .routes(routes!(delete))
+}
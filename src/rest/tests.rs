@@ -1,16 +1,37 @@
 use crate::{
     AppContext,
     entities::{
-        answers, categories, question_categories, questions, test_question_answers, test_questions,
-        tests, topics, user_favorite_questions,
+        answers, categories, daily_challenge_questions, daily_challenges, question_categories,
+        questions, test_question_answers, test_question_options, test_questions,
+        test_template_questions, test_templates, tests, topics, user_favorite_questions,
     },
+    models::images::ImageLink,
+    models::question_types::{AnswerPayload, QuestionType, SubmittedAnswer},
     models::tests::{
         AnswerOption, AnswerOptionWithCorrectness, AnswerParams, AnswerResultResponse,
-        CompleteTestResponse, CreateTestParams, CurrentQuestionResponse, QuestionInfo,
-        QuestionInfoWithExplanation, ReviewQuestionResponse, TestDetailResponse, TestQuestionInfo,
-        TestResponse, TestReviewResponse, TestsQuery,
+        CompleteTestResponse, CreateTestParams, CurrentQuestionResponse, HistoryQuery,
+        QuestionInfo, QuestionInfoWithExplanation, RetakeTestParams, ReviewQuestionResponse,
+        TestAttemptResponse, TestDetailResponse, TestQuestionInfo, TestResponse,
+        TestReviewResponse, TestsQuery,
+    },
+    rest::{
+        categories::category_subtree,
+        curriculum::locked_topics,
+        images::image_links,
+        license_classes::license_class_condition,
+        question_translations::translation_groups,
+        questions::question_image_ids,
+        regions::questions_in_effect,
+        road_signs::{insert_sign_questions, recognizable_signs},
+        test_templates::template_matches_class,
+    },
+    tasks::daily_challenges::ensure_daily_challenge,
+    utils::{
+        config::CURRICULUM_MODE,
+        extractors::{AuthUser, check_template_access},
+        response::ApiError,
+        scoring::{AnswerSubmission, ScoringPolicy, score_percent},
     },
-    utils::{extractors::AuthUser, response::ApiError},
 };
 use axum::{
     Json,
@@ -18,23 +39,293 @@ use axum::{
     response::{IntoResponse, Response},
 };
 use rand::seq::SliceRandom;
+use rust_decimal::Decimal;
 use sea_orm::{
-    ActiveModelTrait, ActiveValue::Set, ColumnTrait, EntityTrait, IntoActiveModel, QueryFilter,
-    QueryOrder, TransactionTrait,
+    ActiveModelTrait, ActiveValue::Set, ColumnTrait, Condition, ConnectionTrait, DbErr,
+    EntityTrait, IntoActiveModel, QueryFilter, QueryOrder, TransactionTrait,
 };
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use utoipa_axum::{router::OpenApiRouter, routes};
 use uuid::Uuid;
 use validator::Validate;
 
 /// Helper to generate filter_hash
-fn generate_filter_hash(filter_type: &str, filter_id: Option<Uuid>, lang: &str) -> String {
+pub(crate) fn generate_filter_hash(
+    filter_type: &str,
+    filter_id: Option<Uuid>,
+    lang: &str,
+) -> String {
     match filter_id {
         Some(id) => format!("{}:{}:{}", filter_type, id, lang),
         None => format!("{}:{}", filter_type, lang),
     }
 }
 
+/// Inserts test questions in the given order together with a snapshot of
+/// their content, so later edits of the bank do not change the test
+pub(crate) async fn insert_test_questions<C: ConnectionTrait>(
+    db: &C,
+    test_id: Uuid,
+    questions: &[(Uuid, i16)],
+) -> Result<(), ApiError> {
+    let question_ids: Vec<Uuid> = questions.iter().map(|(id, _)| *id).collect();
+
+    let mut contents: HashMap<Uuid, questions::Model> = questions::Entity::find()
+        .filter(questions::Column::Id.is_in(question_ids.clone()))
+        .all(db)
+        .await
+        .map_err(ApiError::from)?
+        .into_iter()
+        .map(|q| (q.id, q))
+        .collect();
+
+    let mut image_ids = question_image_ids(db, &question_ids)
+        .await
+        .map_err(ApiError::from)?;
+
+    let mut options: HashMap<Uuid, Vec<answers::Model>> = HashMap::new();
+    for answer in answers::Entity::find()
+        .filter(answers::Column::QuestionId.is_in(question_ids))
+        .order_by_asc(answers::Column::Id)
+        .all(db)
+        .await
+        .map_err(ApiError::from)?
+    {
+        options.entry(answer.question_id).or_default().push(answer);
+    }
+
+    for (order, (question_id, points)) in questions.iter().enumerate() {
+        let question = contents.remove(question_id).ok_or(ApiError::NotFound)?;
+        let question_image_ids = image_ids.remove(question_id).unwrap_or_default();
+
+        let tq = test_questions::ActiveModel {
+            test_id: Set(test_id),
+            question_id: Set(*question_id),
+            question_order: Set((order + 1) as i16),
+            is_correct: Set(None),
+            answered_at: Set(None),
+            points: Set(*points),
+            earned_points: Set(None),
+            question_name: Set(question.name),
+            question_content: Set(question.content),
+            explanation: Set(question.explanation),
+            question_type: Set(question.question_type),
+            response: Set(None),
+            image_ids: Set(Some(serde_json::json!(question_image_ids))),
+        };
+        tq.insert(db).await.map_err(ApiError::from)?;
+
+        for (option_order, answer) in options
+            .remove(question_id)
+            .unwrap_or_default()
+            .into_iter()
+            .enumerate()
+        {
+            let option = test_question_options::ActiveModel {
+                test_id: Set(test_id),
+                question_id: Set(*question_id),
+                answer_id: Set(answer.id),
+                value: Set(answer.value),
+                is_correct: Set(answer.is_correct),
+                option_order: Set((option_order + 1) as i16),
+                payload: Set(answer.payload),
+                image_id: Set(answer.image_id),
+            };
+            option.insert(db).await.map_err(ApiError::from)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Answer options of a test question as they were when the test was created
+pub(crate) async fn snapshot_options<C: ConnectionTrait>(
+    db: &C,
+    test_id: Uuid,
+    question_id: Uuid,
+) -> Result<Vec<test_question_options::Model>, DbErr> {
+    test_question_options::Entity::find()
+        .filter(test_question_options::Column::TestId.eq(test_id))
+        .filter(test_question_options::Column::QuestionId.eq(question_id))
+        .order_by_asc(test_question_options::Column::OptionOrder)
+        .all(db)
+        .await
+}
+
+/// Images snapshotted with the test question, in display order
+fn snapshot_image_ids(tq: &test_questions::Model) -> Vec<Uuid> {
+    tq.image_ids
+        .clone()
+        .and_then(|value| serde_json::from_value(value).ok())
+        .unwrap_or_default()
+}
+
+/// Links of the question and option images of a snapshotted question.
+/// Images deleted since the test was created are left out.
+pub(crate) async fn snapshot_images<C: ConnectionTrait>(
+    db: &C,
+    tq: &test_questions::Model,
+    options: &[test_question_options::Model],
+) -> Result<(Vec<ImageLink>, HashMap<Uuid, ImageLink>), DbErr> {
+    let image_ids = snapshot_image_ids(tq);
+    let links = image_links(
+        db,
+        image_ids
+            .iter()
+            .cloned()
+            .chain(options.iter().filter_map(|o| o.image_id)),
+    )
+    .await?;
+    let images = image_ids
+        .iter()
+        .filter_map(|id| links.get(id).cloned())
+        .collect();
+    Ok((images, links))
+}
+
+/// Test question from its snapshot, with the set of correct answers
+pub(crate) async fn snapshot_question<C: ConnectionTrait>(
+    db: &C,
+    tq: test_questions::Model,
+    lang: &str,
+) -> Result<(CurrentQuestionResponse, HashSet<Uuid>), DbErr> {
+    let options = snapshot_options(db, tq.test_id, tq.question_id).await?;
+    let (images, links) = snapshot_images(db, &tq, &options).await?;
+    let question_type = tq.question_type.parse::<QuestionType>().unwrap_or_default();
+    let correct_ids: HashSet<Uuid> = options
+        .iter()
+        .filter(|o| o.is_correct)
+        .map(|o| o.answer_id)
+        .collect();
+
+    Ok((
+        CurrentQuestionResponse {
+            order: tq.question_order,
+            question: QuestionInfo {
+                id: tq.question_id,
+                name: tq.question_name,
+                content: tq.question_content,
+                lang: lang.to_string(),
+            },
+            question_type: tq.question_type,
+            images,
+            answers: options
+                .into_iter()
+                .filter(|_| question_type.shows_options())
+                .map(|o| AnswerOption {
+                    id: o.answer_id,
+                    image: o.image_id.and_then(|id| links.get(&id).cloned()),
+                    value: o.value,
+                })
+                .collect(),
+            multiple_answers: question_type == QuestionType::Choice && correct_ids.len() > 1,
+        },
+        correct_ids,
+    ))
+}
+
+/// Snapshotted option with its correctness and payload
+pub(crate) fn option_with_correctness(
+    option: test_question_options::Model,
+    links: &HashMap<Uuid, ImageLink>,
+) -> AnswerOptionWithCorrectness {
+    AnswerOptionWithCorrectness {
+        id: option.answer_id,
+        payload: AnswerPayload::from_json(option.payload.as_ref()),
+        image: option.image_id.and_then(|id| links.get(&id).cloned()),
+        value: option.value,
+        is_correct: option.is_correct,
+    }
+}
+
+/// Correct options of a question, every option of an ordering question in its correct order
+pub(crate) fn correct_answers(
+    question_type: QuestionType,
+    options: &[test_question_options::Model],
+    links: &HashMap<Uuid, ImageLink>,
+) -> Vec<AnswerOptionWithCorrectness> {
+    let mut correct: Vec<AnswerOptionWithCorrectness> = options
+        .iter()
+        .filter(|o| o.is_correct || question_type == QuestionType::Ordering)
+        .cloned()
+        .map(|o| option_with_correctness(o, links))
+        .collect();
+    correct.sort_by_key(|o| match o.payload {
+        Some(AnswerPayload::Ordering { position }) => position,
+        _ => 0,
+    });
+    correct
+}
+
+/// Grades the answer against the snapshot and stores it on the test
+/// question. Returns correctness, earned points and the snapshotted options.
+pub(crate) async fn grade_answer<C: ConnectionTrait>(
+    db: &C,
+    test: &tests::Model,
+    tq: test_questions::Model,
+    answer: &SubmittedAnswer,
+) -> Result<(bool, Decimal, Vec<test_question_options::Model>), DbErr> {
+    let options = snapshot_options(db, test.id, tq.question_id).await?;
+    let question_type = tq.question_type.parse::<QuestionType>().unwrap_or_default();
+
+    // Points depend on the test scoring policy
+    let submission = AnswerSubmission::grade(question_type, tq.points, &options, answer);
+    let is_correct = submission.is_exact();
+    let policy = test
+        .scoring_policy
+        .parse::<ScoringPolicy>()
+        .unwrap_or_default();
+    let earned_points = policy.strategy().score(&submission);
+
+    // Выбранные варианты сохраняются для статистики, чужие id отбрасываются
+    let option_ids: HashSet<Uuid> = options.iter().map(|o| o.answer_id).collect();
+    let selected_ids: HashSet<Uuid> = answer
+        .answer_ids
+        .iter()
+        .filter(|id| option_ids.contains(id))
+        .cloned()
+        .collect();
+    for answer_id in selected_ids {
+        let tqa = test_question_answers::ActiveModel {
+            test_id: Set(test.id),
+            question_id: Set(tq.question_id),
+            answer_id: Set(answer_id),
+        };
+        tqa.insert(db).await?;
+    }
+
+    let mut tq_active = tq.into_active_model();
+    tq_active.is_correct = Set(Some(is_correct));
+    tq_active.earned_points = Set(Some(earned_points));
+    tq_active.answered_at = Set(Some(chrono::Utc::now().into()));
+    tq_active.response = Set(Some(
+        serde_json::to_value(answer).map_err(|err| DbErr::Custom(err.to_string()))?,
+    ));
+    tq_active.update(db).await?;
+
+    Ok((is_correct, earned_points, options))
+}
+
+/// Whether the test time limit has run out
+fn is_time_over(test: &tests::Model) -> bool {
+    test.time_limit_seconds.is_some_and(|limit| {
+        chrono::Utc::now().signed_duration_since(test.created_at)
+            > chrono::Duration::seconds(limit as i64)
+    })
+}
+
+/// Duel and classroom tests are answered over their WebSockets
+fn is_live(test: &tests::Model) -> bool {
+    ["duel", "classroom"].contains(&test.filter_type.as_str())
+}
+
+/// Condition matching every attempt of a retake series
+fn series_condition(series_id: Uuid) -> Condition {
+    Condition::any()
+        .add(tests::Column::Id.eq(series_id))
+        .add(tests::Column::SeriesId.eq(series_id))
+}
+
 /// List user's tests
 #[utoipa::path(
     get,
@@ -124,6 +415,8 @@ async fn get(
             question_id: tq.question_id,
             is_answered: tq.answered_at.is_some(),
             is_correct: tq.is_correct,
+            points: tq.points,
+            earned_points: tq.earned_points,
         })
         .collect();
 
@@ -139,6 +432,10 @@ async fn get(
         correct_count: test.correct_count,
         status: test.status,
         score_percent: test.score_percent,
+        scoring_policy: test.scoring_policy,
+        max_points: test.max_points,
+        earned_points: test.earned_points,
+        time_limit_seconds: test.time_limit_seconds,
         questions,
     })
     .into_response())
@@ -164,16 +461,72 @@ async fn create(
     params.validate().map_err(ApiError::from)?;
 
     // Validate filter_type
-    if !["favorites", "category", "topic"].contains(&params.filter_type.as_str()) {
+    if ![
+        "favorites",
+        "category",
+        "topic",
+        "template",
+        "daily",
+        "signs",
+    ]
+    .contains(&params.filter_type.as_str())
+    {
         return Err(ApiError::InvalidFieldValue.into());
     }
 
     // Validate filter_id requirement
-    if params.filter_type != "favorites" && params.filter_id.is_none() {
+    if !["favorites", "daily", "signs"].contains(&params.filter_type.as_str())
+        && params.filter_id.is_none()
+    {
         return Err(ApiError::MissingField.into());
     }
 
-    let filter_hash = generate_filter_hash(&params.filter_type, params.filter_id, &params.lang);
+    let scoring_policy = match params.scoring_policy.as_deref() {
+        Some(policy) => policy
+            .parse::<ScoringPolicy>()
+            .map_err(|_| ApiError::InvalidFieldValue)?,
+        None => ScoringPolicy::default(),
+    };
+
+    let mut filter_id = params.filter_id;
+
+    // Daily challenge: only today's challenge, a single attempt per user
+    if params.filter_type == "daily" {
+        let today = chrono::Utc::now().date_naive();
+        let challenge = match params.filter_id {
+            Some(challenge_id) => daily_challenges::Entity::find_by_id(challenge_id)
+                .one(&ctx.db)
+                .await
+                .map_err(ApiError::from)?,
+            None => ensure_daily_challenge(&ctx.db, today, &params.lang)
+                .await
+                .map_err(ApiError::from)?,
+        }
+        .ok_or(ApiError::NotFound)?;
+
+        if challenge.challenge_date != today {
+            return Err(ApiError::InvalidState.into());
+        }
+        if challenge.lang != params.lang {
+            return Err(ApiError::InvalidFieldValue.into());
+        }
+
+        let attempted = tests::Entity::find()
+            .filter(tests::Column::UserId.eq(auth_user.user.id))
+            .filter(tests::Column::FilterType.eq("daily"))
+            .filter(tests::Column::FilterId.eq(challenge.id))
+            .one(&ctx.db)
+            .await
+            .map_err(ApiError::from)?;
+
+        if attempted.is_some() {
+            return Err(ApiError::AlreadyExists.into());
+        }
+
+        filter_id = Some(challenge.id);
+    }
+
+    let filter_hash = generate_filter_hash(&params.filter_type, filter_id, &params.lang);
 
     // Check for existing active test with same filter
     let existing = tests::Entity::find()
@@ -190,6 +543,23 @@ async fn create(
         return Err(ApiError::AlreadyExists.into());
     }
 
+    // Template tests keep the template order and size
+    let mut fixed_order = false;
+    let mut time_limit_seconds = None;
+    // Pools follow the class from the profile, templates carry their own
+    let mut license_class = auth_user.user.license_class.clone();
+
+    // В режиме учебной программы вопросы закрытых тем не попадают в тест
+    let locked: Vec<Uuid> = if *CURRICULUM_MODE {
+        locked_topics(&ctx.db, &auth_user.user)
+            .await
+            .map_err(ApiError::from)?
+            .into_iter()
+            .collect()
+    } else {
+        Vec::new()
+    };
+
     // Get questions based on filter_type
     let question_ids: Vec<Uuid> = match params.filter_type.as_str() {
         "favorites" => {
@@ -200,10 +570,18 @@ async fn create(
                 .map_err(ApiError::from)?;
 
             let fav_question_ids: Vec<Uuid> = favorites.iter().map(|f| f.question_id).collect();
+            // Избранное на другом языке подставляется переводом
+            let fav_groups = translation_groups(&ctx.db, fav_question_ids)
+                .await
+                .map_err(ApiError::from)?;
 
             questions::Entity::find()
-                .filter(questions::Column::Id.is_in(fav_question_ids))
+                .filter(questions::Column::TranslationGroupId.is_in(fav_groups))
+                .filter(questions::Column::TopicId.is_not_in(locked.clone()))
                 .filter(questions::Column::Lang.eq(&params.lang))
+                .filter(questions::Column::Status.eq("published"))
+                .filter(license_class_condition(license_class.as_deref()))
+                .filter(questions_in_effect(auth_user.user.region.as_deref()))
                 .all(&ctx.db)
                 .await
                 .map_err(ApiError::from)?
@@ -221,8 +599,12 @@ async fn create(
                 .map_err(ApiError::from)?
                 .ok_or(ApiError::NotFound)?;
 
+            // Подкатегории входят в выборку
+            let category_ids = category_subtree(&ctx.db, category_id)
+                .await
+                .map_err(ApiError::from)?;
             let qc = question_categories::Entity::find()
-                .filter(question_categories::Column::CategoryId.eq(category_id))
+                .filter(question_categories::Column::CategoryId.is_in(category_ids))
                 .all(&ctx.db)
                 .await
                 .map_err(ApiError::from)?;
@@ -231,7 +613,11 @@ async fn create(
 
             questions::Entity::find()
                 .filter(questions::Column::Id.is_in(qc_question_ids))
+                .filter(questions::Column::TopicId.is_not_in(locked.clone()))
                 .filter(questions::Column::Lang.eq(&params.lang))
+                .filter(questions::Column::Status.eq("published"))
+                .filter(license_class_condition(license_class.as_deref()))
+                .filter(questions_in_effect(auth_user.user.region.as_deref()))
                 .all(&ctx.db)
                 .await
                 .map_err(ApiError::from)?
@@ -248,27 +634,115 @@ async fn create(
                 .await
                 .map_err(ApiError::from)?
                 .ok_or(ApiError::NotFound)?;
+            if locked.contains(&topic_id) {
+                return Err(ApiError::AccessDenied.into());
+            }
 
             questions::Entity::find()
                 .filter(questions::Column::TopicId.eq(topic_id))
                 .filter(questions::Column::Lang.eq(&params.lang))
+                .filter(questions::Column::Status.eq("published"))
+                .filter(license_class_condition(license_class.as_deref()))
+                .filter(questions_in_effect(auth_user.user.region.as_deref()))
+                .all(&ctx.db)
+                .await
+                .map_err(ApiError::from)?
+                .iter()
+                .map(|q| q.id)
+                .collect()
+        }
+        "daily" => {
+            fixed_order = true;
+
+            daily_challenge_questions::Entity::find()
+                .filter(daily_challenge_questions::Column::ChallengeId.eq(filter_id.unwrap()))
+                .order_by_asc(daily_challenge_questions::Column::QuestionOrder)
+                .all(&ctx.db)
+                .await
+                .map_err(ApiError::from)?
+                .iter()
+                .map(|dq| dq.question_id)
+                .collect()
+        }
+        "signs" => {
+            // Вопросы на узнавание собираются из каталога знаков
+            let signs = recognizable_signs(&ctx.db, &params.lang)
+                .await
+                .map_err(ApiError::from)?;
+            if signs.len() < 2 {
+                return Err(ApiError::InvalidState.into());
+            }
+            signs.into_iter().map(|(sign, _)| sign.id).collect()
+        }
+        "template" => {
+            let template_id = params.filter_id.unwrap();
+
+            let template = test_templates::Entity::find_by_id(template_id)
+                .filter(test_templates::Column::IsPublished.eq(true))
+                .filter(test_templates::Column::IsDeleted.eq(false))
+                .one(&ctx.db)
+                .await
+                .map_err(ApiError::from)?
+                .ok_or(ApiError::NotFound)?;
+
+            if template.lang != params.lang {
+                return Err(ApiError::InvalidFieldValue.into());
+            }
+
+            if !template_matches_class(&template, license_class.as_deref()) {
+                return Err(ApiError::NotFound.into());
+            }
+
+            let topic_ids = check_template_access(&ctx.db, auth_user.user.id, template.id).await?;
+            if topic_ids.iter().any(|topic_id| locked.contains(topic_id)) {
+                return Err(ApiError::AccessDenied.into());
+            }
+
+            fixed_order = true;
+            time_limit_seconds = template.time_limit_seconds;
+            license_class = template.license_class;
+
+            let template_ids: Vec<Uuid> = test_template_questions::Entity::find()
+                .filter(test_template_questions::Column::TemplateId.eq(template.id))
+                .order_by_asc(test_template_questions::Column::QuestionOrder)
                 .all(&ctx.db)
                 .await
                 .map_err(ApiError::from)?
                 .iter()
+                .map(|tq| tq.question_id)
+                .collect();
+
+            // Неопубликованные, устаревшие и ещё не вступившие в силу вопросы пропускаются
+            let in_effect: HashSet<Uuid> = questions::Entity::find()
+                .filter(questions::Column::Id.is_in(template_ids.clone()))
+                .filter(questions::Column::Status.eq("published"))
+                .filter(questions_in_effect(auth_user.user.region.as_deref()))
+                .all(&ctx.db)
+                .await
+                .map_err(ApiError::from)?
+                .into_iter()
                 .map(|q| q.id)
+                .collect();
+            template_ids
+                .into_iter()
+                .filter(|id| in_effect.contains(id))
                 .collect()
         }
         _ => return Err(ApiError::InvalidFieldValue.into()),
     };
 
-    // Check if we have enough questions
-    if (question_ids.len() as i16) < params.questions_count {
-        return Err(ApiError::InvalidInput.into());
-    }
+    let selected_ids: Vec<Uuid> = if fixed_order {
+        if question_ids.is_empty() {
+            return Err(ApiError::InvalidState.into());
+        }
+        question_ids
+    } else {
+        // Check if we have enough questions
+        if (question_ids.len() as i16) < params.questions_count {
+            return Err(ApiError::InvalidInput.into());
+        }
 
-    // Select random questions (scope rng to avoid Send issues)
-    let selected_ids: Vec<Uuid> = {
+        // Select random questions (scope rng to avoid Send issues)
         let mut rng = rand::thread_rng();
         let mut ids = question_ids.clone();
         ids.shuffle(&mut rng);
@@ -277,33 +751,56 @@ async fn create(
             .collect()
     };
 
+    // Points per selected question, generated sign questions are worth one point
+    let points: HashMap<Uuid, i16> = if params.filter_type == "signs" {
+        selected_ids.iter().map(|id| (*id, 1)).collect()
+    } else {
+        questions::Entity::find()
+            .filter(questions::Column::Id.is_in(selected_ids.clone()))
+            .all(&ctx.db)
+            .await
+            .map_err(ApiError::from)?
+            .into_iter()
+            .map(|q| (q.id, q.points))
+            .collect()
+    };
+    let max_points: i16 = points.values().sum();
+
     // Create test
     let test = tests::ActiveModel {
         user_id: Set(auth_user.user.id),
         filter_type: Set(params.filter_type),
-        filter_id: Set(params.filter_id),
+        filter_id: Set(filter_id),
         lang: Set(params.lang),
         filter_hash: Set(filter_hash),
-        total_questions: Set(params.questions_count),
+        total_questions: Set(selected_ids.len() as i16),
         correct_count: Set(0),
         status: Set("active".to_string()),
+        scoring_policy: Set(scoring_policy.as_str().to_string()),
+        max_points: Set(max_points),
+        time_limit_seconds: Set(time_limit_seconds),
+        license_class: Set(license_class),
         ..Default::default()
     };
 
-    let test = test.insert(&ctx.db).await.map_err(ApiError::from)?;
+    let txn = ctx.db.begin().await.map_err(ApiError::from)?;
+    let test = test.insert(&txn).await.map_err(ApiError::from)?;
 
     // Create test_questions
-    for (order, question_id) in selected_ids.iter().enumerate() {
-        let tq = test_questions::ActiveModel {
-            test_id: Set(test.id),
-            question_id: Set(*question_id),
-            question_order: Set((order + 1) as i16),
-            is_correct: Set(None),
-            answered_at: Set(None),
-        };
-        tq.insert(&ctx.db).await.map_err(ApiError::from)?;
+    if test.filter_type == "signs" {
+        insert_sign_questions(&txn, test.id, &test.lang, &selected_ids)
+            .await
+            .map_err(ApiError::from)?;
+    } else {
+        let selected: Vec<(Uuid, i16)> = selected_ids
+            .iter()
+            .map(|id| (*id, points.get(id).copied().unwrap_or(1)))
+            .collect();
+        insert_test_questions(&txn, test.id, &selected).await?;
     }
 
+    txn.commit().await.map_err(ApiError::from)?;
+
     Ok((
         axum::http::StatusCode::CREATED,
         Json(TestResponse::from_model(test, 0)),
@@ -311,6 +808,244 @@ async fn create(
         .into_response())
 }
 
+/// Retake a finished test with the same question set, optionally in another language
+#[utoipa::path(
+    post,
+    tag = "Tests",
+    path = "/api/tests/{id}/retake",
+    params(("id" = Uuid, Path, description = "Test ID")),
+    request_body = RetakeTestParams,
+    responses(
+        (status = 201, body = TestResponse),
+        ApiError
+    ),
+    security(("jwt_token" = []))
+)]
+async fn retake(
+    auth_user: AuthUser,
+    Path(id): Path<Uuid>,
+    State(ctx): State<AppContext>,
+    Json(params): Json<RetakeTestParams>,
+) -> axum::response::Result<Response> {
+    params.validate().map_err(ApiError::from)?;
+    let txn = ctx.db.begin().await.map_err(ApiError::from)?;
+
+    let original = tests::Entity::find_by_id(id)
+        .filter(tests::Column::IsDeleted.eq(false))
+        .one(&txn)
+        .await
+        .map_err(ApiError::from)?
+        .ok_or(ApiError::NotFound)?;
+
+    if original.user_id != auth_user.user.id {
+        return Err(ApiError::Forbidden.into());
+    }
+
+    // Daily challenges allow a single attempt, live tests are not replayed alone
+    if original.status == "active" || original.filter_type == "daily" || is_live(&original) {
+        return Err(ApiError::InvalidState.into());
+    }
+
+    let series_id = original.series_id.unwrap_or(original.id);
+    let filter_hash = generate_filter_hash("retake", Some(series_id), &original.lang);
+
+    // Only one active retake per series
+    let existing = tests::Entity::find()
+        .filter(tests::Column::UserId.eq(auth_user.user.id))
+        .filter(tests::Column::FilterHash.eq(&filter_hash))
+        .filter(tests::Column::Status.eq("active"))
+        .filter(tests::Column::IsDeleted.eq(false))
+        .filter(tests::Column::IsActive.eq(true))
+        .one(&txn)
+        .await
+        .map_err(ApiError::from)?;
+
+    if existing.is_some() {
+        return Err(ApiError::AlreadyExists.into());
+    }
+
+    let original_questions = test_questions::Entity::find()
+        .filter(test_questions::Column::TestId.eq(original.id))
+        .order_by_asc(test_questions::Column::QuestionOrder)
+        .all(&txn)
+        .await
+        .map_err(ApiError::from)?;
+
+    let mut selected: Vec<(Uuid, i16)> = original_questions
+        .iter()
+        .filter(|tq| !params.only_wrong || tq.is_correct != Some(true))
+        .map(|tq| (tq.question_id, tq.points))
+        .collect();
+
+    let lang = params.lang.clone().unwrap_or(original.lang.clone());
+
+    // Questions removed from the bank or no longer in force are left out,
+    // the others are replaced by their variant in the requested language
+    if original.filter_type == "signs" {
+        let remaining: HashSet<Uuid> = recognizable_signs(&txn, &lang)
+            .await
+            .map_err(ApiError::from)?
+            .into_iter()
+            .map(|(sign, _)| sign.id)
+            .collect();
+        selected.retain(|(id, _)| remaining.contains(id));
+    } else {
+        let groups: HashMap<Uuid, Uuid> = questions::Entity::find()
+            .filter(questions::Column::Id.is_in(selected.iter().map(|(id, _)| *id)))
+            .all(&txn)
+            .await
+            .map_err(ApiError::from)?
+            .into_iter()
+            .map(|q| (q.id, q.translation_group_id))
+            .collect();
+        let variants: HashMap<Uuid, Uuid> = questions::Entity::find()
+            .filter(questions::Column::TranslationGroupId.is_in(groups.values().cloned()))
+            .filter(questions::Column::Lang.eq(&lang))
+            .filter(
+                Condition::any()
+                    .add(questions::Column::Id.is_in(groups.keys().cloned()))
+                    .add(questions::Column::Status.eq("published")),
+            )
+            .filter(questions_in_effect(auth_user.user.region.as_deref()))
+            .all(&txn)
+            .await
+            .map_err(ApiError::from)?
+            .into_iter()
+            .map(|q| (q.translation_group_id, q.id))
+            .collect();
+        selected = selected
+            .into_iter()
+            .filter_map(|(id, points)| {
+                let group = groups.get(&id)?;
+                variants.get(group).map(|variant| (*variant, points))
+            })
+            .collect();
+    }
+
+    if selected.is_empty() {
+        return Err(ApiError::InvalidState.into());
+    }
+
+    if params.shuffle {
+        let mut rng = rand::thread_rng();
+        selected.shuffle(&mut rng);
+    }
+
+    let attempt_number = tests::Entity::find()
+        .filter(tests::Column::UserId.eq(auth_user.user.id))
+        .filter(series_condition(series_id))
+        .order_by_desc(tests::Column::AttemptNumber)
+        .one(&txn)
+        .await
+        .map_err(ApiError::from)?
+        .map(|t| t.attempt_number + 1)
+        .unwrap_or(2);
+
+    let test = tests::ActiveModel {
+        user_id: Set(auth_user.user.id),
+        filter_type: Set(original.filter_type),
+        filter_id: Set(original.filter_id),
+        lang: Set(lang),
+        filter_hash: Set(filter_hash),
+        total_questions: Set(selected.len() as i16),
+        correct_count: Set(0),
+        status: Set("active".to_string()),
+        scoring_policy: Set(original.scoring_policy),
+        max_points: Set(selected.iter().map(|(_, points)| points).sum()),
+        time_limit_seconds: Set(original.time_limit_seconds),
+        retake_of_id: Set(Some(original.id)),
+        series_id: Set(Some(series_id)),
+        attempt_number: Set(attempt_number),
+        license_class: Set(original.license_class),
+        ..Default::default()
+    };
+
+    let test = test.insert(&txn).await.map_err(ApiError::from)?;
+
+    // Знаки получают новые варианты ответа
+    if test.filter_type == "signs" {
+        let sign_ids: Vec<Uuid> = selected.iter().map(|(id, _)| *id).collect();
+        insert_sign_questions(&txn, test.id, &test.lang, &sign_ids)
+            .await
+            .map_err(ApiError::from)?;
+    } else {
+        insert_test_questions(&txn, test.id, &selected).await?;
+    }
+
+    txn.commit().await.map_err(ApiError::from)?;
+
+    Ok((
+        axum::http::StatusCode::CREATED,
+        Json(TestResponse::from_model(test, 0)),
+    )
+        .into_response())
+}
+
+/// Score progression across retakes of the same question set
+#[utoipa::path(
+    get,
+    tag = "Tests",
+    path = "/api/tests/{id}/attempts",
+    params(("id" = Uuid, Path, description = "Test ID")),
+    responses(
+        (status = 200, body = Vec<TestAttemptResponse>),
+        ApiError
+    ),
+    security(("jwt_token" = []))
+)]
+async fn attempts(
+    auth_user: AuthUser,
+    Path(id): Path<Uuid>,
+    State(ctx): State<AppContext>,
+) -> axum::response::Result<Response> {
+    let test = tests::Entity::find_by_id(id)
+        .filter(tests::Column::IsDeleted.eq(false))
+        .one(&ctx.db)
+        .await
+        .map_err(ApiError::from)?
+        .ok_or(ApiError::NotFound)?;
+
+    if test.user_id != auth_user.user.id {
+        return Err(ApiError::Forbidden.into());
+    }
+
+    let series = tests::Entity::find()
+        .filter(tests::Column::UserId.eq(auth_user.user.id))
+        .filter(tests::Column::IsDeleted.eq(false))
+        .filter(series_condition(test.series_id.unwrap_or(test.id)))
+        .order_by_asc(tests::Column::AttemptNumber)
+        .all(&ctx.db)
+        .await
+        .map_err(ApiError::from)?;
+
+    let mut previous_score: Option<i16> = None;
+    let mut responses = Vec::new();
+    for attempt in series {
+        let score_change = match (attempt.score_percent, previous_score) {
+            (Some(score), Some(previous)) => Some(score - previous),
+            _ => None,
+        };
+        if attempt.score_percent.is_some() {
+            previous_score = attempt.score_percent;
+        }
+
+        responses.push(TestAttemptResponse {
+            id: attempt.id,
+            attempt_number: attempt.attempt_number,
+            total_questions: attempt.total_questions,
+            correct_count: attempt.correct_count,
+            status: attempt.status,
+            score_percent: attempt.score_percent,
+            score_change,
+            earned_points: attempt.earned_points,
+            created_at: attempt.created_at.into(),
+            completed_at: attempt.completed_at.map(|dt| dt.into()),
+        });
+    }
+
+    Ok(Json(responses).into_response())
+}
+
 /// Get current (next unanswered) question
 #[utoipa::path(
     get,
@@ -339,7 +1074,7 @@ async fn get_current_question(
         return Err(ApiError::Forbidden.into());
     }
 
-    if test.status != "active" {
+    if test.status != "active" || is_time_over(&test) {
         return Err(ApiError::InvalidState.into());
     }
 
@@ -357,41 +1092,11 @@ async fn get_current_question(
         None => return Err(ApiError::NotFound.into()),
     };
 
-    // Get question
-    let question = questions::Entity::find_by_id(tq.question_id)
-        .one(&ctx.db)
-        .await
-        .map_err(ApiError::from)?
-        .ok_or(ApiError::NotFound)?;
-
-    // Get answers
-    let answers_list = answers::Entity::find()
-        .filter(answers::Column::QuestionId.eq(question.id))
-        .all(&ctx.db)
+    let (question, _) = snapshot_question(&ctx.db, tq, &test.lang)
         .await
         .map_err(ApiError::from)?;
 
-    // Count correct answers to determine if multiple selection
-    let correct_count = answers_list.iter().filter(|a| a.is_correct).count();
-
-    Ok(Json(CurrentQuestionResponse {
-        order: tq.question_order,
-        question: QuestionInfo {
-            id: question.id,
-            name: question.name,
-            content: question.content,
-            lang: question.lang,
-        },
-        answers: answers_list
-            .iter()
-            .map(|a| AnswerOption {
-                id: a.id,
-                value: a.value.clone(),
-            })
-            .collect(),
-        multiple_answers: correct_count > 1,
-    })
-    .into_response())
+    Ok(Json(question).into_response())
 }
 
 /// Answer a question
@@ -427,7 +1132,7 @@ async fn answer_question(
         return Err(ApiError::Forbidden.into());
     }
 
-    if test.status != "active" {
+    if test.status != "active" || is_live(&test) || is_time_over(&test) {
         return Err(ApiError::InvalidState.into());
     }
 
@@ -444,48 +1149,28 @@ async fn answer_question(
         return Err(ApiError::Conflict.into());
     }
 
-    // Get question for explanation
-    let question = questions::Entity::find_by_id(params.question_id)
-        .one(&txn)
+    // Graded against the answers as they were when the test was created
+    let explanation = tq.explanation.clone();
+    let question_type = tq.question_type.parse::<QuestionType>().unwrap_or_default();
+    let (is_correct, earned_points, options) = grade_answer(&txn, &test, tq, &params.answer)
         .await
-        .map_err(ApiError::from)?
-        .ok_or(ApiError::NotFound)?;
-
-    // Get all correct answers for the question
-    let correct_answers = answers::Entity::find()
-        .filter(answers::Column::QuestionId.eq(params.question_id))
-        .filter(answers::Column::IsCorrect.eq(true))
-        .all(&txn)
+        .map_err(ApiError::from)?;
+    let links = image_links(&txn, options.iter().filter_map(|o| o.image_id))
         .await
         .map_err(ApiError::from)?;
+    let correct_answers = correct_answers(question_type, &options, &links);
 
-    let correct_ids: HashSet<Uuid> = correct_answers.iter().map(|a| a.id).collect();
-    let selected_ids: HashSet<Uuid> = params.answer_ids.iter().cloned().collect();
-
-    // Answer is correct only if selected_ids == correct_ids (exact match)
-    let is_correct = selected_ids == correct_ids;
-
-    // Save selected answers to test_question_answers
-    for answer_id in &params.answer_ids {
-        let tqa = test_question_answers::ActiveModel {
-            test_id: Set(test.id),
-            question_id: Set(params.question_id),
-            answer_id: Set(*answer_id),
-        };
-        tqa.insert(&txn).await.map_err(ApiError::from)?;
-    }
-
-    // Update test_questions
-    let mut tq_active = tq.into_active_model();
-    tq_active.is_correct = Set(Some(is_correct));
-    tq_active.answered_at = Set(Some(chrono::Utc::now().into()));
-    tq_active.update(&txn).await.map_err(ApiError::from)?;
+    // Update test correct_count and earned points
+    let new_correct_count = if is_correct {
+        test.correct_count + 1
+    } else {
+        test.correct_count
+    };
+    let test_earned_points = test.earned_points + earned_points;
 
-    // Update test correct_count if correct
     let mut test_active = test.clone().into_active_model();
-    if is_correct {
-        test_active.correct_count = Set(test.correct_count + 1);
-    }
+    test_active.correct_count = Set(new_correct_count);
+    test_active.earned_points = Set(test_earned_points);
 
     // Check if all questions are answered
     let unanswered = test_questions::Entity::find()
@@ -495,16 +1180,11 @@ async fn answer_question(
         .await
         .map_err(ApiError::from)?;
 
-    let test_completed = unanswered.len() == 0; // Current question was the last one
-    let new_correct_count = if is_correct {
-        test.correct_count + 1
-    } else {
-        test.correct_count
-    };
-    let answered_count = test.total_questions - (unanswered.len() as i16 - 1);
+    let test_completed = unanswered.is_empty(); // Current question was the last one
+    let answered_count = test.total_questions - unanswered.len() as i16;
 
     let score_percent = if test_completed {
-        let score = (new_correct_count as f32 / test.total_questions as f32 * 100.0) as i16;
+        let score = score_percent(test_earned_points, test.max_points);
         test_active.status = Set("completed".to_string());
         test_active.score_percent = Set(Some(score));
         test_active.completed_at = Set(Some(chrono::Utc::now().into()));
@@ -519,11 +1199,14 @@ async fn answer_question(
 
     Ok(Json(AnswerResultResponse {
         is_correct,
-        correct_answer_ids: correct_ids.into_iter().collect(),
-        explanation: question.explanation,
+        correct_answer_ids: correct_answers.iter().map(|a| a.id).collect(),
+        correct_answers,
+        explanation,
+        earned_points,
         test_completed,
         answered_count,
         correct_count: new_correct_count,
+        test_earned_points,
         score_percent,
     })
     .into_response())
@@ -557,25 +1240,22 @@ async fn complete_test(
         return Err(ApiError::Forbidden.into());
     }
 
-    if test.status != "active" {
+    if test.status != "active" || is_live(&test) {
         return Err(ApiError::InvalidState.into());
     }
 
-    // Count answered questions
-    let answered_count = test_questions::Entity::find()
+    // Answered questions
+    let answered = test_questions::Entity::find()
         .filter(test_questions::Column::TestId.eq(test.id))
         .filter(test_questions::Column::AnsweredAt.is_not_null())
         .all(&ctx.db)
         .await
-        .map_err(ApiError::from)?
-        .len() as i16;
+        .map_err(ApiError::from)?;
+    let answered_count = answered.len() as i16;
 
-    // Calculate score based on answered questions
-    let score_percent = if answered_count > 0 {
-        (test.correct_count as f32 / answered_count as f32 * 100.0) as i16
-    } else {
-        0
-    };
+    // Calculate score based on points of answered questions
+    let answered_points: i16 = answered.iter().map(|tq| tq.points).sum();
+    let score_percent = score_percent(test.earned_points, answered_points);
 
     let mut test_active = test.into_active_model();
     test_active.status = Set("abandoned".to_string());
@@ -587,6 +1267,7 @@ async fn complete_test(
         status: updated_test.status,
         answered_count,
         correct_count: updated_test.correct_count,
+        earned_points: updated_test.earned_points,
         score_percent,
     })
     .into_response())
@@ -632,6 +1313,7 @@ async fn delete(
     get,
     tag = "Tests",
     path = "/api/tests/history",
+    params(HistoryQuery),
     responses(
         (status = 200, body = Vec<TestResponse>),
         ApiError
@@ -641,11 +1323,18 @@ async fn delete(
 async fn history(
     auth_user: AuthUser,
     State(ctx): State<AppContext>,
+    Query(query): Query<HistoryQuery>,
 ) -> axum::response::Result<Response> {
-    let tests_list = tests::Entity::find()
+    let mut tests_query = tests::Entity::find()
         .filter(tests::Column::UserId.eq(auth_user.user.id))
         .filter(tests::Column::IsDeleted.eq(false))
-        .filter(tests::Column::Status.ne("active"))
+        .filter(tests::Column::Status.ne("active"));
+
+    if let Some(series_id) = query.series_id {
+        tests_query = tests_query.filter(series_condition(series_id));
+    }
+
+    let tests_list = tests_query
         .order_by_desc(tests::Column::CompletedAt)
         .all(&ctx.db)
         .await
@@ -704,24 +1393,18 @@ async fn review(
     let mut review_questions = Vec::new();
 
     for tq in test_questions_list {
-        // Get question
-        let question = questions::Entity::find_by_id(tq.question_id)
-            .one(&ctx.db)
+        // Answers as the user saw them
+        let answers_list = snapshot_options(&ctx.db, test.id, tq.question_id)
             .await
-            .map_err(ApiError::from)?
-            .ok_or(ApiError::NotFound)?;
-
-        // Get all answers
-        let answers_list = answers::Entity::find()
-            .filter(answers::Column::QuestionId.eq(question.id))
-            .all(&ctx.db)
+            .map_err(ApiError::from)?;
+        let (images, links) = snapshot_images(&ctx.db, &tq, &answers_list)
             .await
             .map_err(ApiError::from)?;
 
         // Get selected answers
         let selected_answers = test_question_answers::Entity::find()
             .filter(test_question_answers::Column::TestId.eq(test.id))
-            .filter(test_question_answers::Column::QuestionId.eq(question.id))
+            .filter(test_question_answers::Column::QuestionId.eq(tq.question_id))
             .all(&ctx.db)
             .await
             .map_err(ApiError::from)?;
@@ -731,22 +1414,25 @@ async fn review(
         review_questions.push(ReviewQuestionResponse {
             order: tq.question_order,
             question: QuestionInfoWithExplanation {
-                id: question.id,
-                name: question.name,
-                content: question.content,
-                lang: question.lang,
-                explanation: question.explanation,
+                id: tq.question_id,
+                name: tq.question_name,
+                content: tq.question_content,
+                lang: test.lang.clone(),
+                explanation: tq.explanation,
             },
+            question_type: tq.question_type,
+            images,
             answers: answers_list
-                .iter()
-                .map(|a| AnswerOptionWithCorrectness {
-                    id: a.id,
-                    value: a.value.clone(),
-                    is_correct: a.is_correct,
-                })
+                .into_iter()
+                .map(|o| option_with_correctness(o, &links))
                 .collect(),
             selected_answer_ids: selected_ids,
+            response: tq
+                .response
+                .and_then(|value| serde_json::from_value(value).ok()),
             is_correct: tq.is_correct.unwrap_or(false),
+            points: tq.points,
+            earned_points: tq.earned_points,
         });
     }
 
@@ -758,6 +1444,9 @@ async fn review(
         total_questions: test.total_questions,
         correct_count: test.correct_count,
         score_percent: test.score_percent.unwrap_or(0),
+        scoring_policy: test.scoring_policy,
+        max_points: test.max_points,
+        earned_points: test.earned_points,
         status: test.status,
         questions: review_questions,
     })
@@ -769,6 +1458,8 @@ pub fn routes() -> OpenApiRouter<AppContext> {
         .routes(routes!(list))
         .routes(routes!(get))
         .routes(routes!(create))
+        .routes(routes!(retake))
+        .routes(routes!(attempts))
         .routes(routes!(get_current_question))
         .routes(routes!(answer_question))
         .routes(routes!(complete_test))
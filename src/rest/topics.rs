@@ -1,55 +1,180 @@
 use crate::{
     AppContext,
-    entities::topics,
-    models::topics::{CreateTopicParams, TopicResponse, UpdateTopicParams},
-    utils::response::ApiError,
+    entities::{topic_prerequisites, topic_translations, topics},
+    models::topics::{CreateTopicParams, TopicResponse, TopicTranslationParams, UpdateTopicParams},
+    rest::{
+        curriculum::{locked_topics, save_prerequisites},
+        lesson_progress::topic_progress,
+    },
+    utils::{
+        config::CURRICULUM_MODE,
+        extractors::AuthUser,
+        lang::{ContentLangQuery, PreferredLangs, check_translation_langs},
+        response::ApiError,
+    },
 };
 use axum::{
     Json,
     extract::{Path, State},
     response::{IntoResponse, Response},
 };
-use sea_orm::{ActiveModelTrait, ActiveValue::Set, EntityTrait, IntoActiveModel};
+use sea_orm::{
+    ActiveModelTrait, ActiveValue::Set, ColumnTrait, ConnectionTrait, DbErr, EntityTrait,
+    IntoActiveModel, QueryFilter, QueryOrder, TransactionTrait,
+};
+use std::collections::HashMap;
 use utoipa_axum::{router::OpenApiRouter, routes};
 use uuid::Uuid;
 use validator::Validate;
 
-/// List all topics
+/// Topics with their prerequisites and names in the first available preferred language
+pub(crate) async fn topic_responses<C: ConnectionTrait>(
+    db: &C,
+    topics: Vec<topics::Model>,
+    langs: &PreferredLangs,
+) -> Result<Vec<TopicResponse>, DbErr> {
+    let mut prerequisites: HashMap<Uuid, Vec<topic_prerequisites::Model>> = HashMap::new();
+    for prerequisite in topic_prerequisites::Entity::find()
+        .filter(topic_prerequisites::Column::TopicId.is_in(topics.iter().map(|t| t.id)))
+        .all(db)
+        .await?
+    {
+        prerequisites
+            .entry(prerequisite.topic_id)
+            .or_default()
+            .push(prerequisite);
+    }
+
+    let mut names: HashMap<Uuid, HashMap<String, String>> = HashMap::new();
+    for translation in topic_translations::Entity::find()
+        .filter(topic_translations::Column::TopicId.is_in(topics.iter().map(|t| t.id)))
+        .filter(topic_translations::Column::Lang.is_in(langs.0.iter().cloned()))
+        .all(db)
+        .await?
+    {
+        names
+            .entry(translation.topic_id)
+            .or_default()
+            .insert(translation.lang, translation.name);
+    }
+
+    Ok(topics
+        .into_iter()
+        .map(|topic| {
+            let mut response = TopicResponse::from(topic);
+            response.prerequisites = prerequisites
+                .remove(&response.id)
+                .unwrap_or_default()
+                .into_iter()
+                .map(Into::into)
+                .collect();
+            if let Some((lang, name)) = langs.pick(names.get(&response.id)) {
+                response.lang = Some(lang.to_string());
+                response.name = name.clone();
+            }
+            response
+        })
+        .collect())
+}
+
+/// Fills the completion of the topics for an authorized user, and the lock
+/// state in curriculum mode
+async fn add_progress<C: ConnectionTrait>(
+    db: &C,
+    auth_user: Option<AuthUser>,
+    topics: &mut [TopicResponse],
+) -> Result<(), DbErr> {
+    let Some(auth_user) = auth_user else {
+        return Ok(());
+    };
+    let topic_ids: Vec<Uuid> = topics.iter().map(|topic| topic.id).collect();
+    let mut progress = topic_progress(db, &auth_user.user, &topic_ids).await?;
+    let locked = if *CURRICULUM_MODE {
+        Some(locked_topics(db, &auth_user.user).await?)
+    } else {
+        None
+    };
+    for topic in topics {
+        topic.progress = progress.remove(&topic.id);
+        topic.unlocked = locked.as_ref().map(|locked| !locked.contains(&topic.id));
+    }
+    Ok(())
+}
+
+/// Adds or replaces the translations of a topic
+async fn save_translations<C: ConnectionTrait>(
+    db: &C,
+    topic_id: Uuid,
+    translations: Vec<TopicTranslationParams>,
+) -> Result<(), DbErr> {
+    for translation in translations {
+        let lang = translation.lang.to_lowercase();
+        topic_translations::Entity::delete_by_id((topic_id, lang.clone()))
+            .exec(db)
+            .await?;
+        topic_translations::ActiveModel {
+            topic_id: Set(topic_id),
+            lang: Set(lang),
+            name: Set(translation.name),
+        }
+        .insert(db)
+        .await?;
+    }
+    Ok(())
+}
+
+/// List all topics in curriculum order in the requested language, with the
+/// completion for an authorized user
 #[utoipa::path(
     get,
     tag = "Topics",
     path = "/api/topics",
+    params(ContentLangQuery),
     responses(
         (status = 200, body = Vec<TopicResponse>),
         ApiError
     ),
-    security()
+    security((), ("jwt_token" = []))
 )]
-async fn list(State(ctx): State<AppContext>) -> axum::response::Result<Response> {
+async fn list(
+    auth_user: Option<AuthUser>,
+    langs: PreferredLangs,
+    State(ctx): State<AppContext>,
+) -> axum::response::Result<Response> {
     let topics = topics::Entity::find()
+        .order_by_asc(topics::Column::Position)
+        .order_by_asc(topics::Column::Name)
         .all(&ctx.db)
         .await
-        .map_err(ApiError::from)?
-        .into_iter()
-        .map(TopicResponse::from)
-        .collect::<Vec<_>>();
+        .map_err(ApiError::from)?;
+    let mut topics = topic_responses(&ctx.db, topics, &langs)
+        .await
+        .map_err(ApiError::from)?;
+    add_progress(&ctx.db, auth_user, &mut topics)
+        .await
+        .map_err(ApiError::from)?;
 
     Ok(Json(topics).into_response())
 }
 
-/// Get topic by id
+/// Get topic by id in the requested language, with the completion for an authorized user
 #[utoipa::path(
     get,
     tag = "Topics",
     path = "/api/topics/{id}",
-    params(("id" = Uuid, Path, description = "Topic ID")),
+    params(
+        ("id" = Uuid, Path, description = "Topic ID"),
+        ContentLangQuery
+    ),
     responses(
         (status = 200, body = TopicResponse),
         ApiError
     ),
-    security()
+    security((), ("jwt_token" = []))
 )]
 async fn get(
+    auth_user: Option<AuthUser>,
+    langs: PreferredLangs,
     Path(id): Path<Uuid>,
     State(ctx): State<AppContext>,
 ) -> axum::response::Result<Response> {
@@ -58,8 +183,15 @@ async fn get(
         .await
         .map_err(ApiError::from)?
         .ok_or(ApiError::NotFound)?;
+    let mut topics = topic_responses(&ctx.db, vec![topic], &langs)
+        .await
+        .map_err(ApiError::from)?;
+    add_progress(&ctx.db, auth_user, &mut topics)
+        .await
+        .map_err(ApiError::from)?;
+    let topic = topics.remove(0);
 
-    Ok(Json(TopicResponse::from(topic)).into_response())
+    Ok(Json(topic).into_response())
 }
 
 /// Create topic
@@ -79,22 +211,33 @@ async fn create(
     Json(params): Json<CreateTopicParams>,
 ) -> axum::response::Result<Response> {
     params.validate().map_err(ApiError::from)?;
+    check_translation_langs(params.translations.iter().map(|t| t.lang.as_str()))?;
+
+    let txn = ctx.db.begin().await.map_err(ApiError::from)?;
 
     let topic = topics::ActiveModel {
         name: Set(params.name),
         difficulty: Set(params.difficulty),
         duration: Set(params.duration),
         subscription_required: Set(params.subscription_required),
+        position: Set(params.position),
         ..Default::default()
     };
 
-    let topic = topic.insert(&ctx.db).await.map_err(ApiError::from)?;
+    let topic = topic.insert(&txn).await.map_err(ApiError::from)?;
+    save_translations(&txn, topic.id, params.translations)
+        .await
+        .map_err(ApiError::from)?;
+    save_prerequisites(&txn, topic.id, params.prerequisites).await?;
 
-    Ok((
-        axum::http::StatusCode::CREATED,
-        Json(TopicResponse::from(topic)),
-    )
-        .into_response())
+    let topic = topic_responses(&txn, vec![topic], &PreferredLangs::default())
+        .await
+        .map_err(ApiError::from)?
+        .remove(0);
+
+    txn.commit().await.map_err(ApiError::from)?;
+
+    Ok((axum::http::StatusCode::CREATED, Json(topic)).into_response())
 }
 
 /// Update topic by id
@@ -116,9 +259,14 @@ async fn update(
     Json(params): Json<UpdateTopicParams>,
 ) -> axum::response::Result<Response> {
     params.validate().map_err(ApiError::from)?;
+    if let Some(translations) = &params.translations {
+        check_translation_langs(translations.iter().map(|t| t.lang.as_str()))?;
+    }
+
+    let txn = ctx.db.begin().await.map_err(ApiError::from)?;
 
     let topic = topics::Entity::find_by_id(id)
-        .one(&ctx.db)
+        .one(&txn)
         .await
         .map_err(ApiError::from)?
         .ok_or(ApiError::NotFound)?;
@@ -137,10 +285,28 @@ async fn update(
     if let Some(subscription_required) = params.subscription_required {
         to_update.subscription_required = Set(subscription_required);
     }
+    if let Some(position) = params.position {
+        to_update.position = Set(position);
+    }
+
+    let topic = to_update.update(&txn).await.map_err(ApiError::from)?;
+    if let Some(translations) = params.translations {
+        save_translations(&txn, topic.id, translations)
+            .await
+            .map_err(ApiError::from)?;
+    }
+    if let Some(prerequisites) = params.prerequisites {
+        save_prerequisites(&txn, topic.id, prerequisites).await?;
+    }
+
+    let topic = topic_responses(&txn, vec![topic], &PreferredLangs::default())
+        .await
+        .map_err(ApiError::from)?
+        .remove(0);
 
-    let topic = to_update.update(&ctx.db).await.map_err(ApiError::from)?;
+    txn.commit().await.map_err(ApiError::from)?;
 
-    Ok(Json(TopicResponse::from(topic)).into_response())
+    Ok(Json(topic).into_response())
 }
 
 /// Delete topic by id
@@ -1,17 +1,41 @@
 use crate::{
     AppContext,
-    entities::user_favorite_questions,
+    entities::{questions, user_favorite_questions},
     utils::{extractors::AuthUser, response::ApiError},
 };
 use axum::{
     extract::{Path, State},
     response::{IntoResponse, Response},
 };
-use sea_orm::{ActiveModelTrait, ActiveValue::Set, EntityTrait, ModelTrait};
+use sea_orm::{
+    ActiveModelTrait, ActiveValue::Set, ColumnTrait, ConnectionTrait, DbErr, EntityTrait,
+    QueryFilter,
+};
 use utoipa_axum::{router::OpenApiRouter, routes};
 use uuid::Uuid;
 
-/// Add question to favorites
+/// Favorites of the user among the language variants of the question
+async fn group_favorites<C: ConnectionTrait>(
+    db: &C,
+    user_id: Uuid,
+    question: &questions::Model,
+) -> Result<Vec<user_favorite_questions::Model>, DbErr> {
+    let variant_ids: Vec<Uuid> = questions::Entity::find()
+        .filter(questions::Column::TranslationGroupId.eq(question.translation_group_id))
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|q| q.id)
+        .collect();
+
+    user_favorite_questions::Entity::find()
+        .filter(user_favorite_questions::Column::UserId.eq(user_id))
+        .filter(user_favorite_questions::Column::QuestionId.is_in(variant_ids))
+        .all(db)
+        .await
+}
+
+/// Add question to favorites, in every language
 #[utoipa::path(
     post,
     tag = "User Favorite Questions",
@@ -28,6 +52,20 @@ async fn add_favorite(
     Path(question_id): Path<Uuid>,
     State(ctx): State<AppContext>,
 ) -> axum::response::Result<Response> {
+    let question = questions::Entity::find_by_id(question_id)
+        .one(&ctx.db)
+        .await
+        .map_err(ApiError::from)?
+        .ok_or(ApiError::NotFound)?;
+
+    // Перевод уже добавленного вопроса считается тем же избранным
+    let existing = group_favorites(&ctx.db, auth_user.user.id, &question)
+        .await
+        .map_err(ApiError::from)?;
+    if !existing.is_empty() {
+        return Err(ApiError::AlreadyExists.into());
+    }
+
     let favorite = user_favorite_questions::ActiveModel {
         user_id: Set(auth_user.user.id),
         question_id: Set(question_id),
@@ -38,7 +76,7 @@ async fn add_favorite(
     Ok(axum::http::StatusCode::CREATED.into_response())
 }
 
-/// Remove question from favorites
+/// Remove question from favorites, in every language
 #[utoipa::path(
     delete,
     tag = "User Favorite Questions",
@@ -55,13 +93,28 @@ async fn remove_favorite(
     Path(question_id): Path<Uuid>,
     State(ctx): State<AppContext>,
 ) -> axum::response::Result<Response> {
-    let favorite = user_favorite_questions::Entity::find_by_id((auth_user.user.id, question_id))
+    let question = questions::Entity::find_by_id(question_id)
         .one(&ctx.db)
         .await
         .map_err(ApiError::from)?
         .ok_or(ApiError::NotFound)?;
 
-    favorite.delete(&ctx.db).await.map_err(ApiError::from)?;
+    let favorites = group_favorites(&ctx.db, auth_user.user.id, &question)
+        .await
+        .map_err(ApiError::from)?;
+    if favorites.is_empty() {
+        return Err(ApiError::NotFound.into());
+    }
+
+    user_favorite_questions::Entity::delete_many()
+        .filter(user_favorite_questions::Column::UserId.eq(auth_user.user.id))
+        .filter(
+            user_favorite_questions::Column::QuestionId
+                .is_in(favorites.iter().map(|f| f.question_id)),
+        )
+        .exec(&ctx.db)
+        .await
+        .map_err(ApiError::from)?;
 
     Ok(().into_response())
 }
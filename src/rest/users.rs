@@ -1,8 +1,9 @@
 use crate::{
     AppContext,
     entities::{user_subscriptions, users},
-    models::users::{AuthParams, UpdateUserParams, UsersResponse},
-    utils::response::ApiError,
+    models::users::{AuthParams, ROLES, UpdateRoleParams, UpdateUserParams, UsersResponse},
+    rest::license_classes::check_license_classes_exist,
+    utils::{extractors::AdminUser, response::ApiError},
 };
 use axum::{
     Json,
@@ -12,6 +13,7 @@ use axum::{
 use sea_orm::{ActiveModelTrait, ActiveValue::Set, EntityTrait, IntoActiveModel};
 use utoipa_axum::{router::OpenApiRouter, routes};
 use uuid::Uuid;
+use validator::Validate;
 
 /// Add subscription
 #[utoipa::path(
@@ -140,6 +142,8 @@ async fn update(
     State(ctx): State<AppContext>,
     Json(params): Json<UpdateUserParams>,
 ) -> axum::response::Result<Response> {
+    params.validate().map_err(ApiError::from)?;
+
     let user = users::Entity::find_by_id(id)
         .one(&ctx.db)
         .await
@@ -160,6 +164,58 @@ async fn update(
         to_update.email = Set(v);
     }
 
+    if let Some(v) = params.license_class {
+        if v.is_empty() {
+            to_update.license_class = Set(None);
+        } else {
+            check_license_classes_exist(&ctx.db, std::slice::from_ref(&v)).await?;
+            to_update.license_class = Set(Some(v));
+        }
+    }
+
+    if let Some(v) = params.region {
+        to_update.region = Set(Some(v).filter(|region| !region.is_empty()));
+    }
+
+    let user = to_update.update(&ctx.db).await.map_err(ApiError::from)?;
+
+    Ok(Json(UsersResponse::from(user)).into_response())
+}
+
+/// Change the role of a user (admins only)
+#[utoipa::path(
+    put,
+    tag = "Users",
+    path = "/api/users/{id}/role",
+    params(("id" = Uuid, Path, description = "Id")),
+    request_body = UpdateRoleParams,
+    responses(
+        (status = 200, body = UsersResponse),
+        ApiError
+    ),
+    security(("jwt_token" = []))
+)]
+async fn update_role(
+    admin: AdminUser,
+    Path(id): Path<Uuid>,
+    State(ctx): State<AppContext>,
+    Json(params): Json<UpdateRoleParams>,
+) -> axum::response::Result<Response> {
+    if admin.user.role != "admin" {
+        return Err(ApiError::InsufficientPermissions.into());
+    }
+    if !ROLES.contains(&params.role.as_str()) {
+        return Err(ApiError::InvalidFieldValue.into());
+    }
+
+    let user = users::Entity::find_by_id(id)
+        .one(&ctx.db)
+        .await
+        .map_err(ApiError::from)?
+        .ok_or(ApiError::UserNotFound)?;
+
+    let mut to_update = user.into_active_model();
+    to_update.role = Set(params.role);
     let user = to_update.update(&ctx.db).await.map_err(ApiError::from)?;
 
     Ok(Json(UsersResponse::from(user)).into_response())
@@ -171,5 +227,6 @@ pub fn routes() -> OpenApiRouter<AppContext> {
         .routes(routes!(get))
         .routes(routes!(delete))
         .routes(routes!(update))
+        .routes(routes!(update_role))
         .routes(routes!(add_sub))
 }
@@ -0,0 +1,130 @@
+use chrono::{Datelike, NaiveDate};
+use rand::{SeedableRng, rngs::StdRng, seq::SliceRandom};
+use sea_orm::{
+    ActiveModelTrait, ActiveValue::Set, ColumnTrait, DatabaseConnection, DbErr, EntityTrait,
+    QueryFilter, QueryOrder, QuerySelect, TransactionTrait,
+};
+use uuid::Uuid;
+
+use crate::entities::{daily_challenge_questions, daily_challenges, questions, topics};
+use crate::rest::regions::questions_in_effect;
+
+/// Number of questions in every daily challenge
+pub const DAILY_CHALLENGE_QUESTIONS: usize = 10;
+
+/// Seed derived from the date and language, so a re-run picks the same set
+fn challenge_seed(date: NaiveDate, lang: &str) -> u64 {
+    // FNV-1a: стабилен между запусками в отличие от DefaultHasher
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for byte in lang.bytes() {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    hash ^ date.num_days_from_ce() as u64
+}
+
+/// Returns the challenge for the date and language, generating it on first use.
+/// `None` when there are no free questions in the language.
+pub async fn ensure_daily_challenge(
+    db: &DatabaseConnection,
+    date: NaiveDate,
+    lang: &str,
+) -> Result<Option<daily_challenges::Model>, DbErr> {
+    let find_existing = || {
+        daily_challenges::Entity::find()
+            .filter(daily_challenges::Column::ChallengeDate.eq(date))
+            .filter(daily_challenges::Column::Lang.eq(lang))
+            .one(db)
+    };
+
+    if let Some(challenge) = find_existing().await? {
+        return Ok(Some(challenge));
+    }
+
+    // Только действующие общие вопросы из бесплатных тем, чтобы челлендж был доступен всем
+    let mut question_ids: Vec<Uuid> = questions::Entity::find()
+        .inner_join(topics::Entity)
+        .filter(topics::Column::SubscriptionRequired.eq(false))
+        .filter(questions::Column::Lang.eq(lang))
+        .filter(questions::Column::Status.eq("published"))
+        .filter(questions_in_effect(None))
+        .order_by_asc(questions::Column::Id)
+        .select_only()
+        .column(questions::Column::Id)
+        .into_tuple()
+        .all(db)
+        .await?;
+
+    if question_ids.is_empty() {
+        return Ok(None);
+    }
+
+    let mut rng = StdRng::seed_from_u64(challenge_seed(date, lang));
+    question_ids.shuffle(&mut rng);
+    question_ids.truncate(DAILY_CHALLENGE_QUESTIONS);
+
+    let txn = db.begin().await?;
+
+    let challenge = daily_challenges::ActiveModel {
+        challenge_date: Set(date),
+        lang: Set(lang.to_string()),
+        questions_count: Set(question_ids.len() as i16),
+        ..Default::default()
+    };
+
+    let challenge = match challenge.insert(&txn).await {
+        Ok(challenge) => challenge,
+        Err(err) => {
+            // Параллельный запрос мог создать челлендж раньше нас
+            txn.rollback().await?;
+            return match find_existing().await? {
+                Some(challenge) => Ok(Some(challenge)),
+                None => Err(err),
+            };
+        }
+    };
+
+    for (order, question_id) in question_ids.iter().enumerate() {
+        let link = daily_challenge_questions::ActiveModel {
+            challenge_id: Set(challenge.id),
+            question_id: Set(*question_id),
+            question_order: Set((order + 1) as i16),
+        };
+        link.insert(&txn).await?;
+    }
+
+    txn.commit().await?;
+
+    Ok(Some(challenge))
+}
+
+pub async fn generate_daily_challenges(db: &DatabaseConnection) {
+    let langs: Vec<String> = match questions::Entity::find()
+        .select_only()
+        .column(questions::Column::Lang)
+        .distinct()
+        .into_tuple()
+        .all(db)
+        .await
+    {
+        Ok(langs) => langs,
+        Err(err) => {
+            tracing::error!("generate_daily_challenges: {err}");
+            return;
+        }
+    };
+
+    let today = chrono::Utc::now().date_naive();
+    for lang in langs {
+        match ensure_daily_challenge(db, today, &lang).await {
+            Ok(Some(challenge)) => tracing::info!(
+                "generate_daily_challenges: {} {} ({} questions)",
+                challenge.challenge_date,
+                challenge.lang,
+                challenge.questions_count
+            ),
+            Ok(None) => tracing::info!("generate_daily_challenges: no free questions for {lang}"),
+            Err(err) => tracing::error!("generate_daily_challenges {lang}: {err}"),
+        }
+    }
+}
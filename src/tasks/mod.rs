@@ -1,4 +1,8 @@
+pub mod daily_challenges;
 pub mod handle_subscriptions;
+pub mod question_export;
+pub mod question_import;
+pub mod user_roles;
 
 use apalis::prelude::{Data, Error};
 use serde::{Deserialize, Serialize};
@@ -9,8 +13,13 @@ use crate::AppContext;
 pub enum TasksEnum {
     #[default]
     CheckSubscriptions,
+    GenerateDailyChallenges,
 }
 
+/// Tick of the daily cron worker
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DailyTick;
+
 pub async fn scheduled_task(
     ctx: TasksEnum,
     state: Data<AppContext>, // Extension(config): Extension<ServerConfig>,
@@ -19,6 +28,13 @@ pub async fn scheduled_task(
         TasksEnum::CheckSubscriptions => {
             handle_subscriptions::disable_expired_subscriptions(&state.db).await
         }
+        TasksEnum::GenerateDailyChallenges => {
+            daily_challenges::generate_daily_challenges(&state.db).await
+        }
     };
     Ok(())
 }
+
+pub async fn daily_task(_: DailyTick, state: Data<AppContext>) -> Result<(), Error> {
+    scheduled_task(TasksEnum::GenerateDailyChallenges, state).await
+}
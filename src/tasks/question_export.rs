@@ -0,0 +1,491 @@
+use std::{
+    collections::{HashMap, HashSet},
+    path::PathBuf,
+    str::FromStr,
+};
+
+use clap::Parser;
+use futures::{Stream, TryStreamExt, stream};
+use sea_orm::{
+    ColumnTrait, DatabaseConnection, DbErr, EntityTrait, QueryFilter, QueryOrder, QuerySelect,
+};
+use tokio::io::AsyncWriteExt;
+use uuid::Uuid;
+
+use crate::{
+    entities::{
+        answers, categories, images, lesson_sections, lessons, question_categories, questions,
+        topics,
+    },
+    models::{
+        question_import::{
+            ImportAnswer, ImportLesson, ImportLessonSection, ImportRecord, ImportTopic,
+        },
+        question_types::AnswerPayload,
+    },
+    rest::{
+        categories::category_paths, license_classes::question_license_classes,
+        questions::question_image_ids,
+    },
+    tasks::question_import::CsvRecord,
+    utils::config::DBConfig,
+};
+
+/// Questions loaded per database round trip
+const PAGE_SIZE: u64 = 500;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    /// Bundle with topics, lessons and questions
+    Json,
+    /// Questions only, in the import CSV layout
+    Csv,
+}
+
+impl FromStr for ExportFormat {
+    type Err = String;
+
+    fn from_str(format: &str) -> Result<Self, Self::Err> {
+        match format {
+            "json" => Ok(Self::Json),
+            "csv" => Ok(Self::Csv),
+            _ => Err(format!("unknown format \"{format}\"")),
+        }
+    }
+}
+
+impl ExportFormat {
+    pub fn content_type(self) -> &'static str {
+        match self {
+            Self::Json => "application/json",
+            Self::Csv => "text/csv; charset=utf-8",
+        }
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Json => "json",
+            Self::Csv => "csv",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ExportFilter {
+    pub lang: Option<String>,
+    pub topic_id: Option<Uuid>,
+    /// Applies to questions and lessons
+    pub status: Option<String>,
+}
+
+/// Topics are referenced by name so the file can be imported into another
+/// database. Names shared by several topics fall back to the ID.
+async fn topic_references(db: &DatabaseConnection) -> Result<HashMap<Uuid, String>, DbErr> {
+    let topics_list = topics::Entity::find().all(db).await?;
+
+    let mut seen = HashSet::new();
+    let duplicated: HashSet<&str> = topics_list
+        .iter()
+        .filter(|t| !seen.insert(t.name.as_str()))
+        .map(|t| t.name.as_str())
+        .collect();
+
+    Ok(topics_list
+        .iter()
+        .map(|t| {
+            let reference = if duplicated.contains(t.name.as_str()) {
+                t.id.to_string()
+            } else {
+                t.name.clone()
+            };
+            (t.id, reference)
+        })
+        .collect())
+}
+
+async fn load_topics(
+    db: &DatabaseConnection,
+    filter: &ExportFilter,
+    references: &HashMap<Uuid, String>,
+) -> Result<(Vec<ImportTopic>, Vec<ImportLesson>), DbErr> {
+    let mut topics_query = topics::Entity::find().order_by_asc(topics::Column::Name);
+    let mut lessons_query = lessons::Entity::find()
+        .order_by_asc(lessons::Column::TopicId)
+        .order_by_asc(lessons::Column::Position);
+    if let Some(topic_id) = filter.topic_id {
+        topics_query = topics_query.filter(topics::Column::Id.eq(topic_id));
+        lessons_query = lessons_query.filter(lessons::Column::TopicId.eq(topic_id));
+    }
+    if let Some(status) = &filter.status {
+        lessons_query = lessons_query.filter(lessons::Column::Status.eq(status));
+    }
+
+    let topics_list = topics_query
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|t| ImportTopic {
+            name: t.name,
+            difficulty: t.difficulty,
+            duration: t.duration,
+            subscription_required: t.subscription_required,
+        })
+        .collect();
+
+    let lessons_list = lessons_query.all(db).await?;
+    let mut sections: HashMap<Uuid, Vec<ImportLessonSection>> = HashMap::new();
+    for section in lesson_sections::Entity::find()
+        .filter(lesson_sections::Column::LessonId.is_in(lessons_list.iter().map(|l| l.id)))
+        .order_by_asc(lesson_sections::Column::Position)
+        .all(db)
+        .await?
+    {
+        sections
+            .entry(section.lesson_id)
+            .or_default()
+            .push(ImportLessonSection {
+                title: section.title,
+                content: section.content,
+            });
+    }
+    let lessons_list = lessons_list
+        .into_iter()
+        .map(|l| ImportLesson {
+            topic: references[&l.topic_id].clone(),
+            title: Some(l.title),
+            position: Some(l.position),
+            content: l.content,
+            sections: sections.remove(&l.id).unwrap_or_default(),
+            status: Some(l.status),
+        })
+        .collect();
+
+    Ok((topics_list, lessons_list))
+}
+
+/// Next page of questions after `after` in ID order, as import records
+async fn load_questions(
+    db: &DatabaseConnection,
+    filter: &ExportFilter,
+    references: &HashMap<Uuid, String>,
+    after: Option<Uuid>,
+) -> Result<Vec<(Uuid, ImportRecord)>, DbErr> {
+    let mut query = questions::Entity::find()
+        .order_by_asc(questions::Column::Id)
+        .limit(PAGE_SIZE);
+    if let Some(after) = after {
+        query = query.filter(questions::Column::Id.gt(after));
+    }
+    if let Some(lang) = &filter.lang {
+        query = query.filter(questions::Column::Lang.eq(lang));
+    }
+    if let Some(topic_id) = filter.topic_id {
+        query = query.filter(questions::Column::TopicId.eq(topic_id));
+    }
+    if let Some(status) = &filter.status {
+        query = query.filter(questions::Column::Status.eq(status));
+    }
+
+    let questions_list = query.all(db).await?;
+    if questions_list.is_empty() {
+        return Ok(Vec::new());
+    }
+    let question_ids: Vec<Uuid> = questions_list.iter().map(|q| q.id).collect();
+
+    let answers_list = answers::Entity::find()
+        .filter(answers::Column::QuestionId.is_in(question_ids.clone()))
+        .order_by_asc(answers::Column::Id)
+        .all(db)
+        .await?;
+    let mut image_ids = question_image_ids(db, &question_ids).await?;
+    let group_keys = translation_group_keys(db, &questions_list).await?;
+    let mut classes_map = question_license_classes(db, &question_ids).await?;
+
+    let image_names: HashMap<Uuid, String> = images::Entity::find()
+        .filter(
+            images::Column::Id.is_in(
+                image_ids
+                    .values()
+                    .flatten()
+                    .cloned()
+                    .chain(answers_list.iter().filter_map(|a| a.image_id))
+                    .collect::<Vec<_>>(),
+            ),
+        )
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|i| (i.id, i.stored_name))
+        .collect();
+
+    let mut answers_map: HashMap<Uuid, Vec<ImportAnswer>> = HashMap::new();
+    for answer in answers_list {
+        answers_map
+            .entry(answer.question_id)
+            .or_default()
+            .push(ImportAnswer {
+                payload: AnswerPayload::from_json(answer.payload.as_ref()),
+                image: answer.image_id.and_then(|id| image_names.get(&id).cloned()),
+                value: answer.value,
+                is_correct: answer.is_correct,
+            });
+    }
+
+    let links = question_categories::Entity::find()
+        .filter(question_categories::Column::QuestionId.is_in(question_ids))
+        .all(db)
+        .await?;
+    // Категории пишутся путём от корня, чтобы импорт восстановил дерево
+    let category_names = category_paths(&categories::Entity::find().all(db).await?);
+    let mut categories_map: HashMap<Uuid, Vec<String>> = HashMap::new();
+    for link in links {
+        if let Some(name) = category_names.get(&link.category_id) {
+            categories_map
+                .entry(link.question_id)
+                .or_default()
+                .push(name.clone());
+        }
+    }
+    for names in categories_map.values_mut() {
+        names.sort();
+    }
+
+    Ok(questions_list
+        .into_iter()
+        .map(|q| {
+            let record = ImportRecord {
+                // Без внешнего ID повторный импорт сопоставит вопрос по id
+                external_id: q.external_id.unwrap_or(q.id.to_string()),
+                topic: references[&q.topic_id].clone(),
+                lang: q.lang,
+                name: q.name,
+                content: q.content,
+                explanation: q.explanation,
+                points: Some(q.points),
+                images: image_ids
+                    .remove(&q.id)
+                    .unwrap_or_default()
+                    .iter()
+                    .filter_map(|id| image_names.get(id).cloned())
+                    .collect(),
+                categories: categories_map.remove(&q.id).unwrap_or_default(),
+                license_classes: classes_map.remove(&q.id).unwrap_or_default(),
+                answers: answers_map.remove(&q.id).unwrap_or_default(),
+                question_type: Some(q.question_type),
+                status: Some(q.status),
+                valid_from: q.valid_from,
+                valid_to: q.valid_to,
+                region: q.region,
+                translation_group: group_keys.get(&q.translation_group_id).cloned(),
+            };
+            (q.id, record)
+        })
+        .collect())
+}
+
+/// Key of every translation group with several variants: the external ID
+/// of its first question by ID, so that every page names the group alike
+async fn translation_group_keys(
+    db: &DatabaseConnection,
+    questions_list: &[questions::Model],
+) -> Result<HashMap<Uuid, String>, DbErr> {
+    let group_ids: HashSet<Uuid> = questions_list
+        .iter()
+        .map(|q| q.translation_group_id)
+        .collect();
+    let mut variants: HashMap<Uuid, Vec<questions::Model>> = HashMap::new();
+    for question in questions::Entity::find()
+        .filter(questions::Column::TranslationGroupId.is_in(group_ids))
+        .order_by_asc(questions::Column::Id)
+        .all(db)
+        .await?
+    {
+        variants
+            .entry(question.translation_group_id)
+            .or_default()
+            .push(question);
+    }
+
+    Ok(variants
+        .into_iter()
+        .filter(|(_, group)| group.len() > 1)
+        .map(|(group_id, group)| {
+            let first = &group[0];
+            let key = first.external_id.clone().unwrap_or(first.id.to_string());
+            (group_id, key)
+        })
+        .collect())
+}
+
+fn to_json<T: serde::Serialize>(value: &T) -> Result<String, DbErr> {
+    serde_json::to_string(value).map_err(|err| DbErr::Custom(err.to_string()))
+}
+
+fn render_page(
+    format: ExportFormat,
+    records: &[(Uuid, ImportRecord)],
+    first: bool,
+) -> Result<String, DbErr> {
+    match format {
+        ExportFormat::Json => {
+            let mut chunk = String::new();
+            for (index, (_, record)) in records.iter().enumerate() {
+                if !first || index > 0 {
+                    chunk.push(',');
+                }
+                chunk.push('\n');
+                chunk.push_str(&to_json(record)?);
+            }
+            Ok(chunk)
+        }
+        ExportFormat::Csv => {
+            let mut writer = csv::WriterBuilder::new()
+                .has_headers(first)
+                .from_writer(Vec::new());
+            for (_, record) in records {
+                writer
+                    .serialize(CsvRecord::from(record))
+                    .map_err(|err| DbErr::Custom(err.to_string()))?;
+            }
+            let bytes = writer
+                .into_inner()
+                .map_err(|err| DbErr::Custom(err.to_string()))?;
+            String::from_utf8(bytes).map_err(|err| DbErr::Custom(err.to_string()))
+        }
+    }
+}
+
+enum Stage {
+    Start,
+    Questions { after: Option<Uuid>, first: bool },
+    Done,
+}
+
+struct ExportState {
+    db: DatabaseConnection,
+    format: ExportFormat,
+    filter: ExportFilter,
+    references: HashMap<Uuid, String>,
+    stage: Stage,
+}
+
+/// Streams the question bank page by page in a layout accepted by the import
+pub fn export_stream(
+    db: DatabaseConnection,
+    format: ExportFormat,
+    filter: ExportFilter,
+) -> impl Stream<Item = Result<String, DbErr>> {
+    let state = ExportState {
+        db,
+        format,
+        filter,
+        references: HashMap::new(),
+        stage: Stage::Start,
+    };
+
+    stream::try_unfold(state, |mut state| async move {
+        let chunk = match state.stage {
+            Stage::Start => {
+                state.references = topic_references(&state.db).await?;
+                state.stage = Stage::Questions {
+                    after: None,
+                    first: true,
+                };
+                match state.format {
+                    ExportFormat::Json => {
+                        let (topics_list, lessons_list) =
+                            load_topics(&state.db, &state.filter, &state.references).await?;
+                        format!(
+                            "{{\"topics\":{},\"lessons\":{},\"questions\":[",
+                            to_json(&topics_list)?,
+                            to_json(&lessons_list)?
+                        )
+                    }
+                    ExportFormat::Csv => String::new(),
+                }
+            }
+            Stage::Questions { after, first } => {
+                let records =
+                    load_questions(&state.db, &state.filter, &state.references, after).await?;
+                match records.last() {
+                    Some((last_id, _)) => {
+                        state.stage = Stage::Questions {
+                            after: Some(*last_id),
+                            first: false,
+                        };
+                        render_page(state.format, &records, first)?
+                    }
+                    None => {
+                        state.stage = Stage::Done;
+                        match state.format {
+                            ExportFormat::Json => "\n]}\n".to_string(),
+                            ExportFormat::Csv => String::new(),
+                        }
+                    }
+                }
+            }
+            Stage::Done => return Ok(None),
+        };
+        Ok(Some((chunk, state)))
+    })
+}
+
+/// `drive_mind export-questions --out bank.json [--lang ru] [--status published]`
+#[derive(Parser, Debug)]
+#[command(
+    name = "export-questions",
+    about = "Export topics, lessons and questions to a CSV or JSON file"
+)]
+pub struct ExportCli {
+    /// Output file, stdout by default
+    #[arg(long)]
+    pub out: Option<PathBuf>,
+    /// "csv" or "json", taken from the file extension by default
+    #[arg(long)]
+    pub format: Option<String>,
+    #[arg(long)]
+    pub lang: Option<String>,
+    #[arg(long)]
+    pub topic_id: Option<Uuid>,
+    #[arg(long)]
+    pub status: Option<String>,
+    #[command(flatten)]
+    pub db: DBConfig,
+}
+
+pub async fn run_cli(args: impl Iterator<Item = String>) -> Result<(), Box<dyn std::error::Error>> {
+    let cli = ExportCli::parse_from(args);
+
+    let format: ExportFormat = cli
+        .format
+        .or_else(|| {
+            cli.out
+                .as_ref()
+                .and_then(|path| path.extension())
+                .and_then(|ext| ext.to_str())
+                .map(str::to_lowercase)
+        })
+        .as_deref()
+        .unwrap_or("json")
+        .parse()?;
+
+    let filter = ExportFilter {
+        lang: cli.lang,
+        topic_id: cli.topic_id,
+        status: cli.status,
+    };
+
+    let db = cli.db.connect().await;
+    let mut out: Box<dyn tokio::io::AsyncWrite + Unpin> = match &cli.out {
+        Some(path) => Box::new(tokio::fs::File::create(path).await?),
+        None => Box::new(tokio::io::stdout()),
+    };
+
+    let mut chunks = std::pin::pin!(export_stream(db, format, filter));
+    while let Some(chunk) = chunks.try_next().await? {
+        out.write_all(chunk.as_bytes()).await?;
+    }
+    out.flush().await?;
+
+    Ok(())
+}
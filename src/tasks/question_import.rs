@@ -0,0 +1,1305 @@
+use std::{
+    collections::{BTreeSet, HashMap, HashSet},
+    path::PathBuf,
+};
+
+use chrono::NaiveDate;
+use clap::Parser;
+use sea_orm::{
+    ActiveModelTrait, ActiveValue::Set, ColumnTrait, Condition, ConnectionTrait,
+    DatabaseConnection, DbErr, EntityTrait, IntoActiveModel, QueryFilter, QueryOrder,
+    TransactionTrait,
+};
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use uuid::Uuid;
+
+use crate::{
+    entities::{
+        answers, categories, images, lessons, license_classes, question_categories, questions,
+        topics,
+    },
+    models::lessons::LessonSectionParams,
+    models::question_import::{
+        ImportAnswer, ImportIssue, ImportLesson, ImportRecord, ImportReport, ImportTopic,
+    },
+    models::question_types::{AnswerPayload, QuestionType},
+    rest::{
+        categories::{CATEGORY_PATH_SEPARATOR, category_paths},
+        content_reviews::{STATUSES, log_review},
+        lessons::{estimate_reading_minutes, next_position, replace_sections},
+        license_classes::set_question_license_classes,
+        question_revisions::record_revision,
+        questions::set_question_images,
+    },
+    utils::{
+        config::DBConfig,
+        markdown::{MarkdownRefs, ResolvedRefs, render_markdown},
+    },
+};
+
+/// Separator of list cells in CSV files
+const LIST_SEPARATOR: char = '|';
+
+/// Flat CSV layout of an import record. `image`, `categories`,
+/// `license_classes` and `answers` are `|`-separated lists, `correct` holds 1-based positions of correct
+/// answers, `payloads` and `answer_images` JSON arrays with the payload and
+/// the image of every answer.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CsvRecord {
+    pub external_id: String,
+    pub topic: String,
+    pub lang: String,
+    pub name: String,
+    pub content: Option<String>,
+    pub explanation: String,
+    pub points: Option<i16>,
+    pub image: Option<String>,
+    pub categories: Option<String>,
+    pub answers: String,
+    pub correct: String,
+    pub status: Option<String>,
+    #[serde(default)]
+    pub question_type: Option<String>,
+    #[serde(default)]
+    pub payloads: Option<String>,
+    #[serde(default)]
+    pub answer_images: Option<String>,
+    #[serde(default)]
+    pub license_classes: Option<String>,
+    #[serde(default)]
+    pub valid_from: Option<NaiveDate>,
+    #[serde(default)]
+    pub valid_to: Option<NaiveDate>,
+    #[serde(default)]
+    pub region: Option<String>,
+    #[serde(default)]
+    pub translation_group: Option<String>,
+}
+
+fn split_list(cell: &str) -> Vec<String> {
+    cell.split(LIST_SEPARATOR)
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+fn join_list<'a>(items: impl Iterator<Item = &'a str>) -> String {
+    items.collect::<Vec<_>>().join(&LIST_SEPARATOR.to_string())
+}
+
+impl TryFrom<CsvRecord> for ImportRecord {
+    type Error = String;
+
+    fn try_from(row: CsvRecord) -> Result<Self, Self::Error> {
+        let values = split_list(&row.answers);
+        let correct = split_list(&row.correct)
+            .iter()
+            .map(|position| {
+                position
+                    .parse::<usize>()
+                    .ok()
+                    .filter(|p| (1..=values.len()).contains(p))
+                    .ok_or(format!("invalid correct answer position \"{position}\""))
+            })
+            .collect::<Result<HashSet<usize>, String>>()?;
+        let mut payloads: Vec<Option<AnswerPayload>> = match row.payloads.as_deref() {
+            Some(cell) if !cell.trim().is_empty() => {
+                serde_json::from_str(cell).map_err(|err| format!("invalid payloads: {err}"))?
+            }
+            _ => Vec::new(),
+        };
+        if !payloads.is_empty() && payloads.len() != values.len() {
+            return Err("payloads must match the answers".to_string());
+        }
+        payloads.resize(values.len(), None);
+        let mut answer_images: Vec<Option<String>> = match row.answer_images.as_deref() {
+            Some(cell) if !cell.trim().is_empty() => {
+                serde_json::from_str(cell).map_err(|err| format!("invalid answer images: {err}"))?
+            }
+            _ => Vec::new(),
+        };
+        if !answer_images.is_empty() && answer_images.len() != values.len() {
+            return Err("answer images must match the answers".to_string());
+        }
+        answer_images.resize(values.len(), None);
+
+        Ok(ImportRecord {
+            external_id: row.external_id,
+            topic: row.topic,
+            lang: row.lang,
+            name: row.name,
+            content: row.content,
+            explanation: row.explanation,
+            points: row.points,
+            images: row.image.as_deref().map(split_list).unwrap_or_default(),
+            categories: row
+                .categories
+                .as_deref()
+                .map(split_list)
+                .unwrap_or_default(),
+            license_classes: row
+                .license_classes
+                .as_deref()
+                .map(split_list)
+                .unwrap_or_default(),
+            answers: values
+                .into_iter()
+                .zip(payloads)
+                .zip(answer_images)
+                .enumerate()
+                .map(|(index, ((value, payload), image))| ImportAnswer {
+                    value,
+                    is_correct: correct.contains(&(index + 1)),
+                    payload,
+                    image,
+                })
+                .collect(),
+            question_type: row.question_type.filter(|t| !t.trim().is_empty()),
+            status: row.status,
+            valid_from: row.valid_from,
+            valid_to: row.valid_to,
+            region: row.region.filter(|r| !r.trim().is_empty()),
+            translation_group: row.translation_group.filter(|g| !g.trim().is_empty()),
+        })
+    }
+}
+
+impl From<&ImportRecord> for CsvRecord {
+    fn from(record: &ImportRecord) -> Self {
+        Self {
+            external_id: record.external_id.clone(),
+            topic: record.topic.clone(),
+            lang: record.lang.clone(),
+            name: record.name.clone(),
+            content: record.content.clone(),
+            explanation: record.explanation.clone(),
+            points: record.points,
+            image: Some(join_list(record.images.iter().map(String::as_str)))
+                .filter(|c| !c.is_empty()),
+            categories: Some(join_list(record.categories.iter().map(String::as_str))),
+            answers: join_list(record.answers.iter().map(|a| a.value.as_str())),
+            correct: record
+                .answers
+                .iter()
+                .enumerate()
+                .filter(|(_, a)| a.is_correct)
+                .map(|(index, _)| (index + 1).to_string())
+                .collect::<Vec<_>>()
+                .join(&LIST_SEPARATOR.to_string()),
+            status: record.status.clone(),
+            question_type: record.question_type.clone(),
+            payloads: record.answers.iter().any(|a| a.payload.is_some()).then(|| {
+                let payloads: Vec<&Option<AnswerPayload>> =
+                    record.answers.iter().map(|a| &a.payload).collect();
+                serde_json::to_string(&payloads).unwrap_or_default()
+            }),
+            answer_images: record.answers.iter().any(|a| a.image.is_some()).then(|| {
+                let images: Vec<&Option<String>> =
+                    record.answers.iter().map(|a| &a.image).collect();
+                serde_json::to_string(&images).unwrap_or_default()
+            }),
+            license_classes: Some(join_list(record.license_classes.iter().map(String::as_str)))
+                .filter(|c| !c.is_empty()),
+            valid_from: record.valid_from,
+            valid_to: record.valid_to,
+            region: record.region.clone(),
+            translation_group: record.translation_group.clone(),
+        }
+    }
+}
+
+/// Contents of an import file. Every record keeps its 1-based position in
+/// its section, records that could not be read are reported as issues.
+#[derive(Debug, Default)]
+pub struct ParsedFile {
+    pub topics: Vec<(usize, ImportTopic)>,
+    pub lessons: Vec<(usize, ImportLesson)>,
+    pub questions: Vec<(usize, ImportRecord)>,
+    pub issues: Vec<ImportIssue>,
+}
+
+/// A JSON file is either a plain array of questions or the bundle written by the export
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum JsonFile {
+    Questions(Vec<serde_json::Value>),
+    Bundle {
+        #[serde(default)]
+        topics: Vec<serde_json::Value>,
+        #[serde(default)]
+        lessons: Vec<serde_json::Value>,
+        #[serde(default)]
+        questions: Vec<serde_json::Value>,
+    },
+}
+
+fn read_section<T: DeserializeOwned>(
+    section: &str,
+    values: Vec<serde_json::Value>,
+    issues: &mut Vec<ImportIssue>,
+) -> Vec<(usize, T)> {
+    let mut records = Vec::new();
+    for (index, value) in values.into_iter().enumerate() {
+        match serde_json::from_value(value) {
+            Ok(record) => records.push((index + 1, record)),
+            Err(err) => issues.push(ImportIssue {
+                section: section.to_string(),
+                row: index + 1,
+                external_id: None,
+                message: err.to_string(),
+            }),
+        }
+    }
+    records
+}
+
+/// Reads an import file. Fails only when the file as a whole is unreadable.
+pub fn parse_records(format: &str, data: &str) -> Result<ParsedFile, String> {
+    let mut parsed = ParsedFile::default();
+
+    match format {
+        "json" => {
+            let (topics, lessons, questions) =
+                match serde_json::from_str(data).map_err(|err| err.to_string())? {
+                    JsonFile::Questions(questions) => (Vec::new(), Vec::new(), questions),
+                    JsonFile::Bundle {
+                        topics,
+                        lessons,
+                        questions,
+                    } => (topics, lessons, questions),
+                };
+            parsed.topics = read_section("topics", topics, &mut parsed.issues);
+            parsed.lessons = read_section("lessons", lessons, &mut parsed.issues);
+            parsed.questions = read_section("questions", questions, &mut parsed.issues);
+        }
+        "csv" => {
+            let mut reader = csv::Reader::from_reader(data.as_bytes());
+            for (index, row) in reader.deserialize::<CsvRecord>().enumerate() {
+                match row
+                    .map_err(|err| err.to_string())
+                    .and_then(ImportRecord::try_from)
+                {
+                    Ok(record) => parsed.questions.push((index + 1, record)),
+                    Err(message) => parsed.issues.push(ImportIssue {
+                        section: "questions".to_string(),
+                        row: index + 1,
+                        external_id: None,
+                        message,
+                    }),
+                }
+            }
+        }
+        _ => return Err(format!("unknown format \"{format}\"")),
+    }
+
+    Ok(parsed)
+}
+
+/// Topic of a record: an existing one or one created from the same file
+#[derive(Debug, Clone)]
+enum TopicRef {
+    Existing(Uuid),
+    New(String),
+}
+
+impl TopicRef {
+    fn id(&self, created: &HashMap<String, Uuid>) -> Uuid {
+        match self {
+            TopicRef::Existing(id) => *id,
+            TopicRef::New(name) => created[name],
+        }
+    }
+}
+
+/// Record that passed validation, with resolved references
+struct ResolvedRecord {
+    record: ImportRecord,
+    topic: TopicRef,
+    /// Question images in display order
+    image_ids: Vec<Uuid>,
+    /// Image of every answer, in the order of the answers
+    answer_image_ids: Vec<Option<Uuid>>,
+    /// Paths of the linked categories, existing or to be created
+    category_paths: Vec<String>,
+    existing: Option<questions::Model>,
+}
+
+/// Existing questions by external ID. Exports fill in the question ID when
+/// there is no external ID yet, so such IDs match too.
+async fn questions_by_external_id<C: ConnectionTrait>(
+    db: &C,
+    external_ids: &[String],
+) -> Result<HashMap<String, questions::Model>, DbErr> {
+    let mut found: HashMap<String, questions::Model> = questions::Entity::find()
+        .filter(questions::Column::ExternalId.is_in(external_ids.to_vec()))
+        .all(db)
+        .await?
+        .into_iter()
+        .filter_map(|q| q.external_id.clone().map(|id| (id, q)))
+        .collect();
+
+    let question_ids: Vec<Uuid> = external_ids
+        .iter()
+        .filter_map(|id| id.parse::<Uuid>().ok())
+        .collect();
+    if !question_ids.is_empty() {
+        for question in questions::Entity::find()
+            .filter(questions::Column::Id.is_in(question_ids))
+            .filter(questions::Column::ExternalId.is_null())
+            .all(db)
+            .await?
+        {
+            found.entry(question.id.to_string()).or_insert(question);
+        }
+    }
+
+    Ok(found)
+}
+
+/// Checks the translation groups named by the records. Returns the IDs of
+/// group keys that are only in the bank and an error message for every
+/// invalid key.
+async fn check_translation_groups<C: ConnectionTrait>(
+    db: &C,
+    records: &[(usize, ImportRecord)],
+    existing: &HashMap<String, questions::Model>,
+) -> Result<(HashMap<String, Uuid>, HashMap<String, String>), DbErr> {
+    // Первая запись с внешним ID, повторы отклоняются позже
+    let mut in_file: HashMap<String, (String, Option<String>)> = HashMap::new();
+    for (_, record) in records {
+        in_file
+            .entry(record.external_id.trim().to_string())
+            .or_insert((
+                record.lang.clone(),
+                record
+                    .translation_group
+                    .as_deref()
+                    .map(|key| key.trim().to_string()),
+            ));
+    }
+    let keys: BTreeSet<String> = in_file
+        .values()
+        .filter_map(|(_, key)| key.clone())
+        .collect();
+    if keys.is_empty() {
+        return Ok((HashMap::new(), HashMap::new()));
+    }
+
+    let outside: Vec<String> = keys
+        .iter()
+        .filter(|key| !in_file.contains_key(*key))
+        .cloned()
+        .collect();
+    let bank_anchors = questions_by_external_id(db, &outside).await?;
+    let group_ids: Vec<Uuid> = keys
+        .iter()
+        .filter_map(|key| existing.get(key).or(bank_anchors.get(key)))
+        .map(|q| q.translation_group_id)
+        .collect();
+    let mut variants: HashMap<Uuid, Vec<questions::Model>> = HashMap::new();
+    for question in questions::Entity::find()
+        .filter(questions::Column::TranslationGroupId.is_in(group_ids))
+        .all(db)
+        .await?
+    {
+        variants
+            .entry(question.translation_group_id)
+            .or_default()
+            .push(question);
+    }
+
+    let mut errors = HashMap::new();
+    for key in keys {
+        if let Some((_, Some(own))) = in_file.get(&key)
+            && *own != key
+        {
+            errors.insert(
+                key.clone(),
+                format!("translation group \"{key}\" belongs to the group \"{own}\""),
+            );
+            continue;
+        }
+        // Язык каждого вопроса, который окажется в группе
+        let mut members: HashMap<String, String> = in_file
+            .iter()
+            .filter(|(_, (_, group))| group.as_ref() == Some(&key))
+            .map(|(external_id, (lang, _))| (external_id.clone(), lang.clone()))
+            .collect();
+        match in_file.get(&key) {
+            Some((lang, _)) => {
+                members.insert(key.clone(), lang.clone());
+            }
+            None if bank_anchors.contains_key(&key) => {}
+            None => {
+                errors.insert(
+                    key.clone(),
+                    format!("translation group \"{key}\" not found"),
+                );
+                continue;
+            }
+        }
+        if let Some(anchor) = existing.get(&key).or(bank_anchors.get(&key)) {
+            for variant in variants
+                .get(&anchor.translation_group_id)
+                .into_iter()
+                .flatten()
+            {
+                let external_id = variant
+                    .external_id
+                    .clone()
+                    .unwrap_or(variant.id.to_string());
+                // Вопрос файла со своей группой уходит из текущей
+                match in_file.get(&external_id) {
+                    Some((_, Some(_))) => {}
+                    Some((lang, None)) => {
+                        members.entry(external_id).or_insert(lang.clone());
+                    }
+                    None => {
+                        members.entry(external_id).or_insert(variant.lang.clone());
+                    }
+                }
+            }
+        }
+
+        let mut langs = HashSet::new();
+        if let Some(lang) = members.values().find(|lang| !langs.insert(lang.as_str())) {
+            errors.insert(
+                key.clone(),
+                format!("translation group \"{key}\" has several questions in \"{lang}\""),
+            );
+        }
+    }
+
+    Ok((
+        bank_anchors
+            .into_iter()
+            .map(|(key, q)| (key, q.id))
+            .collect(),
+        errors,
+    ))
+}
+
+/// Existing categories by path and by name; several IDs under one key make
+/// the reference ambiguous
+struct CategoryIndex {
+    by_path: HashMap<String, Vec<Uuid>>,
+    by_name: HashMap<String, Vec<String>>,
+}
+
+impl CategoryIndex {
+    fn new(categories_list: &[categories::Model]) -> Self {
+        let paths = category_paths(categories_list);
+        let mut index = CategoryIndex {
+            by_path: HashMap::new(),
+            by_name: HashMap::new(),
+        };
+        for category in categories_list {
+            let path = paths[&category.id].clone();
+            index
+                .by_path
+                .entry(path.clone())
+                .or_default()
+                .push(category.id);
+            index
+                .by_name
+                .entry(category.name.clone())
+                .or_default()
+                .push(path);
+        }
+        index
+    }
+
+    /// Categories with a path of their own, the others are only reachable by ID
+    fn unique_paths(&self) -> HashMap<String, Uuid> {
+        self.by_path
+            .iter()
+            .filter_map(|(path, ids)| match ids.as_slice() {
+                [id] => Some((path.clone(), *id)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Resolves a category reference to the path of an existing category or
+    /// of one to be created
+    fn resolve(&self, reference: &str) -> Result<String, String> {
+        let path = reference
+            .split(CATEGORY_PATH_SEPARATOR)
+            .map(str::trim)
+            .collect::<Vec<_>>()
+            .join(&CATEGORY_PATH_SEPARATOR.to_string());
+
+        match self.by_path.get(&path).map(Vec::len) {
+            Some(1) => return Ok(path),
+            Some(_) => return Err(format!("category \"{path}\" is ambiguous")),
+            None => {}
+        }
+        if !path.contains(CATEGORY_PATH_SEPARATOR) {
+            match self.by_name.get(&path).map(Vec::as_slice) {
+                Some([only]) => return Ok(only.clone()),
+                Some([_, _, ..]) => {
+                    return Err(format!(
+                        "category \"{path}\" is ambiguous, use its path from the root"
+                    ));
+                }
+                _ => {}
+            }
+        }
+        for (end, _) in path.match_indices(CATEGORY_PATH_SEPARATOR) {
+            if self
+                .by_path
+                .get(&path[..end])
+                .is_some_and(|ids| ids.len() > 1)
+            {
+                return Err(format!("category \"{}\" is ambiguous", &path[..end]));
+            }
+        }
+
+        Ok(path)
+    }
+}
+
+/// Finds a topic by ID or by its exact name, including topics of the same file
+fn resolve_topic(
+    topics_list: &[topics::Model],
+    new_topics: &BTreeSet<String>,
+    reference: &str,
+) -> Result<TopicRef, String> {
+    if let Ok(id) = reference.parse::<Uuid>() {
+        return topics_list
+            .iter()
+            .find(|t| t.id == id)
+            .map(|t| TopicRef::Existing(t.id))
+            .ok_or(format!("topic {id} not found"));
+    }
+
+    let matches: Vec<&topics::Model> = topics_list.iter().filter(|t| t.name == reference).collect();
+    match matches.as_slice() {
+        [topic] => Ok(TopicRef::Existing(topic.id)),
+        [] if new_topics.contains(reference) => Ok(TopicRef::New(reference.to_string())),
+        [] => Err(format!("topic \"{reference}\" not found")),
+        _ => Err(format!(
+            "topic name \"{reference}\" is ambiguous, use the ID"
+        )),
+    }
+}
+
+fn check_topic(topic: &ImportTopic) -> Result<(), String> {
+    if !(1..=255).contains(&topic.name.trim().chars().count()) {
+        return Err("name must be 1-255 characters".to_string());
+    }
+    if !(1..=50).contains(&topic.difficulty.chars().count()) {
+        return Err("difficulty must be 1-50 characters".to_string());
+    }
+    if topic.duration < 0 {
+        return Err("duration must not be negative".to_string());
+    }
+    Ok(())
+}
+
+fn check_lesson(lesson: &ImportLesson) -> Result<(), String> {
+    if let Some(title) = &lesson.title
+        && !(1..=255).contains(&title.trim().chars().count())
+    {
+        return Err("title must be 1-255 characters".to_string());
+    }
+    if lesson.content.is_empty() {
+        return Err("content is required".to_string());
+    }
+    for (index, section) in lesson.sections.iter().enumerate() {
+        if !(1..=255).contains(&section.title.chars().count()) || section.content.is_empty() {
+            return Err(format!(
+                "section {} needs a 1-255 character title and content",
+                index + 1
+            ));
+        }
+    }
+    if let Some(status) = &lesson.status
+        && !STATUSES.contains(&status.as_str())
+    {
+        return Err(format!("unknown status \"{status}\""));
+    }
+    Ok(())
+}
+
+fn check_record(record: &ImportRecord) -> Result<(), String> {
+    let external_id = record.external_id.trim();
+    if external_id.is_empty() || external_id.len() > 255 {
+        return Err("external_id must be 1-255 characters".to_string());
+    }
+    if !(2..=10).contains(&record.lang.chars().count()) {
+        return Err("lang must be 2-10 characters".to_string());
+    }
+    if !(1..=500).contains(&record.name.chars().count()) {
+        return Err("name must be 1-500 characters".to_string());
+    }
+    if record.explanation.is_empty() {
+        return Err("explanation is required".to_string());
+    }
+    if record.points.is_some_and(|p| !(1..=100).contains(&p)) {
+        return Err("points must be between 1 and 100".to_string());
+    }
+    let question_type = match &record.question_type {
+        Some(question_type) => question_type
+            .parse::<QuestionType>()
+            .map_err(|_| format!("unknown question type \"{question_type}\""))?,
+        None => QuestionType::Choice,
+    };
+    if record.answers.is_empty() {
+        return Err("at least one answer is required".to_string());
+    }
+    question_type.check_answers(
+        record
+            .answers
+            .iter()
+            .map(|a| (a.is_correct, a.payload.as_ref())),
+    )?;
+    if record
+        .answers
+        .iter()
+        .any(|a| !(1..=500).contains(&a.value.chars().count()))
+    {
+        return Err("answer values must be 1-500 characters".to_string());
+    }
+    if record.categories.iter().any(|c| {
+        c.split(CATEGORY_PATH_SEPARATOR)
+            .any(|name| name.trim().is_empty() || name.trim().len() > 255)
+    }) {
+        return Err("category names must be 1-255 characters".to_string());
+    }
+    if let Some(status) = &record.status
+        && !STATUSES.contains(&status.as_str())
+    {
+        return Err(format!("unknown status \"{status}\""));
+    }
+    if let (Some(from), Some(to)) = (record.valid_from, record.valid_to)
+        && from > to
+    {
+        return Err("valid_from must not be after valid_to".to_string());
+    }
+    if record
+        .region
+        .as_ref()
+        .is_some_and(|r| r.trim().is_empty() || r.len() > 20)
+    {
+        return Err("region must be 1-20 characters".to_string());
+    }
+    Ok(())
+}
+
+/// Creates or updates the question of a record, replacing its categories and
+/// licence classes. Answers are matched by value, then by position, and
+/// updated in place to keep their IDs and translation groups.
+async fn upsert_question<C: ConnectionTrait>(
+    db: &C,
+    item: ResolvedRecord,
+    topic_ids: &HashMap<String, Uuid>,
+    category_ids: &HashMap<String, Uuid>,
+) -> Result<Uuid, DbErr> {
+    let record = item.record;
+    let topic_id = item.topic.id(topic_ids);
+
+    let question = match item.existing {
+        Some(question) => {
+            let mut to_update = question.into_active_model();
+            to_update.topic_id = Set(topic_id);
+            to_update.external_id = Set(Some(record.external_id.trim().to_string()));
+            to_update.name = Set(record.name);
+            to_update.lang = Set(record.lang);
+            to_update.content = Set(record.content);
+            to_update.explanation = Set(record.explanation);
+            to_update.points = Set(record.points.unwrap_or(1));
+            if let Some(question_type) = record.question_type {
+                to_update.question_type = Set(question_type);
+            }
+            if let Some(status) = record.status {
+                to_update.status = Set(status);
+            }
+            to_update.valid_from = Set(record.valid_from);
+            to_update.valid_to = Set(record.valid_to);
+            to_update.region = Set(record.region);
+            to_update.update(db).await?
+        }
+        None => {
+            questions::ActiveModel {
+                topic_id: Set(topic_id),
+                name: Set(record.name),
+                lang: Set(record.lang),
+                content: Set(record.content),
+                explanation: Set(record.explanation),
+                points: Set(record.points.unwrap_or(1)),
+                status: Set(record.status.unwrap_or("draft".to_string())),
+                question_type: Set(record
+                    .question_type
+                    .unwrap_or(QuestionType::Choice.as_str().to_string())),
+                external_id: Set(Some(record.external_id.trim().to_string())),
+                valid_from: Set(record.valid_from),
+                valid_to: Set(record.valid_to),
+                region: Set(record.region),
+                ..Default::default()
+            }
+            .insert(db)
+            .await?
+        }
+    };
+
+    set_question_images(db, question.id, &item.image_ids).await?;
+    let classes: Vec<String> = record
+        .license_classes
+        .iter()
+        .map(|code| code.trim().to_string())
+        .collect();
+    set_question_license_classes(db, question.id, &classes).await?;
+
+    // Сначала совпадение по значению, затем по позиции среди оставшихся
+    let mut existing: Vec<Option<answers::Model>> = answers::Entity::find()
+        .filter(answers::Column::QuestionId.eq(question.id))
+        .order_by_asc(answers::Column::Id)
+        .all(db)
+        .await?
+        .into_iter()
+        .map(Some)
+        .collect();
+    let mut matched: Vec<Option<answers::Model>> = record
+        .answers
+        .iter()
+        .map(|answer| {
+            existing
+                .iter_mut()
+                .find(|current| current.as_ref().is_some_and(|a| a.value == answer.value))
+                .and_then(Option::take)
+        })
+        .collect();
+    for (index, current) in matched.iter_mut().enumerate() {
+        if current.is_none() {
+            *current = existing.get_mut(index).and_then(Option::take);
+        }
+    }
+
+    let stale: Vec<Uuid> = existing.into_iter().flatten().map(|a| a.id).collect();
+    if !stale.is_empty() {
+        answers::Entity::delete_many()
+            .filter(answers::Column::Id.is_in(stale))
+            .exec(db)
+            .await?;
+    }
+    for ((answer, image_id), current) in record
+        .answers
+        .into_iter()
+        .zip(item.answer_image_ids)
+        .zip(matched)
+    {
+        let payload = answer.payload.map(|p| p.to_json());
+        match current {
+            Some(current) => {
+                let mut to_update = current.into_active_model();
+                to_update.value = Set(answer.value);
+                to_update.is_correct = Set(answer.is_correct);
+                to_update.payload = Set(payload);
+                to_update.image_id = Set(image_id);
+                to_update.update(db).await?;
+            }
+            None => {
+                answers::ActiveModel {
+                    question_id: Set(question.id),
+                    value: Set(answer.value),
+                    is_correct: Set(answer.is_correct),
+                    payload: Set(payload),
+                    image_id: Set(image_id),
+                    ..Default::default()
+                }
+                .insert(db)
+                .await?;
+            }
+        }
+    }
+
+    question_categories::Entity::delete_many()
+        .filter(question_categories::Column::QuestionId.eq(question.id))
+        .exec(db)
+        .await?;
+    let linked: BTreeSet<Uuid> = item
+        .category_paths
+        .iter()
+        .filter_map(|path| category_ids.get(path).copied())
+        .collect();
+    for category_id in linked {
+        question_categories::ActiveModel {
+            question_id: Set(question.id),
+            category_id: Set(category_id),
+        }
+        .insert(db)
+        .await?;
+    }
+
+    Ok(question.id)
+}
+
+/// Status set by an import when it differs from the current one, new content
+/// starts as a draft
+fn imported_status(previous: Option<String>, imported: Option<String>) -> Option<String> {
+    imported.filter(|status| previous.as_deref().unwrap_or("draft") != status)
+}
+
+/// Validates the records and, when `commit` is set and nothing failed,
+/// writes all of them in a single transaction. Topics are matched by name,
+/// lessons by topic and title and questions by external ID. Imports run only
+/// from the editors' endpoint or the server CLI, so a `status` in the file is
+/// applied; every status it changes is logged in the review history.
+pub async fn import_records(
+    db: &DatabaseConnection,
+    parsed: ParsedFile,
+    commit: bool,
+    author_id: Option<Uuid>,
+) -> Result<ImportReport, DbErr> {
+    let ParsedFile {
+        topics: topic_records,
+        lessons: lesson_records,
+        questions: records,
+        mut issues,
+    } = parsed;
+    let total = records.len() + issues.iter().filter(|i| i.section == "questions").count();
+
+    let topics_list = topics::Entity::find().all(db).await?;
+    let category_index = CategoryIndex::new(&categories::Entity::find().all(db).await?);
+    let mut category_ids = category_index.unique_paths();
+    let known_classes: HashSet<String> = license_classes::Entity::find()
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|c| c.code)
+        .collect();
+
+    let mut new_topics: BTreeSet<String> = BTreeSet::new();
+    let mut seen_topics: HashSet<String> = HashSet::new();
+    let mut resolved_topics = Vec::new();
+    for (row, topic) in topic_records {
+        let name = topic.name.trim().to_string();
+        let checked = check_topic(&topic).and_then(|_| {
+            if !seen_topics.insert(name.clone()) {
+                return Err("duplicate topic name in the file".to_string());
+            }
+            let matches: Vec<&topics::Model> =
+                topics_list.iter().filter(|t| t.name == name).collect();
+            match matches.as_slice() {
+                [] => Ok(None),
+                [existing] => Ok(Some((*existing).clone())),
+                _ => Err(format!("topic name \"{name}\" is ambiguous")),
+            }
+        });
+        match checked {
+            Ok(existing) => {
+                if existing.is_none() {
+                    new_topics.insert(name);
+                }
+                resolved_topics.push((existing, topic));
+            }
+            Err(message) => issues.push(ImportIssue {
+                section: "topics".to_string(),
+                row,
+                external_id: None,
+                message,
+            }),
+        }
+    }
+
+    let mut lesson_keys: HashSet<(String, String)> = HashSet::new();
+    let mut resolved_lessons = Vec::new();
+    for (row, lesson) in lesson_records {
+        let checked = check_lesson(&lesson)
+            .and_then(|_| resolve_topic(&topics_list, &new_topics, lesson.topic.trim()))
+            .and_then(|topic| {
+                let (key, topic_name) = match &topic {
+                    TopicRef::Existing(id) => (
+                        id.to_string(),
+                        topics_list
+                            .iter()
+                            .find(|t| t.id == *id)
+                            .map(|t| t.name.clone())
+                            .unwrap_or_default(),
+                    ),
+                    TopicRef::New(name) => (name.clone(), name.clone()),
+                };
+                // Урок без названия совпадает с уроком, названным по теме
+                let title = match lesson.title.as_deref().map(str::trim) {
+                    Some(title) if !title.is_empty() => title.to_string(),
+                    _ => topic_name,
+                };
+                if lesson_keys.insert((key, title.clone())) {
+                    Ok((topic, title))
+                } else {
+                    Err("duplicate lesson title for the topic in the file".to_string())
+                }
+            });
+        // Встроенные изображения и ссылки должны существовать
+        let checked = match checked {
+            Ok(resolved) => {
+                let refs = MarkdownRefs::collect(
+                    std::iter::once(lesson.content.as_str())
+                        .chain(lesson.sections.iter().map(|s| s.content.as_str())),
+                );
+                let missing = ResolvedRefs::load(db, &refs).await?.missing(&refs);
+                if missing.is_empty() {
+                    Ok(resolved)
+                } else {
+                    Err(format!("unknown references: {}", missing.join(", ")))
+                }
+            }
+            Err(message) => Err(message),
+        };
+        match checked {
+            Ok((topic, title)) => resolved_lessons.push((topic, title, lesson)),
+            Err(message) => issues.push(ImportIssue {
+                section: "lessons".to_string(),
+                row,
+                external_id: None,
+                message,
+            }),
+        }
+    }
+
+    let external_ids: Vec<String> = records
+        .iter()
+        .map(|(_, r)| r.external_id.trim().to_string())
+        .collect();
+    let mut existing = questions_by_external_id(db, &external_ids).await?;
+    let (group_anchors, group_errors) = check_translation_groups(db, &records, &existing).await?;
+
+    // Картинки указываются по ID или по имени сохранённого файла
+    let image_refs: Vec<&str> = records
+        .iter()
+        .flat_map(|(_, r)| {
+            r.images
+                .iter()
+                .map(|r| r.trim())
+                .chain(r.answers.iter().filter_map(|a| a.image.as_deref()))
+        })
+        .collect();
+    let images_list = images::Entity::find()
+        .filter(
+            Condition::any()
+                .add(
+                    images::Column::Id.is_in(
+                        image_refs
+                            .iter()
+                            .filter_map(|r| r.parse::<Uuid>().ok())
+                            .collect::<Vec<_>>(),
+                    ),
+                )
+                .add(images::Column::StoredName.is_in(image_refs.iter().cloned())),
+        )
+        .all(db)
+        .await?;
+
+    let mut seen: HashSet<String> = HashSet::new();
+    let mut new_categories: BTreeSet<String> = BTreeSet::new();
+    let mut resolved = Vec::new();
+
+    for (row, record) in records {
+        let external_id = record.external_id.trim().to_string();
+        let checked = check_record(&record)
+            .and_then(|_| {
+                if seen.insert(external_id.clone()) {
+                    Ok(())
+                } else {
+                    Err("duplicate external_id in the file".to_string())
+                }
+            })
+            .and_then(|_| {
+                match record
+                    .license_classes
+                    .iter()
+                    .find(|code| !known_classes.contains(code.trim()))
+                {
+                    Some(code) => Err(format!("unknown licence class \"{code}\"")),
+                    None => Ok(()),
+                }
+            })
+            .and_then(|_| {
+                match record
+                    .translation_group
+                    .as_deref()
+                    .and_then(|key| group_errors.get(key.trim()))
+                {
+                    Some(message) => Err(message.clone()),
+                    None => Ok(()),
+                }
+            })
+            .and_then(|_| resolve_topic(&topics_list, &new_topics, record.topic.trim()))
+            .and_then(|topic| {
+                let find_image = |reference: &str| {
+                    images_list
+                        .iter()
+                        .find(|i| i.id.to_string() == reference || i.stored_name == reference)
+                        .map(|i| i.id)
+                        .ok_or(format!("image \"{reference}\" not found"))
+                };
+                let image_ids = record
+                    .images
+                    .iter()
+                    .map(|r| find_image(r.trim()))
+                    .collect::<Result<Vec<_>, _>>()?;
+                let answer_image_ids = record
+                    .answers
+                    .iter()
+                    .map(|a| a.image.as_deref().map(|r| find_image(r.trim())).transpose())
+                    .collect::<Result<Vec<_>, _>>()?;
+                let category_paths = record
+                    .categories
+                    .iter()
+                    .map(|reference| category_index.resolve(reference))
+                    .collect::<Result<Vec<_>, _>>()?;
+                Ok((topic, image_ids, answer_image_ids, category_paths))
+            });
+
+        match checked {
+            Ok((topic, image_ids, answer_image_ids, category_paths)) => {
+                // Недостающие категории создаются вместе с родителями
+                for path in &category_paths {
+                    for (end, _) in path
+                        .match_indices(CATEGORY_PATH_SEPARATOR)
+                        .chain([(path.len(), "")])
+                    {
+                        if !category_ids.contains_key(&path[..end]) {
+                            new_categories.insert(path[..end].to_string());
+                        }
+                    }
+                }
+                resolved.push(ResolvedRecord {
+                    existing: existing.remove(&external_id),
+                    record,
+                    topic,
+                    image_ids,
+                    answer_image_ids,
+                    category_paths,
+                });
+            }
+            Err(message) => issues.push(ImportIssue {
+                section: "questions".to_string(),
+                row,
+                external_id: Some(external_id),
+                message,
+            }),
+        }
+    }
+
+    issues.sort_by_key(|issue| {
+        let section = ["topics", "lessons", "questions"]
+            .iter()
+            .position(|s| *s == issue.section);
+        (section, issue.row)
+    });
+    let updated = resolved.iter().filter(|r| r.existing.is_some()).count();
+    let mut report = ImportReport {
+        total,
+        created: resolved.len() - updated,
+        updated,
+        new_categories: new_categories.iter().cloned().collect(),
+        new_topics: new_topics.iter().cloned().collect(),
+        lessons: resolved_lessons.len(),
+        errors: issues,
+        committed: false,
+    };
+
+    if !commit || !report.errors.is_empty() {
+        return Ok(report);
+    }
+
+    let txn = db.begin().await?;
+
+    let mut topic_ids: HashMap<String, Uuid> = HashMap::new();
+    for (existing, topic) in resolved_topics {
+        match existing {
+            Some(model) => {
+                let mut to_update = model.into_active_model();
+                to_update.difficulty = Set(topic.difficulty);
+                to_update.duration = Set(topic.duration);
+                to_update.subscription_required = Set(topic.subscription_required);
+                to_update.update(&txn).await?;
+            }
+            None => {
+                let model = topics::ActiveModel {
+                    name: Set(topic.name.trim().to_string()),
+                    difficulty: Set(topic.difficulty),
+                    duration: Set(topic.duration),
+                    subscription_required: Set(topic.subscription_required),
+                    ..Default::default()
+                }
+                .insert(&txn)
+                .await?;
+                topic_ids.insert(model.name, model.id);
+            }
+        }
+    }
+
+    for (topic, title, lesson) in resolved_lessons {
+        let topic_id = topic.id(&topic_ids);
+        let reading_minutes = estimate_reading_minutes(
+            std::iter::once(lesson.content.as_str())
+                .chain(lesson.sections.iter().map(|s| s.content.as_str())),
+        );
+        let sections = lesson
+            .sections
+            .into_iter()
+            .map(|section| LessonSectionParams {
+                title: section.title,
+                content: section.content,
+                translations: Vec::new(),
+            })
+            .collect();
+        let current = lessons::Entity::find()
+            .filter(lessons::Column::TopicId.eq(topic_id))
+            .filter(lessons::Column::Title.eq(&title))
+            .one(&txn)
+            .await?;
+        let content_html = render_markdown(&txn, &lesson.content).await?;
+        let previous_status = current.as_ref().map(|model| model.status.clone());
+        let lesson_id = match current {
+            Some(model) => {
+                let mut to_update = model.into_active_model();
+                to_update.content = Set(lesson.content);
+                to_update.content_html = Set(Some(content_html));
+                to_update.reading_minutes = Set(reading_minutes);
+                if let Some(position) = lesson.position {
+                    to_update.position = Set(position);
+                }
+                if let Some(status) = lesson.status.clone() {
+                    to_update.status = Set(status);
+                }
+                to_update.update(&txn).await?.id
+            }
+            None => {
+                let position = match lesson.position {
+                    Some(position) => position,
+                    None => next_position(&txn, topic_id).await?,
+                };
+                lessons::ActiveModel {
+                    topic_id: Set(topic_id),
+                    title: Set(title),
+                    position: Set(position),
+                    reading_minutes: Set(reading_minutes),
+                    content: Set(lesson.content),
+                    content_html: Set(Some(content_html)),
+                    status: Set(lesson.status.clone().unwrap_or("draft".to_string())),
+                    ..Default::default()
+                }
+                .insert(&txn)
+                .await?
+                .id
+            }
+        };
+        replace_sections(&txn, lesson_id, sections).await?;
+        if let Some(status) = imported_status(previous_status, lesson.status) {
+            log_review(
+                &txn, "lesson", lesson_id, author_id, "import", &status, None,
+            )
+            .await?;
+        }
+    }
+
+    // Родитель в упорядоченном наборе идёт раньше своих потомков
+    for path in new_categories {
+        let (parent_id, name) = match path.rsplit_once(CATEGORY_PATH_SEPARATOR) {
+            Some((parent, name)) => (category_ids.get(parent).copied(), name.to_string()),
+            None => (None, path.clone()),
+        };
+        let category = categories::ActiveModel {
+            name: Set(name),
+            parent_id: Set(parent_id),
+            ..Default::default()
+        }
+        .insert(&txn)
+        .await?;
+        category_ids.insert(path, category.id);
+    }
+
+    let mut question_ids: HashMap<String, Uuid> = group_anchors;
+    let mut group_links: Vec<(Uuid, String)> = Vec::new();
+    for item in resolved {
+        let previous_status = item.existing.as_ref().map(|q| q.status.clone());
+        let status = item.record.status.clone();
+        let external_id = item.record.external_id.trim().to_string();
+        let translation_group = item.record.translation_group.clone();
+        let question_id = upsert_question(&txn, item, &topic_ids, &category_ids).await?;
+        question_ids.insert(external_id, question_id);
+        if let Some(key) = translation_group {
+            group_links.push((question_id, key.trim().to_string()));
+        }
+        record_revision(&txn, question_id, author_id, "import").await?;
+        if let Some(status) = imported_status(previous_status, status) {
+            log_review(
+                &txn,
+                "question",
+                question_id,
+                author_id,
+                "import",
+                &status,
+                None,
+            )
+            .await?;
+        }
+    }
+
+    // Группы связываются, когда все вопросы файла уже сохранены
+    for (question_id, key) in group_links {
+        let anchor_id = question_ids[&key];
+        if anchor_id == question_id {
+            continue;
+        }
+        let Some(anchor) = questions::Entity::find_by_id(anchor_id).one(&txn).await? else {
+            continue;
+        };
+        if let Some(question) = questions::Entity::find_by_id(question_id).one(&txn).await? {
+            let mut to_update = question.into_active_model();
+            to_update.translation_group_id = Set(anchor.translation_group_id);
+            to_update.update(&txn).await?;
+        }
+    }
+
+    txn.commit().await?;
+    report.committed = true;
+
+    Ok(report)
+}
+
+/// `drive_mind import-questions --file bank.csv [--commit]`
+#[derive(Parser, Debug)]
+#[command(
+    name = "import-questions",
+    about = "Import questions from a CSV or JSON file"
+)]
+pub struct ImportCli {
+    /// Path to the import file
+    #[arg(long)]
+    pub file: PathBuf,
+    /// "csv" or "json", taken from the file extension by default
+    #[arg(long)]
+    pub format: Option<String>,
+    /// Write the records, otherwise only the validation report is printed
+    #[arg(long, default_value_t = false)]
+    pub commit: bool,
+    #[command(flatten)]
+    pub db: DBConfig,
+}
+
+pub async fn run_cli(
+    args: impl Iterator<Item = String>,
+) -> Result<ImportReport, Box<dyn std::error::Error>> {
+    let cli = ImportCli::parse_from(args);
+
+    let format = cli
+        .format
+        .or_else(|| {
+            cli.file
+                .extension()
+                .and_then(|ext| ext.to_str())
+                .map(str::to_lowercase)
+        })
+        .unwrap_or_default();
+    let data = tokio::fs::read_to_string(&cli.file).await?;
+    let parsed = parse_records(&format, &data)?;
+
+    let db = cli.db.connect().await;
+    Ok(import_records(&db, parsed, cli.commit, None).await?)
+}
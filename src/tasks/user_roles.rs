@@ -0,0 +1,47 @@
+use clap::Parser;
+use sea_orm::{
+    ActiveModelTrait, ActiveValue::Set, ColumnTrait, EntityTrait, IntoActiveModel, QueryFilter,
+};
+
+use crate::{entities::users, models::users::ROLES, utils::config::DBConfig};
+
+/// `drive_mind grant-role --email admin@example.com --role admin`
+#[derive(Parser, Debug)]
+#[command(
+    name = "grant-role",
+    about = "Set the role of a user, e.g. to appoint the first admin"
+)]
+pub struct GrantRoleCli {
+    #[arg(long)]
+    pub email: String,
+    /// "learner", "editor" or "admin"
+    #[arg(long)]
+    pub role: String,
+    #[command(flatten)]
+    pub db: DBConfig,
+}
+
+pub async fn run_cli(args: impl Iterator<Item = String>) -> Result<(), Box<dyn std::error::Error>> {
+    let cli = GrantRoleCli::parse_from(args);
+    if !ROLES.contains(&cli.role.as_str()) {
+        return Err(format!(
+            "unknown role \"{}\", expected {}",
+            cli.role,
+            ROLES.join(", ")
+        )
+        .into());
+    }
+
+    let db = cli.db.connect().await;
+    let user = users::Entity::find()
+        .filter(users::Column::Email.eq(cli.email.trim()))
+        .one(&db)
+        .await?
+        .ok_or(format!("user \"{}\" not found", cli.email))?;
+
+    let mut to_update = user.into_active_model();
+    to_update.role = Set(cli.role);
+    to_update.update(&db).await?;
+
+    Ok(())
+}
@@ -1,4 +1,5 @@
 use std::net::{IpAddr, SocketAddr};
+use std::sync::LazyLock;
 use std::{fmt::Debug, net::Ipv4Addr};
 
 use clap::Parser;
@@ -7,6 +8,11 @@ use serde::{Deserialize, Serialize};
 
 use super::db::{apply_migrations, create_sockets_connection};
 
+/// Topics open only once their prerequisites are met
+pub static CURRICULUM_MODE: LazyLock<bool> = LazyLock::new(|| {
+    std::env::var("CURRICULUM_MODE").is_ok_and(|value| matches!(value.as_str(), "true" | "1"))
+});
+
 #[derive(Parser, Debug, Clone)]
 pub struct DBConfig {
     #[arg(long, env("DATABASE_URL"))]
@@ -35,10 +41,10 @@ impl DBConfig {
 
     pub async fn connect(&self) -> DatabaseConnection {
         let db = create_sockets_connection(self.clone()).await;
-        if self.auto_migrate {
-            if let Err(err) = apply_migrations(&db).await {
-                panic!("{:?}", err);
-            }
+        if self.auto_migrate
+            && let Err(err) = apply_migrations(&db).await
+        {
+            panic!("{:?}", err);
         }
         db
     }
@@ -24,8 +24,8 @@ pub async fn create_sockets_connection(config: DBConfig) -> DatabaseConnection {
 }
 
 pub async fn apply_migrations(db: &DatabaseConnection) -> Result<(), DbErr> {
-    let schema_manager = SchemaManager::new(&*db); // To investigate the schema
-    crate::migrations::Migrator::up(&*db, None).await?;
+    let schema_manager = SchemaManager::new(db); // To investigate the schema
+    crate::migrations::Migrator::up(db, None).await?;
     assert!(schema_manager.has_table("users").await?);
 
     Ok(())
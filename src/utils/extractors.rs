@@ -1,13 +1,14 @@
 use axum::{
-    extract::{FromRef, FromRequestParts},
-    http::request::Parts,
+    extract::{FromRef, FromRequestParts, OptionalFromRequestParts},
+    http::{header::AUTHORIZATION, request::Parts},
 };
 use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter};
+use std::collections::HashSet;
 use uuid::Uuid;
 
 use crate::{
     AppContext,
-    entities::{topics, user_subscriptions, users},
+    entities::{questions, test_template_questions, topics, user_subscriptions, users},
     utils::{jwt::Claims, response::ApiError},
 };
 
@@ -37,15 +38,64 @@ where
     }
 }
 
-pub async fn check_topic_access(
-    db: &DatabaseConnection,
-    user_id: Uuid,
-    topic: &topics::Model,
-) -> Result<(), ApiError> {
-    if !topic.subscription_required {
-        return Ok(());
+/// Anonymous requests get `None`, a request with a bad token is still rejected
+impl<S> OptionalFromRequestParts<S> for AuthUser
+where
+    S: Send + Sync,
+    AppContext: FromRef<S>,
+{
+    type Rejection = ApiError;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &S,
+    ) -> Result<Option<Self>, Self::Rejection> {
+        if !parts.headers.contains_key(AUTHORIZATION) {
+            return Ok(None);
+        }
+        <AuthUser as FromRequestParts<S>>::from_request_parts(parts, state)
+            .await
+            .map(Some)
+    }
+}
+
+/// Authenticated user with admin rights (content editors, instructors)
+pub struct AdminUser {
+    pub user: users::Model,
+}
+
+impl<S> FromRequestParts<S> for AdminUser
+where
+    S: Send + Sync,
+    AppContext: FromRef<S>,
+{
+    type Rejection = ApiError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let claims = Claims::from_request_parts(parts, state).await?;
+
+        let ctx = AppContext::from_ref(state);
+
+        let user = users::Entity::find_by_id(claims.id)
+            .one(&ctx.db)
+            .await
+            .map_err(ApiError::from)?
+            .ok_or(ApiError::UserNotFound)?;
+
+        // Роль берётся из базы, а не из токена, чтобы снятие прав действовало сразу
+        if !user.is_editor() {
+            return Err(ApiError::InsufficientPermissions);
+        }
+
+        Ok(AdminUser { user })
     }
+}
 
+/// Whether the user has a paid subscription that has not expired
+pub async fn has_active_subscription(
+    db: &DatabaseConnection,
+    user_id: Uuid,
+) -> Result<bool, ApiError> {
     let now = chrono::Utc::now();
     let subscription = user_subscriptions::Entity::find()
         .filter(user_subscriptions::Column::UserId.eq(user_id))
@@ -55,7 +105,15 @@ pub async fn check_topic_access(
         .await
         .map_err(ApiError::from)?;
 
-    if subscription.is_some() {
+    Ok(subscription.is_some())
+}
+
+pub async fn check_topic_access(
+    db: &DatabaseConnection,
+    user_id: Uuid,
+    topic: &topics::Model,
+) -> Result<(), ApiError> {
+    if !topic.subscription_required || has_active_subscription(db, user_id).await? {
         Ok(())
     } else {
         Err(ApiError::PaymentRequired)
@@ -77,3 +135,34 @@ pub async fn check_topic_access_by_id(
 
     Ok(topic)
 }
+
+/// Checks access to every topic used by the template questions and returns them
+pub async fn check_template_access(
+    db: &DatabaseConnection,
+    user_id: Uuid,
+    template_id: Uuid,
+) -> Result<HashSet<Uuid>, ApiError> {
+    let question_ids: Vec<Uuid> = test_template_questions::Entity::find()
+        .filter(test_template_questions::Column::TemplateId.eq(template_id))
+        .all(db)
+        .await
+        .map_err(ApiError::from)?
+        .into_iter()
+        .map(|tq| tq.question_id)
+        .collect();
+
+    let topic_ids: HashSet<Uuid> = questions::Entity::find()
+        .filter(questions::Column::Id.is_in(question_ids))
+        .all(db)
+        .await
+        .map_err(ApiError::from)?
+        .into_iter()
+        .map(|q| q.topic_id)
+        .collect();
+
+    for &topic_id in &topic_ids {
+        check_topic_access_by_id(db, user_id, topic_id).await?;
+    }
+
+    Ok(topic_ids)
+}
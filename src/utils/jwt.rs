@@ -38,7 +38,14 @@ where
             .await
             .map_err(|_| ApiError::TokenMissing)?;
         // Decode the user data
-        let token_data = decode::<Claims>(bearer.token(), &KEYS.decoding, &Validation::default())
+        Claims::from_token(bearer.token())
+    }
+}
+
+impl Claims {
+    /// Decodes claims from a raw token, e.g. one passed in a query string
+    pub fn from_token(token: &str) -> Result<Self, ApiError> {
+        let token_data = decode::<Claims>(token, &KEYS.decoding, &Validation::default())
             .map_err(|_| ApiError::InvalidToken)?;
 
         Ok(token_data.claims)
@@ -0,0 +1,119 @@
+use std::collections::{HashMap, HashSet};
+use std::convert::Infallible;
+
+use axum::{
+    extract::{FromRequestParts, Query},
+    http::{header::ACCEPT_LANGUAGE, request::Parts},
+};
+use serde::Deserialize;
+use utoipa::IntoParams;
+
+use crate::utils::response::ApiError;
+
+/// Longest language chain taken from a request
+const MAX_LANGS: usize = 10;
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct ContentLangQuery {
+    /// Preferred language (e.g., "en", "ru"), the Accept-Language header is used without it
+    pub lang: Option<String>,
+}
+
+/// Languages the client asked for, most preferred first.
+///
+/// Built from the `lang` query parameter followed by the Accept-Language
+/// entries by weight; a regional tag is followed by its base language
+/// ("de-at", then "de"). Content without a translation in any of them falls
+/// back to the original text.
+#[derive(Default)]
+pub struct PreferredLangs(pub Vec<String>);
+
+impl<S> FromRequestParts<S> for PreferredLangs
+where
+    S: Send + Sync,
+{
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let mut tags = Vec::new();
+
+        if let Ok(Query(ContentLangQuery { lang: Some(lang) })) = Query::try_from_uri(&parts.uri) {
+            tags.push(lang);
+        }
+
+        if let Some(header) = parts
+            .headers
+            .get(ACCEPT_LANGUAGE)
+            .and_then(|value| value.to_str().ok())
+        {
+            tags.extend(accept_language(header));
+        }
+
+        Ok(PreferredLangs::new(tags))
+    }
+}
+
+impl PreferredLangs {
+    fn new(tags: impl IntoIterator<Item = String>) -> Self {
+        let mut langs: Vec<String> = Vec::new();
+        for tag in tags {
+            let tag = tag.trim().to_lowercase();
+            if tag.is_empty() || tag == "*" {
+                continue;
+            }
+            let base = tag.split('-').next().map(str::to_string);
+            for lang in std::iter::once(tag).chain(base) {
+                if !langs.contains(&lang) {
+                    langs.push(lang);
+                }
+            }
+        }
+        langs.truncate(MAX_LANGS);
+        Self(langs)
+    }
+
+    /// First language of the chain with an entry in `translations`
+    pub fn pick<'a, T>(
+        &self,
+        translations: Option<&'a HashMap<String, T>>,
+    ) -> Option<(&'a str, &'a T)> {
+        let translations = translations?;
+        self.0.iter().find_map(|lang| {
+            translations
+                .get_key_value(lang)
+                .map(|(lang, value)| (lang.as_str(), value))
+        })
+    }
+}
+
+/// Language tags of an Accept-Language header by descending weight
+fn accept_language(header: &str) -> Vec<String> {
+    let mut weighted: Vec<(f32, String)> = header
+        .split(',')
+        .filter_map(|entry| {
+            let mut parts = entry.split(';');
+            let tag = parts.next()?.trim().to_string();
+            let weight = parts
+                .filter_map(|param| param.trim().strip_prefix("q="))
+                .find_map(|q| q.parse::<f32>().ok())
+                .unwrap_or(1.0);
+            (weight > 0.0).then_some((weight, tag))
+        })
+        .collect();
+    // Стабильная сортировка сохраняет порядок тегов с одинаковым весом
+    weighted.sort_by(|a, b| b.0.total_cmp(&a.0));
+    weighted.into_iter().map(|(_, tag)| tag).collect()
+}
+
+/// Rejects a list of translations naming the same language twice
+pub fn check_translation_langs<'a>(
+    langs: impl IntoIterator<Item = &'a str>,
+) -> Result<(), ApiError> {
+    let mut seen = HashSet::new();
+    for lang in langs {
+        if !seen.insert(lang.to_lowercase()) {
+            return Err(ApiError::InvalidFieldValue);
+        }
+    }
+    Ok(())
+}
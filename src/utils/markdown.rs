@@ -0,0 +1,212 @@
+use std::collections::{HashMap, HashSet};
+
+use pulldown_cmark::{CowStr, Event, Options, Parser, Tag, html};
+use sea_orm::{ColumnTrait, ConnectionTrait, DbErr, EntityTrait, QueryFilter};
+use uuid::Uuid;
+
+use crate::{
+    entities::{images, questions, road_signs},
+    utils::response::ApiError,
+};
+
+/// Prefix of uploaded image URLs, see `rest::images::image_url`
+const IMAGE_PREFIX: &str = "/api/images/file/";
+
+/// Link to a question: `[text](question:<uuid>)`
+const QUESTION_SCHEME: &str = "question:";
+
+/// Link to a road sign by code: `[text](sign:1.1)`
+const SIGN_SCHEME: &str = "sign:";
+
+fn options() -> Options {
+    Options::ENABLE_TABLES | Options::ENABLE_STRIKETHROUGH
+}
+
+/// Uploaded images and internal links used by a Markdown text
+#[derive(Debug, Default)]
+pub struct MarkdownRefs {
+    /// Stored names of embedded uploads
+    pub images: HashSet<String>,
+    pub questions: HashSet<Uuid>,
+    /// Sign codes
+    pub signs: HashSet<String>,
+    /// Internal links that could not be read, such as `question:abc`
+    pub malformed: Vec<String>,
+}
+
+impl MarkdownRefs {
+    pub fn collect<'a>(sources: impl IntoIterator<Item = &'a str>) -> Self {
+        let mut refs = Self::default();
+        for source in sources {
+            for event in Parser::new_ext(source, options()) {
+                match event {
+                    Event::Start(Tag::Image { dest_url, .. }) => {
+                        if let Some(name) = dest_url.strip_prefix(IMAGE_PREFIX) {
+                            refs.images.insert(name.to_string());
+                        }
+                    }
+                    Event::Start(Tag::Link { dest_url, .. }) => {
+                        if let Some(id) = dest_url.strip_prefix(QUESTION_SCHEME) {
+                            match id.parse() {
+                                Ok(id) => {
+                                    refs.questions.insert(id);
+                                }
+                                Err(_) => refs.malformed.push(dest_url.to_string()),
+                            }
+                        } else if let Some(code) = dest_url.strip_prefix(SIGN_SCHEME) {
+                            refs.signs.insert(code.to_string());
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+        refs
+    }
+}
+
+/// Targets of the references that exist in the database
+pub struct ResolvedRefs {
+    images: HashSet<String>,
+    questions: HashSet<Uuid>,
+    /// Sign ID by code
+    signs: HashMap<String, Uuid>,
+}
+
+impl ResolvedRefs {
+    pub async fn load<C: ConnectionTrait>(db: &C, refs: &MarkdownRefs) -> Result<Self, DbErr> {
+        let images = if refs.images.is_empty() {
+            HashSet::new()
+        } else {
+            images::Entity::find()
+                .filter(images::Column::StoredName.is_in(refs.images.iter().cloned()))
+                .all(db)
+                .await?
+                .into_iter()
+                .map(|image| image.stored_name)
+                .collect()
+        };
+        let questions = if refs.questions.is_empty() {
+            HashSet::new()
+        } else {
+            questions::Entity::find()
+                .filter(questions::Column::Id.is_in(refs.questions.iter().cloned()))
+                .all(db)
+                .await?
+                .into_iter()
+                .map(|question| question.id)
+                .collect()
+        };
+        let signs = if refs.signs.is_empty() {
+            HashMap::new()
+        } else {
+            road_signs::Entity::find()
+                .filter(road_signs::Column::Code.is_in(refs.signs.iter().cloned()))
+                .all(db)
+                .await?
+                .into_iter()
+                .map(|sign| (sign.code, sign.id))
+                .collect()
+        };
+        Ok(Self {
+            images,
+            questions,
+            signs,
+        })
+    }
+
+    /// References of `refs` that point nowhere, as written in the text
+    pub fn missing(&self, refs: &MarkdownRefs) -> Vec<String> {
+        let mut missing = refs.malformed.clone();
+        missing.extend(
+            refs.images
+                .iter()
+                .filter(|name| !self.images.contains(*name))
+                .map(|name| format!("{IMAGE_PREFIX}{name}")),
+        );
+        missing.extend(
+            refs.questions
+                .iter()
+                .filter(|id| !self.questions.contains(id))
+                .map(|id| format!("{QUESTION_SCHEME}{id}")),
+        );
+        missing.extend(
+            refs.signs
+                .iter()
+                .filter(|code| !self.signs.contains_key(*code))
+                .map(|code| format!("{SIGN_SCHEME}{code}")),
+        );
+        missing.sort();
+        missing
+    }
+
+    /// API path of an internal link, `None` for external links
+    fn link(&self, dest_url: &str) -> Option<String> {
+        if let Some(id) = dest_url.strip_prefix(QUESTION_SCHEME) {
+            let id = id.parse().ok().filter(|id| self.questions.contains(id));
+            return Some(
+                id.map(|id| format!("/api/questions/{id}"))
+                    .unwrap_or_default(),
+            );
+        }
+        if let Some(code) = dest_url.strip_prefix(SIGN_SCHEME) {
+            let id = self.signs.get(code);
+            return Some(
+                id.map(|id| format!("/api/road-signs/{id}"))
+                    .unwrap_or_default(),
+            );
+        }
+        None
+    }
+
+    /// Sanitized HTML of a Markdown text. Internal links point to the API,
+    /// unresolved ones lose their target.
+    pub fn render(&self, source: &str) -> String {
+        let events = Parser::new_ext(source, options()).map(|event| match event {
+            Event::Start(Tag::Link {
+                link_type,
+                dest_url,
+                title,
+                id,
+            }) => {
+                let dest_url = match self.link(&dest_url) {
+                    Some(path) => CowStr::from(path),
+                    None => dest_url,
+                };
+                Event::Start(Tag::Link {
+                    link_type,
+                    dest_url,
+                    title,
+                    id,
+                })
+            }
+            event => event,
+        });
+
+        let mut unsafe_html = String::new();
+        html::push_html(&mut unsafe_html, events);
+        ammonia::clean(&unsafe_html)
+    }
+}
+
+/// HTML of a Markdown text, references that point nowhere are left unresolved
+pub async fn render_markdown<C: ConnectionTrait>(db: &C, source: &str) -> Result<String, DbErr> {
+    let refs = MarkdownRefs::collect([source]);
+    Ok(ResolvedRefs::load(db, &refs).await?.render(source))
+}
+
+/// Fails when a text embeds an unknown image or links to a missing question or sign
+pub async fn check_markdown<'a, C: ConnectionTrait>(
+    db: &C,
+    sources: impl IntoIterator<Item = &'a str>,
+) -> Result<(), ApiError> {
+    let refs = MarkdownRefs::collect(sources);
+    let resolved = ResolvedRefs::load(db, &refs)
+        .await
+        .map_err(ApiError::from)?;
+    if resolved.missing(&refs).is_empty() {
+        Ok(())
+    } else {
+        Err(ApiError::InvalidFieldValue)
+    }
+}
@@ -2,5 +2,8 @@ pub mod config;
 pub mod db;
 pub mod extractors;
 pub mod jwt;
+pub mod lang;
+pub mod markdown;
 pub mod password;
 pub mod response;
+pub mod scoring;
@@ -0,0 +1,245 @@
+use std::collections::HashSet;
+use std::str::FromStr;
+
+use rust_decimal::{Decimal, prelude::ToPrimitive};
+use uuid::Uuid;
+
+use crate::{
+    entities::test_question_options,
+    models::question_types::{AnswerPayload, QuestionType, SubmittedAnswer},
+};
+
+/// Outcome of a single answer before the scoring policy is applied
+pub struct AnswerSubmission {
+    /// Points the question is worth
+    pub points: i16,
+    /// Parts the answer needs to get right: correct options, items to order
+    /// or 1 for numeric and hotspot questions
+    pub expected: usize,
+    /// Parts the answer got right
+    pub hits: usize,
+    /// Wrong picks, penalized by negative marking
+    pub misses: usize,
+}
+
+impl AnswerSubmission {
+    /// Selected options compared with the correct set
+    pub fn from_selection(
+        points: i16,
+        correct_ids: &HashSet<Uuid>,
+        selected_ids: &HashSet<Uuid>,
+    ) -> Self {
+        Self {
+            points,
+            expected: correct_ids.len(),
+            hits: selected_ids.intersection(correct_ids).count(),
+            misses: selected_ids.difference(correct_ids).count(),
+        }
+    }
+
+    /// Grades an answer against the snapshotted options of a test question.
+    /// Options the question does not have are ignored.
+    pub fn grade(
+        question_type: QuestionType,
+        points: i16,
+        options: &[test_question_options::Model],
+        answer: &SubmittedAnswer,
+    ) -> Self {
+        let payloads: Vec<(&test_question_options::Model, Option<AnswerPayload>)> = options
+            .iter()
+            .map(|o| (o, AnswerPayload::from_json(o.payload.as_ref())))
+            .collect();
+
+        match question_type {
+            QuestionType::Choice | QuestionType::TrueFalse => {
+                let option_ids: HashSet<Uuid> = options.iter().map(|o| o.answer_id).collect();
+                let correct_ids: HashSet<Uuid> = options
+                    .iter()
+                    .filter(|o| o.is_correct)
+                    .map(|o| o.answer_id)
+                    .collect();
+                let selected_ids: HashSet<Uuid> = answer
+                    .answer_ids
+                    .iter()
+                    .filter(|id| option_ids.contains(id))
+                    .cloned()
+                    .collect();
+                Self::from_selection(points, &correct_ids, &selected_ids)
+            }
+            QuestionType::Ordering => {
+                let mut correct_order: Vec<(i16, Uuid)> = payloads
+                    .iter()
+                    .filter_map(|(o, payload)| match payload {
+                        Some(AnswerPayload::Ordering { position }) => {
+                            Some((*position, o.answer_id))
+                        }
+                        _ => None,
+                    })
+                    .collect();
+                correct_order.sort_unstable();
+
+                // Каждый элемент на своём месте засчитывается отдельно
+                let hits = correct_order
+                    .iter()
+                    .zip(answer.answer_ids.iter())
+                    .filter(|((_, expected), given)| expected == *given)
+                    .count();
+                Self {
+                    points,
+                    expected: correct_order.len(),
+                    hits,
+                    misses: 0,
+                }
+            }
+            QuestionType::Numeric => {
+                let hit = answer.numeric_value.is_some_and(|given| {
+                    payloads.iter().any(|(o, payload)| match payload {
+                        Some(AnswerPayload::Numeric { value, tolerance }) => {
+                            o.is_correct && (given - value).abs() <= *tolerance
+                        }
+                        _ => false,
+                    })
+                });
+                Self::single(points, hit, answer.numeric_value.is_some())
+            }
+            QuestionType::Hotspot => {
+                let hit = answer.point.is_some_and(|point| {
+                    payloads.iter().any(|(o, payload)| match payload {
+                        Some(AnswerPayload::Hotspot {
+                            x,
+                            y,
+                            width,
+                            height,
+                        }) => {
+                            o.is_correct
+                                && (*x..=x + width).contains(&point.x)
+                                && (*y..=y + height).contains(&point.y)
+                        }
+                        _ => false,
+                    })
+                });
+                Self::single(points, hit, answer.point.is_some())
+            }
+        }
+    }
+
+    /// Question with a single right or wrong answer
+    fn single(points: i16, hit: bool, answered: bool) -> Self {
+        Self {
+            points,
+            expected: 1,
+            hits: usize::from(hit),
+            misses: usize::from(answered && !hit),
+        }
+    }
+
+    /// Answer fully matching the expected one
+    pub fn is_exact(&self) -> bool {
+        self.hits == self.expected && self.misses == 0
+    }
+
+    /// Share of the expected parts earned by the answer: every hit adds
+    /// `1 / expected`, every miss subtracts the same amount
+    fn pick_ratio(&self) -> Decimal {
+        if self.expected == 0 {
+            return if self.misses == 0 {
+                Decimal::ONE
+            } else {
+                Decimal::ZERO
+            };
+        }
+
+        Decimal::from(self.hits as i64 - self.misses as i64) / Decimal::from(self.expected as i64)
+    }
+}
+
+/// Grading rule applied to every answered question of a test
+pub trait ScoringStrategy: Send + Sync {
+    /// Points earned for the submission, may be negative
+    fn score(&self, submission: &AnswerSubmission) -> Decimal;
+}
+
+/// Full points only when the selection matches the correct set exactly
+pub struct ExactMatch;
+
+impl ScoringStrategy for ExactMatch {
+    fn score(&self, submission: &AnswerSubmission) -> Decimal {
+        if submission.is_exact() {
+            Decimal::from(submission.points)
+        } else {
+            Decimal::ZERO
+        }
+    }
+}
+
+/// Proportional credit for correct picks minus wrong picks, never below zero
+pub struct PartialCredit;
+
+impl ScoringStrategy for PartialCredit {
+    fn score(&self, submission: &AnswerSubmission) -> Decimal {
+        let ratio = submission.pick_ratio().clamp(Decimal::ZERO, Decimal::ONE);
+        (Decimal::from(submission.points) * ratio).round_dp(2)
+    }
+}
+
+/// Like partial credit, but wrong picks can take the question below zero
+pub struct NegativeMarking;
+
+impl ScoringStrategy for NegativeMarking {
+    fn score(&self, submission: &AnswerSubmission) -> Decimal {
+        let ratio = submission
+            .pick_ratio()
+            .clamp(Decimal::NEGATIVE_ONE, Decimal::ONE);
+        (Decimal::from(submission.points) * ratio).round_dp(2)
+    }
+}
+
+/// Scoring policy stored on `tests.scoring_policy`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ScoringPolicy {
+    #[default]
+    Exact,
+    Partial,
+    Negative,
+}
+
+impl ScoringPolicy {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ScoringPolicy::Exact => "exact",
+            ScoringPolicy::Partial => "partial",
+            ScoringPolicy::Negative => "negative",
+        }
+    }
+
+    pub fn strategy(&self) -> &'static dyn ScoringStrategy {
+        match self {
+            ScoringPolicy::Exact => &ExactMatch,
+            ScoringPolicy::Partial => &PartialCredit,
+            ScoringPolicy::Negative => &NegativeMarking,
+        }
+    }
+}
+
+impl FromStr for ScoringPolicy {
+    type Err = ();
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "exact" => Ok(ScoringPolicy::Exact),
+            "partial" => Ok(ScoringPolicy::Partial),
+            "negative" => Ok(ScoringPolicy::Negative),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Earned points as a percentage of the maximum, floored at zero
+pub fn score_percent(earned: Decimal, max_points: i16) -> i16 {
+    if max_points <= 0 {
+        return 0;
+    }
+
+    let percent = earned * Decimal::ONE_HUNDRED / Decimal::from(max_points);
+    percent.max(Decimal::ZERO).trunc().to_i16().unwrap_or(0)
+}